@@ -0,0 +1,127 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use kzg_impl::kzg_commit::KZG;
+use kzg_impl::polynomial::{Fr, Polynomial};
+use num_bigint::BigUint;
+use oblast_demo::{curve_order, Scalar, P1};
+
+const DEGREES: [usize; 4] = [16, 256, 1024, 4096];
+const TAU: [u8; 32] = [0x5au8; 32];
+
+/// a dense polynomial of the given degree with arbitrary non-zero coefficients
+fn polynomial(degree: usize) -> Polynomial {
+    Polynomial::from_coefficients((0..=degree as u64).map(|i| Fr::from_u64(i * 7 + 3)).collect())
+}
+
+/// the G1 powers the way the setup first computed them: a fresh `modpow` for every power
+fn modpow_g1_powers(tau: &[u8; 32], degree: usize) -> Vec<P1> {
+    let modulus = curve_order();
+    let tau = BigUint::from_bytes_be(tau);
+    (0..=degree)
+        .map(|i| {
+            let power = tau.modpow(&BigUint::from(i), &modulus).to_bytes_be();
+            let mut bytes = [0u8; 32];
+            bytes[32 - power.len()..].copy_from_slice(&power);
+            Scalar::from_fr_bytes(&bytes) * P1::generator()
+        })
+        .collect()
+}
+
+fn bench_setup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("setup");
+    group.sample_size(10);
+    for degree in DEGREES {
+        group.bench_with_input(BenchmarkId::from_parameter(degree), &degree, |b, &degree| {
+            b.iter(|| KZG::new(&TAU, degree).unwrap())
+        });
+    }
+
+    // the whole incremental setup against the G1 powers alone by modpow, at a degree where the
+    // modpows dominated
+    let degree = 8192;
+    group.bench_with_input(BenchmarkId::new("modpow", degree), &degree, |b, &degree| {
+        b.iter(|| modpow_g1_powers(&TAU, degree))
+    });
+    group.bench_with_input(BenchmarkId::new("incremental", degree), &degree, |b, &degree| {
+        b.iter(|| KZG::new(&TAU, degree).unwrap())
+    });
+    group.finish();
+}
+
+fn bench_commit(c: &mut Criterion) {
+    let mut group = c.benchmark_group("commit");
+    group.sample_size(10);
+    for degree in DEGREES {
+        let kzg = KZG::new(&TAU, degree).unwrap();
+        let polynomial = polynomial(degree);
+        group.bench_with_input(BenchmarkId::from_parameter(degree), &degree, |b, _| {
+            b.iter(|| KZG::commit(&kzg.public_parameter, &polynomial).unwrap().element)
+        });
+    }
+    group.finish();
+}
+
+fn bench_open(c: &mut Criterion) {
+    let mut group = c.benchmark_group("open");
+    group.sample_size(10);
+    for degree in DEGREES {
+        let kzg = KZG::new(&TAU, degree).unwrap();
+        let polynomial = polynomial(degree);
+        let commitment = KZG::commit(&kzg.public_parameter, &polynomial).unwrap();
+        let z = Fr::from_u64(123_456_789);
+        group.bench_with_input(BenchmarkId::from_parameter(degree), &degree, |b, _| {
+            b.iter(|| commitment.open_at(z).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_verify(c: &mut Criterion) {
+    let mut group = c.benchmark_group("verify");
+    for degree in DEGREES {
+        let kzg = KZG::new(&TAU, degree).unwrap();
+        let polynomial = polynomial(degree);
+        let commitment = KZG::commit(&kzg.public_parameter, &polynomial).unwrap();
+        let z = Fr::from_u64(123_456_789);
+        let opening = commitment.open_at(z).unwrap();
+        group.bench_with_input(BenchmarkId::from_parameter(degree), &degree, |b, _| {
+            b.iter(|| opening.verify(&z, &commitment))
+        });
+    }
+    group.finish();
+}
+
+/// schoolbook vs Newton division of a degree-2n polynomial by a degree-n one, to place
+/// `FAST_ARITHMETIC_THRESHOLD`
+fn bench_division(c: &mut Criterion) {
+    let mut group = c.benchmark_group("division");
+    for n in [16usize, 32, 64, 128, 256, 1024] {
+        let dividend = polynomial(2 * n);
+        let divisor = polynomial(n);
+        group.bench_with_input(BenchmarkId::new("naive", n), &n, |b, _| b.iter(|| dividend.div_rem_naive(&divisor)));
+        group.bench_with_input(BenchmarkId::new("newton", n), &n, |b, _| b.iter(|| dividend.div_rem_newton(&divisor)));
+    }
+    group.finish();
+}
+
+/// schoolbook vs FFT multiplication of two degree-n polynomials
+fn bench_multiplication(c: &mut Criterion) {
+    let mut group = c.benchmark_group("multiplication");
+    for n in [16usize, 32, 64, 128, 256, 1024] {
+        let a = polynomial(n);
+        let b_poly = polynomial(n);
+        group.bench_with_input(BenchmarkId::new("schoolbook", n), &n, |b, _| b.iter(|| &a * &b_poly));
+        group.bench_with_input(BenchmarkId::new("fft", n), &n, |b, _| b.iter(|| a.mul_fft(&b_poly)));
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_setup,
+    bench_commit,
+    bench_open,
+    bench_verify,
+    bench_multiplication,
+    bench_division
+);
+criterion_main!(benches);
@@ -0,0 +1,323 @@
+//! The pairing operations the protocol needs, behind a trait so the curve library can be swapped.
+//!
+//! [`PairingBackend`] names the scalar and point types and the handful of field and group
+//! operations the protocol uses. [`Oblast`] implements it with the `oblast_demo` types the rest of
+//! the crate is built on and is the default everywhere a backend parameter appears, so
+//! [`KZG`](crate::kzg_commit::KZG), [`PP`](crate::kzg_commit::PP),
+//! [`Commitment`](crate::kzg_commit::Commitment) and [`Opening`](crate::kzg_commit::Opening) still
+//! mean their `Oblast` versions. [`commit`], [`open`] and [`verify_opening`] are the protocol steps
+//! in backend-generic form, and `KZG::setup_with_backend`, `KZG::commit_with_backend`,
+//! `Commitment::open_with_backend` and `Commitment::verify_with_backend` run them for any backend.
+//! Serialization, batching and the other extensions are written against the `oblast_demo` types
+//! directly.
+
+use std::fmt::Debug;
+use std::ops::{Add, Mul, Sub};
+
+use oblast_demo::{verify_pairings, Fr, P1, P2};
+
+use crate::group;
+
+/// A pairing-friendly curve: scalars, the two source groups and the pairing check
+pub trait PairingBackend {
+    /// elements of the scalar field; setup, evaluation and the quotient of an opening only need
+    /// the ring operations
+    type Scalar: Copy
+        + Debug
+        + PartialEq
+        + Add<Output = Self::Scalar>
+        + Sub<Output = Self::Scalar>
+        + Mul<Output = Self::Scalar>;
+    /// points of the first source group, where commitments and proofs live
+    type G1: Copy + Debug;
+    /// points of the second source group, where `[tau]_2` lives
+    type G2: Copy + Debug;
+
+    /// the scalar `value`; `0` and `1` are what the protocol steps need
+    fn scalar_from_u64(value: u64) -> Self::Scalar;
+    fn g1_generator() -> Self::G1;
+    fn g2_generator() -> Self::G2;
+    /// the point at infinity of G1
+    fn g1_identity() -> Self::G1;
+    /// `a + b` for any two points, equal ones included
+    fn g1_add(a: &Self::G1, b: &Self::G1) -> Self::G1;
+    fn g1_neg(point: &Self::G1) -> Self::G1;
+    /// same as [`PairingBackend::g1_add`] for G2
+    fn g2_add(a: &Self::G2, b: &Self::G2) -> Self::G2;
+    fn g2_neg(point: &Self::G2) -> Self::G2;
+    fn g1_mul(scalar: &Self::Scalar, point: &Self::G1) -> Self::G1;
+    fn g2_mul(scalar: &Self::Scalar, point: &Self::G2) -> Self::G2;
+    /// `e(a1, a2) == e(b1, b2)`
+    fn pairings_match(a1: &Self::G1, a2: &Self::G2, b1: &Self::G1, b2: &Self::G2) -> bool;
+}
+
+/// BLS12-381 through `oblast_demo` (and `blst` underneath)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Oblast;
+
+impl PairingBackend for Oblast {
+    type Scalar = Fr;
+    type G1 = P1;
+    type G2 = P2;
+
+    fn scalar_from_u64(value: u64) -> Fr {
+        Fr::from_u64(value)
+    }
+
+    fn g1_generator() -> P1 {
+        P1::generator()
+    }
+
+    fn g2_generator() -> P2 {
+        P2::generator()
+    }
+
+    fn g1_identity() -> P1 {
+        P1::default()
+    }
+
+    /// through `blst`'s add-or-double, since the `+` of `oblast_demo` can't double
+    fn g1_add(a: &P1, b: &P1) -> P1 {
+        group::g1_add(a, b)
+    }
+
+    fn g1_neg(point: &P1) -> P1 {
+        -*point
+    }
+
+    fn g2_add(a: &P2, b: &P2) -> P2 {
+        group::g2_add(a, b)
+    }
+
+    fn g2_neg(point: &P2) -> P2 {
+        -*point
+    }
+
+    fn g1_mul(scalar: &Fr, point: &P1) -> P1 {
+        *scalar * *point
+    }
+
+    fn g2_mul(scalar: &Fr, point: &P2) -> P2 {
+        *scalar * *point
+    }
+
+    fn pairings_match(a1: &P1, a2: &P2, b1: &P1, b2: &P2) -> bool {
+        verify_pairings(*a1, *a2, *b1, *b2)
+    }
+}
+
+/// `sum coefficients[i] * basis[i]`, the commitment to a polynomial under the G1 powers of tau in
+/// `basis` (which must have at least as many points as there are coefficients)
+pub fn commit<B: PairingBackend>(basis: &[B::G1], coefficients: &[B::Scalar]) -> B::G1 {
+    assert!(coefficients.len() <= basis.len(), "more coefficients than basis points");
+    coefficients
+        .iter()
+        .zip(basis)
+        .fold(B::g1_identity(), |sum, (coefficient, point)| B::g1_add(&sum, &B::g1_mul(coefficient, point)))
+}
+
+/// the value at `z` of the polynomial with `coefficients`, and the commitment to
+/// `(f(x) - f(z)) / (x - z)` as its proof. The quotient comes out of Horner's rule: its
+/// coefficients are the partial sums of the evaluation, so `basis` needs one point fewer than
+/// there are coefficients
+pub fn open<B: PairingBackend>(basis: &[B::G1], coefficients: &[B::Scalar], z: &B::Scalar) -> (B::Scalar, B::G1) {
+    let mut quotient = vec![B::scalar_from_u64(0); coefficients.len().saturating_sub(1)];
+    let mut value = B::scalar_from_u64(0);
+    for (index, coefficient) in coefficients.iter().enumerate().rev() {
+        value = *coefficient + *z * value;
+        if index > 0 {
+            quotient[index - 1] = value;
+        }
+    }
+    (value, commit::<B>(basis, &quotient))
+}
+
+/// `coefficients` without the zero coefficients past the degree, the ones a commitment basis
+/// doesn't need to reach
+pub(crate) fn significant<B: PairingBackend>(coefficients: &[B::Scalar]) -> &[B::Scalar] {
+    let zero = B::scalar_from_u64(0);
+    let length = coefficients.iter().rposition(|coefficient| *coefficient != zero).map_or(0, |degree| degree + 1);
+    &coefficients[..length]
+}
+
+/// the opening check `e(C - [y]_1, [1]_2) == e(proof, [tau]_2 - [z]_2)`
+pub fn verify_opening<B: PairingBackend>(
+    commitment: &B::G1,
+    z: &B::Scalar,
+    value: &B::Scalar,
+    proof: &B::G1,
+    g2_tau: &B::G2,
+) -> bool {
+    let commitment_minus_y = B::g1_add(commitment, &B::g1_neg(&B::g1_mul(value, &B::g1_generator())));
+    let tau_minus_z = B::g2_add(g2_tau, &B::g2_neg(&B::g2_mul(z, &B::g2_generator())));
+    B::pairings_match(&commitment_minus_y, &B::g2_generator(), proof, &tau_minus_z)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg_commit::{KZGErrors, Opening, KZG};
+    use crate::polynomial::Polynomial;
+    use crate::scalar;
+
+    #[test]
+    fn default_backend_runs_the_opening_protocol() {
+        let pp = KZG::new(&[0x68u8; 32], 8).unwrap().public_parameter;
+        let polynomial = Polynomial::from(vec![1u64, 2, 3, 4, 5]);
+        let commitment = KZG::commit(&pp, &polynomial).unwrap();
+        assert_eq!(
+            commit::<Oblast>(&pp.points_in_g1, &polynomial.coefficients).compress(),
+            commitment.element.compress()
+        );
+
+        let z = Fr::from_u64(68);
+        let opening: Opening<Oblast> = commitment.open_at(z).unwrap();
+        assert!(opening.verify_with_backend(&z, &commitment.element, &pp.point_in_g2()));
+        assert!(!opening.verify_with_backend(&(z + Fr::from_u64(1)), &commitment.element, &pp.point_in_g2()));
+
+        // a generic opening built from the backend's types is the crate's opening
+        let generic = Opening::<Oblast> { value: opening.value, proof: opening.proof };
+        assert!(generic.verify(&z, &commitment));
+    }
+
+    #[test]
+    fn generic_api_matches_the_concrete_one() {
+        let secret = [0x69u8; 32];
+        let tau = scalar::fr_from_bytes_be(&secret);
+        let kzg = KZG::<Oblast>::setup_with_backend(&tau, 6).unwrap();
+        let concrete = KZG::new(&secret, 6).unwrap();
+        assert!(kzg == concrete);
+
+        let polynomial = Polynomial::from(vec![9u64, 0, 4, 1, 0, 0, 7]);
+        let commitment = KZG::commit_with_backend(&kzg.public_parameter, &polynomial).unwrap();
+        let expected = KZG::commit(&concrete.public_parameter, &polynomial).unwrap();
+        assert_eq!(commitment.element.compress(), expected.element.compress());
+
+        for z in [Fr::from_u64(0), Fr::from_u64(31), -tau] {
+            let opening = commitment.open_with_backend(z).unwrap();
+            let concrete_opening = expected.open_at(z).unwrap();
+            assert_eq!(opening.value, concrete_opening.value);
+            assert_eq!(opening.proof.compress(), concrete_opening.proof.compress());
+            assert!(commitment.verify_with_backend(&z, &opening));
+            assert!(!commitment.verify_with_backend(&(z + Fr::from_u64(1)), &opening));
+        }
+
+        // a constant claimed to open to its negation, with the identity as proof
+        let constant = Polynomial::from(vec![3u64]);
+        let commitment = KZG::commit_with_backend(&kzg.public_parameter, &constant).unwrap();
+        let forged = Opening::<Oblast> { value: -Fr::from_u64(3), proof: P1::default() };
+        assert!(!commitment.verify_with_backend(&Fr::from_u64(31), &forged));
+
+        let too_long = Polynomial::from(vec![1u64; 8]);
+        assert!(matches!(
+            KZG::commit_with_backend(&kzg.public_parameter, &too_long),
+            Err(KZGErrors::PolynomialDegreeTooLarge { degree: 7, max_degree: 6 })
+        ));
+        assert!(matches!(KZG::<Oblast>::setup_with_backend(&Fr::default(), 6), Err(KZGErrors::SecretMustBeNonZero)));
+    }
+
+    /// INSECURE: points are their discrete logs and the pairing multiplies them, so the generic
+    /// steps can be run over a second backend
+    #[derive(Clone, Copy, Debug)]
+    struct DiscreteLogs;
+
+    impl PairingBackend for DiscreteLogs {
+        type Scalar = Fr;
+        type G1 = Fr;
+        type G2 = Fr;
+
+        fn scalar_from_u64(value: u64) -> Fr {
+            Fr::from_u64(value)
+        }
+
+        fn g1_generator() -> Fr {
+            Fr::from_u64(1)
+        }
+
+        fn g2_generator() -> Fr {
+            Fr::from_u64(1)
+        }
+
+        fn g1_identity() -> Fr {
+            Fr::default()
+        }
+
+        fn g1_add(a: &Fr, b: &Fr) -> Fr {
+            *a + *b
+        }
+
+        fn g1_neg(point: &Fr) -> Fr {
+            -*point
+        }
+
+        fn g2_add(a: &Fr, b: &Fr) -> Fr {
+            *a + *b
+        }
+
+        fn g2_neg(point: &Fr) -> Fr {
+            -*point
+        }
+
+        fn g1_mul(scalar: &Fr, point: &Fr) -> Fr {
+            *scalar * *point
+        }
+
+        fn g2_mul(scalar: &Fr, point: &Fr) -> Fr {
+            *scalar * *point
+        }
+
+        fn pairings_match(a1: &Fr, a2: &Fr, b1: &Fr, b2: &Fr) -> bool {
+            *a1 * *a2 == *b1 * *b2
+        }
+    }
+
+    #[test]
+    fn generic_api_runs_over_another_backend() {
+        let tau = Fr::from_u64(0x6a);
+        let kzg = KZG::<DiscreteLogs>::setup_with_backend(&tau, 4).unwrap();
+        assert_eq!(kzg.public_parameter.max_degree(), 4);
+        assert_eq!(kzg.public_parameter.points_in_g1[3], tau * tau * tau);
+        assert_eq!(kzg.public_parameter.point_in_g2(), tau);
+
+        // the commitment is f(tau) itself
+        let polynomial = Polynomial::from(vec![2u64, 7, 1, 8]);
+        let commitment = KZG::commit_with_backend(&kzg.public_parameter, &polynomial).unwrap();
+        assert_eq!(commitment.element, polynomial.evaluate(tau));
+
+        let z = Fr::from_u64(5);
+        let opening = commitment.open_with_backend(z).unwrap();
+        assert_eq!(opening.value, polynomial.evaluate(z));
+        assert!(commitment.verify_with_backend(&z, &opening));
+        assert!(!commitment.verify_with_backend(&Fr::from_u64(6), &opening));
+        let wrong_value = Opening::<DiscreteLogs> { value: opening.value + Fr::from_u64(1), proof: opening.proof };
+        assert!(!commitment.verify_with_backend(&z, &wrong_value));
+    }
+
+    #[test]
+    fn generic_sums_double_equal_points() {
+        let g1 = P1::generator();
+        let g2 = P2::generator();
+        let two = Fr::from_u64(2);
+        assert_eq!(Oblast::g1_add(&g1, &g1).compress(), (two * g1).compress());
+        assert_eq!(Oblast::g2_add(&g2, &g2).compress(), (two * g2).compress());
+        assert_eq!(Oblast::g1_add(&g1, &Oblast::g1_neg(&g1)).compress(), P1::default().compress());
+
+        let one = Fr::from_u64(1);
+        assert_eq!(commit::<Oblast>(&[g1, g1], &[one, one]).compress(), (two * g1).compress());
+        assert_eq!(commit::<Oblast>(&[g1, g1, g1], &[one, one, one]).compress(), (Fr::from_u64(3) * g1).compress());
+
+        // at z = -tau, [tau]_2 - [z]_2 adds [tau]_2 to itself. f = x^2 + 3 has f(z) = tau^2 + 3
+        // and quotient x + z = x - tau
+        let tau = Fr::from_u64(0x45);
+        let mut secret = [0u8; 32];
+        secret[31] = 0x45;
+        let pp = KZG::new(&secret, 4).unwrap().public_parameter;
+        let polynomial = Polynomial::from(vec![3u64, 0, 1]);
+        let commitment = KZG::commit(&pp, &polynomial).unwrap();
+        let z = -tau;
+        let opening = commitment.open_at(z).unwrap();
+        assert_eq!(opening.value, tau * tau + Fr::from_u64(3));
+        assert!(verify_opening::<Oblast>(&commitment.element, &z, &opening.value, &opening.proof, &pp.point_in_g2()));
+    }
+}
@@ -0,0 +1,202 @@
+//! [`KZGBuilder`]: setup construction with named options instead of positional arguments.
+
+use std::ops::ControlFlow;
+
+use rand::{thread_rng, CryptoRng, RngCore};
+
+use crate::kzg_commit::{self, KZGErrors, SetupProgress, DEFAULT_G2_POWERS, KZG, MAX_SETUP_DEGREE};
+use crate::secret::SecretBytes;
+
+type ProgressCallback<'a> = Box<dyn FnMut(SetupProgress) -> ControlFlow<()> + 'a>;
+
+/// Collects the setup options and checks them together in [`KZGBuilder::build`].
+///
+/// Only the degree is required. Without [`KZGBuilder::tau_bytes`] or [`KZGBuilder::random`], tau
+/// is drawn from `thread_rng` as in [`KZG::new_rand`].
+///
+/// ```
+/// use kzg_impl::builder::KZGBuilder;
+/// use kzg_impl::kzg_commit::KZG;
+///
+/// let kzg = KZGBuilder::new().degree(16).tau_bytes([7u8; 32]).g2_powers(3).build().unwrap();
+/// assert_eq!(kzg.degree(), 16);
+/// assert_eq!(kzg.public_parameter.points_in_g2.len(), 3);
+/// assert_eq!(KZGBuilder::new().degree(16).tau_bytes([7u8; 32]).build().unwrap(), KZG::new(&[7u8; 32], 16).unwrap());
+/// ```
+pub struct KZGBuilder<'a> {
+    degree: Option<usize>,
+    tau: Option<SecretBytes>,
+    random: Option<SecretBytes>,
+    g2_powers: usize,
+    degree_limit: usize,
+    progress: Option<ProgressCallback<'a>>,
+}
+
+impl Default for KZGBuilder<'_> {
+    fn default() -> Self {
+        KZGBuilder::new()
+    }
+}
+
+impl<'a> KZGBuilder<'a> {
+    pub fn new() -> Self {
+        KZGBuilder {
+            degree: None,
+            tau: None,
+            random: None,
+            g2_powers: DEFAULT_G2_POWERS,
+            degree_limit: MAX_SETUP_DEGREE,
+            progress: None,
+        }
+    }
+
+    /// the maximum degree of the committed polynomials (required)
+    pub fn degree(mut self, degree: usize) -> Self {
+        self.degree = Some(degree);
+        self
+    }
+
+    /// a known tau, as big-endian bytes; see the warning on [`KZG::new`]
+    pub fn tau_bytes(mut self, tau: [u8; 32]) -> Self {
+        self.tau = Some(SecretBytes::new(tau));
+        self
+    }
+
+    /// draws tau from `rng` right away; it is kept wiped-on-drop until the setup is built
+    pub fn random<R: RngCore + CryptoRng>(mut self, rng: &mut R) -> Self {
+        self.random = Some(kzg_commit::sample_secret(rng));
+        self
+    }
+
+    /// the number of G2 powers `[tau^i]_2` to generate (at least 2, the default)
+    pub fn g2_powers(mut self, g2_powers: usize) -> Self {
+        self.g2_powers = g2_powers;
+        self
+    }
+
+    /// the largest degree [`KZGBuilder::build`] accepts, [`MAX_SETUP_DEGREE`] unless changed here
+    pub fn degree_limit(mut self, limit: usize) -> Self {
+        self.degree_limit = limit;
+        self
+    }
+
+    /// reports progress while the G1 powers are computed, see [`KZG::setup_with_progress`]
+    pub fn with_progress(mut self, progress: impl FnMut(SetupProgress) -> ControlFlow<()> + 'a) -> Self {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+
+    /// checks the options and computes the setup
+    pub fn build(mut self) -> Result<KZG, KZGErrors> {
+        let degree = self.degree.ok_or(KZGErrors::MissingDegree)?;
+        if degree > self.degree_limit {
+            return Err(KZGErrors::DegreeTooLarge { degree, limit: self.degree_limit });
+        }
+        let secret = match (self.tau.take(), self.random.take()) {
+            (Some(_), Some(_)) => return Err(KZGErrors::ConflictingTauSources),
+            (Some(tau), None) => tau,
+            (None, Some(random)) => random,
+            (None, None) => kzg_commit::sample_secret(&mut thread_rng()),
+        };
+
+        let progress = self
+            .progress
+            .as_mut()
+            .map(|progress| progress.as_mut() as &mut dyn FnMut(SetupProgress) -> ControlFlow<()>);
+        KZG::setup_with_callback(&secret, degree, self.g2_powers, progress)
+    }
+}
+
+impl KZG {
+    /// a [`KZGBuilder`] with the default options
+    pub fn builder<'a>() -> KZGBuilder<'a> {
+        KZGBuilder::new()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn defaults_match_new() {
+        let tau = [0x49u8; 32];
+        let built = KZG::builder().degree(12).tau_bytes(tau).build().unwrap();
+        assert_eq!(built, KZG::new(&tau, 12).unwrap());
+        assert_eq!(built.public_parameter.points_in_g2.len(), DEFAULT_G2_POWERS);
+
+        let random = KZG::builder().degree(3).build().unwrap();
+        assert_eq!(random.degree(), 3);
+        assert!(random.public_parameter.verify_structure().is_ok());
+    }
+
+    #[test]
+    fn fully_specified() {
+        let tau = [0x4au8; 32];
+        let mut reports = 0;
+        let built = KZG::builder()
+            .degree(600)
+            .tau_bytes(tau)
+            .g2_powers(4)
+            .with_progress(|_| {
+                reports += 1;
+                ControlFlow::Continue(())
+            })
+            .build()
+            .unwrap();
+        assert_eq!(reports, 3);
+        assert_eq!(built, KZG::setup_with_g2_powers(&tau, 600, 4).unwrap());
+
+        let from_rng = KZG::builder().degree(8).random(&mut StdRng::seed_from_u64(49)).build().unwrap();
+        assert_eq!(from_rng, KZG::new_rand_with_rng(&mut StdRng::seed_from_u64(49), 8).unwrap());
+    }
+
+    #[test]
+    fn validation_errors() {
+        assert!(matches!(KZG::builder().tau_bytes([1u8; 32]).build(), Err(KZGErrors::MissingDegree)));
+
+        let mut rng = StdRng::seed_from_u64(50);
+        assert!(matches!(
+            KZG::builder().degree(4).tau_bytes([1u8; 32]).random(&mut rng).build(),
+            Err(KZGErrors::ConflictingTauSources)
+        ));
+        assert!(matches!(
+            KZG::builder().degree(4).tau_bytes([1u8; 32]).g2_powers(1).build(),
+            Err(KZGErrors::NotEnoughG2Powers { found: 1 })
+        ));
+        assert!(matches!(
+            KZG::builder().degree(4).tau_bytes([0u8; 32]).build(),
+            Err(KZGErrors::SecretMustBeNonZero)
+        ));
+        assert!(matches!(
+            KZG::builder().degree(4).tau_bytes([0xffu8; 32]).build(),
+            Err(KZGErrors::SecretMustBeLessThanTheOrderOfTheGroup)
+        ));
+        assert!(matches!(
+            KZG::builder().degree(300).tau_bytes([1u8; 32]).with_progress(|_| ControlFlow::Break(())).build(),
+            Err(KZGErrors::Cancelled)
+        ));
+    }
+
+    #[test]
+    fn rejects_absurd_degrees() {
+        assert!(matches!(
+            KZG::new(&[1u8; 32], usize::MAX),
+            Err(KZGErrors::DegreeTooLarge { degree: usize::MAX, limit: MAX_SETUP_DEGREE })
+        ));
+        assert!(matches!(
+            KZG::builder().degree(MAX_SETUP_DEGREE + 1).build(),
+            Err(KZGErrors::DegreeTooLarge { limit: MAX_SETUP_DEGREE, .. })
+        ));
+
+        // the limit can be lowered or raised
+        assert!(matches!(
+            KZG::builder().degree(9).degree_limit(8).tau_bytes([1u8; 32]).build(),
+            Err(KZGErrors::DegreeTooLarge { degree: 9, limit: 8 })
+        ));
+        assert_eq!(KZG::builder().degree(8).degree_limit(8).tau_bytes([1u8; 32]).build().unwrap().degree(), 8);
+    }
+}
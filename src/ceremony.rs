@@ -0,0 +1,256 @@
+//! Multi-party setup ceremonies.
+//!
+//! Each participant takes the current parameters and folds in a fresh secret `s`, turning
+//! `[tau^i]` into `[(tau * s)^i]`. The final tau stays unknown as long as a single participant
+//! discarded their secret. Every contribution comes with a [`ContributionProof`] so anyone can
+//! check it extends the previous parameters instead of replacing them.
+
+use oblast_demo::{verify_pairings, Fr, P1, P2};
+use rand::{CryptoRng, RngCore};
+
+use crate::group;
+use crate::kzg_commit::{self, KZGErrors, PP};
+use crate::scalar;
+use crate::transcript::Transcript;
+
+/// Evidence that a contribution multiplied the previous tau by a secret the contributor knows
+#[derive(Clone, Debug)]
+pub struct ContributionProof {
+    /// `[s]_1`
+    pub s_g1: P1,
+    /// `[s]_2`
+    pub s_g2: P2,
+    /// Schnorr commitment `[k]_1`
+    pub nonce_commitment: P1,
+    /// Schnorr response `k + c * s`
+    pub response: Fr,
+}
+
+/// Why a ceremony transcript was rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CeremonyError {
+    /// there are no contributions, so tau is still 1
+    EmptyTranscript,
+    /// the contribution at `index` doesn't extend the snapshot before it (the canonical setup for
+    /// index 0); later steps were not checked
+    InvalidStep { index: usize },
+}
+
+/// the Schnorr challenge, bound to the parameters being extended so a proof can't be replayed
+fn knowledge_challenge(prev: &PP, s_g1: &P1, s_g2: &P2, nonce_commitment: &P1) -> Fr {
+    let mut transcript = Transcript::new(b"kzg-ceremony-contribution");
+    transcript.append_bytes(&prev.digest());
+    transcript.append_point(s_g1);
+    transcript.append_bytes(&s_g2.compress());
+    transcript.append_point(nonce_commitment);
+    transcript.challenge()
+}
+
+impl PP {
+    /// folds a fresh secret `s` into the parameters: every `[tau^i]_1` becomes `[(tau s)^i]_1` and
+    /// every `[tau^i]_2` becomes `[(tau s)^i]_2`.
+    ///
+    /// `s` is drawn like the tau of [`kzg_commit::KZG::new_rand`] and dropped before returning; the
+    /// returned [`ContributionProof`] shows the new parameters extend these ones.
+    pub fn contribute<R: RngCore + CryptoRng>(&self, rng: &mut R) -> Result<(PP, ContributionProof), KZGErrors> {
+        self.check_consistency()?;
+
+        let secret = kzg_commit::sample_secret(rng);
+        let s = scalar::fr_from_bytes_be(&secret[..]);
+
+        let max_powers = self.points_in_g1.len().max(self.points_in_g2.len());
+        let powers = kzg_commit::powers_of_tau(s, max_powers - 1);
+        let points_in_g1 = self.points_in_g1.iter().zip(&powers).map(|(point, power)| *power * *point).collect();
+        let points_in_g2 = self.points_in_g2.iter().zip(&powers).map(|(point, power)| *power * *point).collect();
+        let next = PP::from_parts(points_in_g1, points_in_g2);
+
+        // Schnorr proof of knowledge of `s` for `[s]_1`
+        let s_g1 = s * P1::generator();
+        let s_g2 = s * P2::generator();
+        let nonce = scalar::fr_from_bytes_be(&kzg_commit::sample_secret(rng)[..]);
+        let nonce_commitment = nonce * P1::generator();
+        let challenge = knowledge_challenge(self, &s_g1, &s_g2, &nonce_commitment);
+
+        let proof = ContributionProof {
+            s_g1,
+            s_g2,
+            nonce_commitment,
+            response: nonce + challenge * s,
+        };
+
+        Ok((next, proof))
+    }
+}
+
+impl ContributionProof {
+    /// checks that `next` is `prev` with the secret behind this proof folded in:
+    /// the contributor knows `s` for `[s]_1`, `[s]_1` and `[s]_2` agree, `next` is a well-formed
+    /// powers-of-tau structure and its `[tau']_1` is `s` times `prev`'s `[tau]_1`
+    pub fn verify(&self, prev: &PP, next: &PP) -> bool {
+        if prev.max_degree() != next.max_degree() || prev.points_in_g2.len() != next.points_in_g2.len() {
+            return false;
+        }
+        if prev.check_consistency().is_err() || next.verify_structure().is_err() {
+            return false;
+        }
+
+        // k + c s = response  <=>  [response]_1 == [k]_1 + c [s]_1
+        let challenge = knowledge_challenge(prev, &self.s_g1, &self.s_g2, &self.nonce_commitment);
+        let expected = group::g1_add(&self.nonce_commitment, &(challenge * self.s_g1));
+        if (self.response * P1::generator()).compress() != expected.compress() {
+            return false;
+        }
+
+        // e([s]_1, [1]_2) == e([1]_1, [s]_2)
+        if !verify_pairings(self.s_g1, P2::generator(), P1::generator(), self.s_g2) {
+            return false;
+        }
+
+        // the new tau is the old one times s: e([tau']_1, [1]_2) == e([tau]_1, [s]_2)
+        // (a degree-0 setup has no [tau]_1, so compare the G2 side instead)
+        match (prev.points_in_g1.get(1), next.points_in_g1.get(1)) {
+            (Some(prev_tau), Some(next_tau)) => verify_pairings(*next_tau, P2::generator(), *prev_tau, self.s_g2),
+            _ => verify_pairings(P1::generator(), next.point_in_g2(), self.s_g1, prev.point_in_g2()),
+        }
+    }
+}
+
+/// the starting point of every ceremony: tau = 1, so every power is the generator
+fn canonical_setup(degree: usize, g2_powers: usize) -> PP {
+    PP::from_parts(vec![P1::generator(); degree + 1], vec![P2::generator(); g2_powers])
+}
+
+/// checks a whole ceremony: `steps[i]` is the snapshot after contribution `i` together with its
+/// proof, and the first one must extend the canonical degree-`initial_degree` setup with tau = 1.
+///
+/// every step is checked with [`ContributionProof::verify`] (Schnorr proof, ratio pairings and the
+/// batched structure check of the new snapshot), in order, and the first failure is reported. the
+/// canonical setup gets as many G2 powers as the first snapshot.
+pub fn verify_transcript(initial_degree: usize, steps: &[(PP, ContributionProof)]) -> Result<(), CeremonyError> {
+    let (first, _) = steps.first().ok_or(CeremonyError::EmptyTranscript)?;
+    let initial = canonical_setup(initial_degree, first.points_in_g2.len().max(kzg_commit::DEFAULT_G2_POWERS));
+
+    let mut prev = &initial;
+    for (index, (next, proof)) in steps.iter().enumerate() {
+        if !proof.verify(prev, next) {
+            return Err(CeremonyError::InvalidStep { index });
+        }
+        prev = next;
+    }
+
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg_commit::KZG;
+    use crate::polynomial::Polynomial;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn sequential_contributions() {
+        let mut rng = StdRng::seed_from_u64(31);
+        let initial = KZG::new(&[3u8; 32], 8).unwrap().public_parameter;
+
+        let (first, first_proof) = initial.contribute(&mut rng).unwrap();
+        let (second, second_proof) = first.contribute(&mut rng).unwrap();
+
+        assert!(first_proof.verify(&initial, &first));
+        assert!(second_proof.verify(&first, &second));
+        assert!(second.verify_structure().is_ok());
+        assert_ne!(second.digest(), first.digest());
+
+        let polynomial = Polynomial::from(vec![8u64, 6, 7, 5, 3, 0, 9]);
+        let commitment = KZG::commit(&second, &polynomial).unwrap();
+        let z = Fr::from_u64(404);
+        assert!(commitment.open_at(z).unwrap().verify(&z, &commitment));
+
+        // proofs are tied to the parameters they extend
+        assert!(!second_proof.verify(&initial, &second));
+        assert!(!first_proof.verify(&first, &second));
+    }
+
+    #[test]
+    fn rejects_forged_contributions() {
+        let mut rng = StdRng::seed_from_u64(32);
+        let initial = KZG::new(&[5u8; 32], 4).unwrap().public_parameter;
+        let (next, proof) = initial.contribute(&mut rng).unwrap();
+
+        // a well-formed setup with an unrelated tau: the ratio to `initial` isn't the proven `s`
+        let (other, _) = initial.contribute(&mut rng).unwrap();
+        assert!(other.verify_structure().is_ok());
+        assert!(!proof.verify(&initial, &other));
+
+        // [s]_1 and [s]_2 for different secrets
+        let mut mismatched = proof.clone();
+        mismatched.s_g2 = Fr::from_u64(2) * mismatched.s_g2;
+        assert!(!mismatched.verify(&initial, &next));
+
+        // a response that doesn't open the Schnorr commitment
+        let mut bad_response = proof.clone();
+        bad_response.response += Fr::from_u64(1);
+        assert!(!bad_response.verify(&initial, &next));
+
+        // a broken structure, even with the right first power
+        let mut broken = next.clone();
+        broken.points_in_g1[3] = P1::generator();
+        assert!(!proof.verify(&initial, &broken));
+
+        assert!(!proof.verify(&initial, &next.truncated(3).unwrap()));
+    }
+
+    /// three participants contributing in turn to the canonical setup
+    fn three_party_transcript(rng: &mut StdRng, degree: usize) -> Vec<(PP, ContributionProof)> {
+        let mut steps: Vec<(PP, ContributionProof)> = Vec::new();
+        let mut current = canonical_setup(degree, kzg_commit::DEFAULT_G2_POWERS);
+        for _ in 0..3 {
+            let (next, proof) = current.contribute(rng).unwrap();
+            current = next.clone();
+            steps.push((next, proof));
+        }
+        steps
+    }
+
+    #[test]
+    fn verifies_a_transcript() {
+        let mut rng = StdRng::seed_from_u64(33);
+        let steps = three_party_transcript(&mut rng, 6);
+
+        assert_eq!(verify_transcript(6, &steps), Ok(()));
+        assert_eq!(verify_transcript(6, &steps[..1]), Ok(()));
+        assert_eq!(verify_transcript(6, &[]), Err(CeremonyError::EmptyTranscript));
+
+        // the wrong starting degree, or a transcript that skips the first participant
+        assert_eq!(verify_transcript(5, &steps), Err(CeremonyError::InvalidStep { index: 0 }));
+        assert_eq!(verify_transcript(6, &steps[1..]), Err(CeremonyError::InvalidStep { index: 0 }));
+    }
+
+    #[test]
+    fn rejects_a_replayed_contribution() {
+        let mut rng = StdRng::seed_from_u64(34);
+        let mut steps = three_party_transcript(&mut rng, 4);
+
+        let replayed = steps[1].clone();
+        steps.insert(2, replayed);
+        assert_eq!(verify_transcript(4, &steps), Err(CeremonyError::InvalidStep { index: 2 }));
+    }
+
+    #[test]
+    fn rejects_a_tampered_snapshot() {
+        let mut rng = StdRng::seed_from_u64(35);
+        let steps = three_party_transcript(&mut rng, 4);
+
+        // a broken structure in the middle snapshot
+        let mut tampered = steps.clone();
+        tampered[1].0.points_in_g1[2] = P1::generator();
+        assert_eq!(verify_transcript(4, &tampered), Err(CeremonyError::InvalidStep { index: 1 }));
+
+        // a well-formed but unrelated middle snapshot
+        let mut swapped = steps.clone();
+        swapped[1].0 = steps[0].0.contribute(&mut rng).unwrap().0;
+        assert_eq!(verify_transcript(4, &swapped), Err(CeremonyError::InvalidStep { index: 1 }));
+    }
+}
@@ -0,0 +1,475 @@
+//! Evaluation domains: the multiplicative subgroups `{1, w, w^2, ..., w^(n-1)}` of the scalar field
+//! for power-of-two `n`, and the FFT between coefficients and evaluations over them.
+
+use std::ops::{Add, Mul, Neg};
+
+use num_bigint::BigUint;
+use oblast_demo::{curve_order, Fr};
+
+use crate::kzg_commit::KZGErrors;
+use crate::polynomial::Polynomial;
+use crate::scalar;
+
+/// `7` generates the multiplicative group of the BLS12-381 scalar field
+const MULTIPLICATIVE_GENERATOR: u64 = 7;
+/// `r - 1 = 2^32 * t` with `t` odd, so domains can have up to `2^32` elements
+const TWO_ADICITY: u32 = 32;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Domain {
+    size: usize,
+    /// a primitive `size`-th root of unity
+    generator: Fr,
+    generator_inverse: Fr,
+    size_inverse: Fr,
+}
+
+impl Domain {
+    /// the domain of the `size`-th roots of unity; `size` must be a power of two up to `2^32`
+    pub fn new(size: usize) -> Result<Domain, KZGErrors> {
+        if !size.is_power_of_two() || size.trailing_zeros() > TWO_ADICITY {
+            return Err(KZGErrors::InvalidDomainSize(size));
+        }
+
+        let generator = primitive_root(size.trailing_zeros() as usize);
+
+        Ok(Domain {
+            size,
+            generator,
+            generator_inverse: Fr::from_u64(1) / generator,
+            size_inverse: Fr::from_u64(1) / Fr::from_u64(size as u64),
+        })
+    }
+
+    /// the number of points in the domain
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// the primitive root of unity `w` generating the domain
+    pub fn generator(&self) -> Fr {
+        self.generator
+    }
+
+    /// `w^index`, the point of the domain at `index` (taken mod the size)
+    pub fn element(&self, index: usize) -> Fr {
+        scalar::fr_pow(self.generator, &BigUint::from(index % self.size))
+    }
+
+    /// `1, w, ..., w^(size-1)`
+    pub fn elements(&self) -> Vec<Fr> {
+        let mut elements = Vec::with_capacity(self.size);
+        let mut element = Fr::from_u64(1);
+        for _ in 0..self.size {
+            elements.push(element);
+            element *= self.generator;
+        }
+        elements
+    }
+
+    /// evaluates the polynomial with the given coefficients (at most `size`, zero-padded) at every
+    /// element of the domain, in order
+    pub fn fft(&self, coefficients: &[Fr]) -> Vec<Fr> {
+        assert!(coefficients.len() <= self.size, "more coefficients than domain points");
+        let mut values = coefficients.to_vec();
+        values.resize(self.size, Fr::default());
+        fft_in_place(&mut values, self.generator);
+        values
+    }
+
+    /// the coefficients of the polynomial of degree `< size` taking the given values on the domain
+    pub fn ifft(&self, evaluations: &[Fr]) -> Vec<Fr> {
+        assert!(evaluations.len() <= self.size, "more evaluations than domain points");
+        let mut values = evaluations.to_vec();
+        values.resize(self.size, Fr::default());
+        self.ifft_in_place(&mut values);
+        values
+    }
+
+    /// the inverse FFT over exactly `size` values, which may be scalars or group elements
+    pub(crate) fn ifft_in_place<T>(&self, values: &mut [T])
+    where
+        T: Copy + Add<Output = T> + Neg<Output = T>,
+        Fr: Mul<T, Output = T>,
+    {
+        assert_eq!(values.len(), self.size, "the inverse FFT needs one value per domain point");
+        fft_in_place(values, self.generator_inverse);
+        for value in values.iter_mut() {
+            *value = self.size_inverse * *value;
+        }
+    }
+}
+
+/// The values of a polynomial of degree `< n` at the points `1, w, ..., w^(n-1)` of a domain of
+/// size `n`, in domain order
+#[derive(Clone, Debug, PartialEq)]
+pub struct Evaluations {
+    values: Vec<Fr>,
+    domain: Domain,
+}
+
+impl Evaluations {
+    /// `values[i]` is the value at `w^i`; there must be exactly one value per domain point
+    pub fn new(values: Vec<Fr>, domain: Domain) -> Result<Evaluations, KZGErrors> {
+        if values.len() != domain.size() {
+            return Err(KZGErrors::EvaluationCountMismatch {
+                evaluations: values.len(),
+                domain_size: domain.size(),
+            });
+        }
+        Ok(Evaluations { values, domain })
+    }
+
+    /// evaluates `polynomial` over `domain` by FFT; its degree must be below the domain size
+    pub fn from_polynomial(polynomial: &Polynomial, domain: &Domain) -> Result<Evaluations, KZGErrors> {
+        let length = polynomial.degree().map_or(0, |degree| degree + 1);
+        if length > domain.size() {
+            return Err(KZGErrors::PolynomialDegreeTooLarge {
+                degree: length - 1,
+                max_degree: domain.size() - 1,
+            });
+        }
+        Ok(Evaluations {
+            values: domain.fft(&polynomial.coefficients[..length]),
+            domain: domain.clone(),
+        })
+    }
+
+    /// the values, in domain order
+    pub fn values(&self) -> &[Fr] {
+        &self.values
+    }
+
+    /// the domain the values are taken over
+    pub fn domain(&self) -> &Domain {
+        &self.domain
+    }
+
+    /// the coefficients of the polynomial, by inverse FFT
+    pub fn to_polynomial(&self) -> Polynomial {
+        Polynomial::from_coefficients(self.domain.ifft(&self.values))
+    }
+}
+
+impl Polynomial {
+    /// the values at the points of the domain of size `domain_size`, by FFT. The size must be a
+    /// power of two ([`KZGErrors::InvalidDomainSize`]) and above the degree
+    /// ([`KZGErrors::PolynomialDegreeTooLarge`]); trailing zero coefficients don't count.
+    pub fn to_evaluations(&self, domain_size: usize) -> Result<Evaluations, KZGErrors> {
+        Evaluations::from_polynomial(self, &Domain::new(domain_size)?)
+    }
+
+    /// the polynomial of degree below the domain size taking `evaluations[i]` at `w^i`, by inverse
+    /// FFT; there must be one value per domain point
+    pub fn from_evaluations(evaluations: &[Fr], domain: &Domain) -> Result<Polynomial, KZGErrors> {
+        if evaluations.len() != domain.size() {
+            return Err(KZGErrors::EvaluationCountMismatch {
+                evaluations: evaluations.len(),
+                domain_size: domain.size(),
+            });
+        }
+        Ok(Polynomial::from_coefficients(domain.ifft(evaluations)))
+    }
+}
+
+/// a primitive `2^log_n`-th root of unity, the generator used by [`Domain::new`].
+///
+/// panics if `log_n` exceeds the two-adicity of the field (32)
+pub fn primitive_root(log_n: usize) -> Fr {
+    assert!(log_n <= TWO_ADICITY as usize, "the scalar field has no 2^{}-th roots of unity", log_n);
+
+    // w = g^((r - 1) / 2^log_n) has order exactly 2^log_n since g generates the whole group
+    let exponent = (curve_order() - BigUint::from(1u32)) >> log_n;
+    scalar::fr_pow(Fr::from_u64(MULTIPLICATIVE_GENERATOR), &exponent)
+}
+
+/// all the `2^log_n`-th roots of unity `1, w, ..., w^(2^log_n - 1)`, in the order of
+/// [`Domain::elements`]; errors if `log_n` exceeds the two-adicity of the field
+pub fn roots_of_unity(log_n: usize) -> Result<Vec<Fr>, KZGErrors> {
+    if log_n > TWO_ADICITY as usize {
+        let size = u32::try_from(log_n).ok().and_then(|log_n| 1usize.checked_shl(log_n));
+        return Err(KZGErrors::InvalidDomainSize(size.unwrap_or(usize::MAX)));
+    }
+    Ok(Domain::new(1 << log_n)?.elements())
+}
+
+/// the index of `z` in `domain` (e.g. [`Domain::elements`]), or `None` for a point outside it.
+/// Evaluation-form formulas divide by `z - w^i`, so a point of the domain needs its own case.
+pub fn is_in_domain(domain: &[Fr], z: Fr) -> Option<usize> {
+    domain.iter().position(|point| *point == z)
+}
+
+/// `f(x) / Z_H(x)` at the points `x = g * w^i` of the coset `g` times a domain, from the values of
+/// `f` there, where `Z_H(x) = x^n - 1` vanishes on the domain `H` of size `domain_size`. The
+/// number of values is the size of the coset, a power of two; a coset meeting `H`, such as `g = 1`,
+/// is [`KZGErrors::CosetIntersectsDomain`].
+pub fn divide_by_vanishing_on_coset(
+    evals_on_coset: &[Fr],
+    domain_size: usize,
+    coset_shift: Fr,
+) -> Result<Vec<Fr>, KZGErrors> {
+    let coset = Domain::new(evals_on_coset.len())?;
+    if !domain_size.is_power_of_two() {
+        return Err(KZGErrors::InvalidDomainSize(domain_size));
+    }
+
+    // Z_H(g * w^i) = g^n * (w^n)^i - 1 only takes as many values as w^n has powers, the size of
+    // the coset over n, so only those are inverted
+    let period = (coset.size() / domain_size).max(1);
+    let step = scalar::fr_pow(coset.generator(), &BigUint::from(domain_size % coset.size()));
+    let mut power = scalar::fr_pow(coset_shift, &BigUint::from(domain_size));
+    let mut inverses = Vec::with_capacity(period);
+    for _ in 0..period {
+        inverses.push(power - Fr::from_u64(1));
+        power *= step;
+    }
+    if inverses.contains(&Fr::default()) {
+        return Err(KZGErrors::CosetIntersectsDomain);
+    }
+    batch_inverse(&mut inverses);
+
+    Ok(evals_on_coset
+        .iter()
+        .enumerate()
+        .map(|(i, value)| *value * inverses[i % period])
+        .collect())
+}
+
+/// replaces every value by its inverse with a single field inversion (Montgomery's trick); the
+/// values must be non-zero
+pub(crate) fn batch_inverse(values: &mut [Fr]) {
+    let mut products = Vec::with_capacity(values.len());
+    let mut product = Fr::from_u64(1);
+    for value in values.iter() {
+        products.push(product);
+        product *= *value;
+    }
+
+    // walking back, `inverse` is the inverse of the product of the values not yet visited
+    let mut inverse = Fr::from_u64(1) / product;
+    for (value, prefix) in values.iter_mut().zip(products).rev() {
+        let next = inverse * *value;
+        *value = inverse * prefix;
+        inverse = next;
+    }
+}
+
+/// iterative radix-2 Cooley–Tukey; `values.len()` must be a power of two and `root` a primitive
+/// root of unity of that order. Only additions, negations and scalar multiplications are used, so
+/// it runs over group elements as well as over scalars; their `+` must handle equal operands,
+/// which the butterflies add whenever values repeat.
+pub(crate) fn fft_in_place<T>(values: &mut [T], root: Fr)
+where
+    T: Copy + Add<Output = T> + Neg<Output = T>,
+    Fr: Mul<T, Output = T>,
+{
+    let n = values.len();
+    if n <= 1 {
+        return;
+    }
+    let log_n = n.trailing_zeros();
+
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - log_n);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let mut half = 1;
+    while half < n {
+        // a primitive (2 * half)-th root of unity
+        let mut step = root;
+        for _ in 0..(log_n - half.trailing_zeros() - 1) {
+            step *= step;
+        }
+
+        for start in (0..n).step_by(2 * half) {
+            let mut twiddle = Fr::from_u64(1);
+            for k in 0..half {
+                let odd = twiddle * values[start + k + half];
+                let even = values[start + k];
+                values[start + k] = even + odd;
+                values[start + k + half] = even + -odd;
+                twiddle *= step;
+            }
+        }
+        half *= 2;
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomial::Polynomial;
+
+    #[test]
+    fn domain_generators() {
+        for size in [1usize, 2, 4, 64, 1024] {
+            let domain = Domain::new(size).unwrap();
+            let w = domain.generator();
+            assert_eq!(scalar::fr_pow(w, &BigUint::from(size)), Fr::from_u64(1));
+            if size > 1 {
+                assert_ne!(scalar::fr_pow(w, &BigUint::from(size / 2)), Fr::from_u64(1));
+            }
+        }
+
+        assert!(matches!(Domain::new(0), Err(KZGErrors::InvalidDomainSize(0))));
+        assert!(matches!(Domain::new(12), Err(KZGErrors::InvalidDomainSize(12))));
+    }
+
+    #[test]
+    fn roots_of_unity_are_distinct() {
+        for log_n in [0usize, 1, 3, 8] {
+            let roots = roots_of_unity(log_n).unwrap();
+            let size = 1usize << log_n;
+            assert_eq!(roots.len(), size);
+            assert_eq!(roots[1 % size], primitive_root(log_n));
+
+            for root in &roots {
+                assert_eq!(scalar::fr_pow(*root, &BigUint::from(size)), Fr::from_u64(1));
+            }
+            let mut encoded: Vec<[u8; 32]> = roots.iter().map(scalar::fr_to_bytes_be).collect();
+            encoded.sort();
+            encoded.dedup();
+            assert_eq!(encoded.len(), size);
+        }
+
+        let w = primitive_root(TWO_ADICITY as usize);
+        assert_eq!(scalar::fr_pow(w, &(BigUint::from(1u32) << TWO_ADICITY)), Fr::from_u64(1));
+        assert_ne!(scalar::fr_pow(w, &(BigUint::from(1u32) << (TWO_ADICITY - 1))), Fr::from_u64(1));
+
+        assert!(matches!(roots_of_unity(33), Err(KZGErrors::InvalidDomainSize(size)) if size == 1 << 33));
+        assert!(matches!(roots_of_unity(200), Err(KZGErrors::InvalidDomainSize(usize::MAX))));
+    }
+
+    #[test]
+    fn membership() {
+        let domain = Domain::new(8).unwrap();
+        let elements = domain.elements();
+        assert_eq!(is_in_domain(&elements, Fr::from_u64(1)), Some(0));
+        assert_eq!(is_in_domain(&elements, domain.generator() * domain.generator()), Some(2));
+        assert_eq!(is_in_domain(&elements, -Fr::from_u64(1)), Some(4));
+        assert_eq!(domain.element(5), elements[5]);
+        assert_eq!(domain.element(8 + 3), elements[3]);
+
+        // a root of unity of a larger domain, and a point that is no root of unity at all
+        assert_eq!(is_in_domain(&elements, primitive_root(4)), None);
+        assert_eq!(is_in_domain(&elements, Fr::from_u64(2)), None);
+        assert_eq!(is_in_domain(&[], Fr::from_u64(1)), None);
+    }
+
+    #[test]
+    fn coefficient_evaluation_round_trip() {
+        let polynomial = Polynomial::from(vec![2u64, 7, 1, 8, 2, 8]);
+        for size in [8, 16, 64] {
+            let evaluations = polynomial.to_evaluations(size).unwrap();
+            let domain = Domain::new(size).unwrap();
+            assert_eq!(evaluations.domain(), &domain);
+            for (point, value) in domain.elements().iter().zip(evaluations.values()) {
+                assert_eq!(polynomial.evalaute(*point), *value);
+            }
+            assert_eq!(Polynomial::from_evaluations(evaluations.values(), &domain).unwrap(), polynomial);
+        }
+
+        // degree 5 needs at least 6 points, trailing zeros aside
+        let padded = Polynomial::from(vec![2u64, 7, 1, 8, 2, 8, 0, 0, 0]);
+        assert!(padded.to_evaluations(8).is_ok());
+        assert!(matches!(
+            polynomial.to_evaluations(4),
+            Err(KZGErrors::PolynomialDegreeTooLarge { degree: 5, max_degree: 3 })
+        ));
+        assert!(matches!(polynomial.to_evaluations(12), Err(KZGErrors::InvalidDomainSize(12))));
+        assert!(matches!(
+            Polynomial::from_evaluations(&[Fr::from_u64(1); 3], &Domain::new(4).unwrap()),
+            Err(KZGErrors::EvaluationCountMismatch { evaluations: 3, domain_size: 4 })
+        ));
+    }
+
+    #[test]
+    fn batch_inversion() {
+        let values: Vec<Fr> = (1..=9u64).map(|i| Fr::from_u64(i * i + 3)).collect();
+        let mut inverses = values.clone();
+        batch_inverse(&mut inverses);
+        for (value, inverse) in values.iter().zip(&inverses) {
+            assert_eq!(*value * *inverse, Fr::from_u64(1));
+        }
+        batch_inverse(&mut []);
+    }
+
+    #[test]
+    fn division_by_vanishing_on_a_coset() {
+        let shift = Fr::from_u64(MULTIPLICATIVE_GENERATOR);
+        let coset = |size: usize| -> Vec<Fr> {
+            Domain::new(size).unwrap().elements().iter().map(|point| shift * *point).collect()
+        };
+
+        // f = q * Z_H over a coset four times the size of H, against division of the coefficients
+        let quotient = Polynomial::from(vec![3u64, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5]);
+        let vanishing = Polynomial::monomial(4, Fr::from_u64(1)).sub_constant(Fr::from_u64(1));
+        let f = quotient.mul_by_vanishing(4);
+        let (divided, remainder) = f.div_rem(&vanishing);
+        assert_eq!(divided, quotient);
+        assert!(remainder.is_zero());
+
+        let values = divide_by_vanishing_on_coset(&f.evaluate_many(&coset(16)), 4, shift).unwrap();
+        assert_eq!(values, divided.evaluate_many(&coset(16)));
+        // interpolated on the coset, q(g * x) has the coefficients q_j * g^j
+        let mut power = Fr::from_u64(1);
+        for (coefficient, shifted) in divided.coefficients.iter().zip(Domain::new(16).unwrap().ifft(&values)) {
+            assert_eq!(shifted, *coefficient * power);
+            power *= shift;
+        }
+
+        // any values, over cosets larger, as large and smaller than H
+        for (size, domain_size) in [(8, 2), (8, 8), (4, 16)] {
+            let points = coset(size);
+            let values: Vec<Fr> = (0..size as u64).map(|i| Fr::from_u64(i * i + 1)).collect();
+            let divided = divide_by_vanishing_on_coset(&values, domain_size, shift).unwrap();
+            for ((point, value), divided) in points.iter().zip(&values).zip(&divided) {
+                let z_h = scalar::fr_pow(*point, &BigUint::from(domain_size)) - Fr::from_u64(1);
+                assert_eq!(*divided * z_h, *value);
+            }
+        }
+
+        // H itself, and a shift by a root of unity that moves the coset of 16 points onto H
+        let values = vec![Fr::from_u64(1); 16];
+        assert!(matches!(
+            divide_by_vanishing_on_coset(&values, 4, Fr::from_u64(1)),
+            Err(KZGErrors::CosetIntersectsDomain)
+        ));
+        assert!(matches!(
+            divide_by_vanishing_on_coset(&values, 4, primitive_root(4)),
+            Err(KZGErrors::CosetIntersectsDomain)
+        ));
+        assert!(matches!(
+            divide_by_vanishing_on_coset(&values[..12], 4, shift),
+            Err(KZGErrors::InvalidDomainSize(12))
+        ));
+        assert!(matches!(
+            divide_by_vanishing_on_coset(&values, 3, shift),
+            Err(KZGErrors::InvalidDomainSize(3))
+        ));
+    }
+
+    #[test]
+    #[should_panic]
+    fn primitive_root_beyond_two_adicity() {
+        primitive_root(33);
+    }
+
+    #[test]
+    fn fft_evaluates_on_the_domain() {
+        let domain = Domain::new(16).unwrap();
+        let polynomial = Polynomial::from(vec![3u64, 1, 4, 1, 5, 9, 2, 6, 5, 3]);
+
+        let evaluations = domain.fft(&polynomial.coefficients);
+        for (element, evaluation) in domain.elements().iter().zip(&evaluations) {
+            assert_eq!(polynomial.evaluate(*element), *evaluation);
+        }
+
+        let coefficients = domain.ifft(&evaluations);
+        assert_eq!(Polynomial::from(coefficients), polynomial);
+    }
+}
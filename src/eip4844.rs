@@ -0,0 +1,241 @@
+//! The EIP-4844 blob commitment, on top of [`KZGSettings`].
+//!
+//! A blob is 4096 field elements of 32 big-endian bytes each. Element `i` is the evaluation of the
+//! blob polynomial at `w^bitrev(i)`, where `bitrev` reverses the 12 bits of the index and `w`
+//! generates the 4096-point domain, so the blob lists the domain in bit-reversed order. The
+//! commitment is computed from the evaluations with the Lagrange setup, without an inverse FFT.
+
+use std::fmt;
+
+use oblast_demo::Fr;
+use sha2::{Digest, Sha256};
+
+use crate::kzg_commit::KZGErrors;
+use crate::scalar::FrBytes;
+use crate::settings::KZGSettings;
+
+/// field elements in a blob
+pub const FIELD_ELEMENTS_PER_BLOB: usize = 4096;
+/// size of an encoded field element
+pub const BYTES_PER_FIELD_ELEMENT: usize = 32;
+/// size of a blob
+pub const BYTES_PER_BLOB: usize = FIELD_ELEMENTS_PER_BLOB * BYTES_PER_FIELD_ELEMENT;
+/// size of a compressed commitment
+pub const BYTES_PER_COMMITMENT: usize = 48;
+
+/// the first byte of a versioned hash of a KZG commitment
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+/// A blob: 4096 big-endian field elements in bit-reversed domain order
+pub type Blob = [u8; BYTES_PER_BLOB];
+/// A compressed G1 commitment to a blob
+pub type KzgCommitmentBytes = [u8; BYTES_PER_COMMITMENT];
+
+#[derive(Debug)]
+pub enum Eip4844Error {
+    /// the field element at this index of the blob is not below the curve order
+    NonCanonicalFieldElement { index: usize },
+    /// the settings are over a domain of `found` points instead of one point per blob element
+    WrongSettingsSize { expected: usize, found: usize },
+    /// committing with the settings failed
+    Kzg(KZGErrors),
+}
+
+impl fmt::Display for Eip4844Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Eip4844Error::NonCanonicalFieldElement { index } => {
+                write!(f, "blob field element {} is not below the curve order", index)
+            }
+            Eip4844Error::WrongSettingsSize { expected, found } => {
+                write!(f, "settings over {} points, a blob needs {}", found, expected)
+            }
+            Eip4844Error::Kzg(error) => write!(f, "committing to the blob failed: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for Eip4844Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Eip4844Error::Kzg(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<KZGErrors> for Eip4844Error {
+    fn from(error: KZGErrors) -> Self {
+        Eip4844Error::Kzg(error)
+    }
+}
+
+/// the commitment to `blob`, as the 48-byte compressed point. `settings` must be over the
+/// 4096-point domain
+pub fn blob_to_kzg_commitment(blob: &Blob, settings: &KZGSettings) -> Result<KzgCommitmentBytes, Eip4844Error> {
+    if settings.blob_size() != FIELD_ELEMENTS_PER_BLOB {
+        return Err(Eip4844Error::WrongSettingsSize {
+            expected: FIELD_ELEMENTS_PER_BLOB,
+            found: settings.blob_size(),
+        });
+    }
+    commit_blob_bytes(blob, settings)
+}
+
+/// the versioned hash a transaction refers to a blob by: SHA-256 of the compressed commitment,
+/// with the first byte replaced by [`VERSIONED_HASH_VERSION_KZG`]
+pub fn kzg_to_versioned_hash(commitment: &KzgCommitmentBytes) -> [u8; 32] {
+    let mut hash: [u8; 32] = Sha256::digest(commitment).into();
+    hash[0] = VERSIONED_HASH_VERSION_KZG;
+    hash
+}
+
+/// true when `hash` is the versioned hash of `commitment`
+pub fn verify_versioned_hash(commitment: &KzgCommitmentBytes, hash: &[u8; 32]) -> bool {
+    kzg_to_versioned_hash(commitment) == *hash
+}
+
+/// [`blob_to_kzg_commitment`] for a blob of any settings' size, which must be a multiple of 32
+/// bytes with one element per domain point
+pub(crate) fn commit_blob_bytes(blob: &[u8], settings: &KZGSettings) -> Result<KzgCommitmentBytes, Eip4844Error> {
+    let elements = blob_to_field_elements(blob)?;
+    let evaluations = bit_reversal_permutation(&elements);
+    let commitment = settings.commit_blob(&evaluations)?;
+    Ok(commitment.compress().try_into().unwrap())
+}
+
+/// the 32-byte big-endian field elements of a blob, each checked to be below the curve order
+fn blob_to_field_elements(blob: &[u8]) -> Result<Vec<Fr>, Eip4844Error> {
+    blob.chunks(BYTES_PER_FIELD_ELEMENT)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let bytes: &[u8; BYTES_PER_FIELD_ELEMENT] = chunk.try_into().map_err(|_| KZGErrors::TruncatedInput)?;
+            Fr::from_bytes_checked(bytes).map_err(|_| Eip4844Error::NonCanonicalFieldElement { index })
+        })
+        .collect()
+}
+
+/// `values[bitrev(i)]` at position `i`, for a power-of-two number of values; the permutation is
+/// its own inverse
+fn bit_reversal_permutation<T: Copy>(values: &[T]) -> Vec<T> {
+    let bits = values.len().trailing_zeros();
+    if bits == 0 {
+        return values.to_vec();
+    }
+    (0..values.len())
+        .map(|index| values[index.reverse_bits() >> (usize::BITS - bits)])
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg_commit::KZG;
+    use crate::polynomial::Polynomial;
+    use oblast_demo::P1;
+
+    /// the 16-point analog of the mainnet settings
+    fn small_settings() -> KZGSettings {
+        KZGSettings::new(KZG::new(&[0x54u8; 32], 15).unwrap().public_parameter, 16).unwrap()
+    }
+
+    fn blob_of(elements: &[u64]) -> Vec<u8> {
+        elements.iter().flat_map(|element| Fr::from_u64(*element).to_bytes_be()).collect()
+    }
+
+    #[test]
+    fn bit_reversal() {
+        assert_eq!(bit_reversal_permutation(&[0, 1, 2, 3, 4, 5, 6, 7]), vec![0, 4, 2, 6, 1, 5, 3, 7]);
+        let values = (0..16).collect::<Vec<_>>();
+        assert_eq!(bit_reversal_permutation(&bit_reversal_permutation(&values)), values);
+        assert_eq!(bit_reversal_permutation(&[9]), vec![9]);
+    }
+
+    #[test]
+    fn small_domain_commitment() {
+        let settings = small_settings();
+        let elements = (1..=16u64).map(|i| i * i + 7).collect::<Vec<_>>();
+        let blob = blob_of(&elements);
+        let commitment = commit_blob_bytes(&blob, &settings).unwrap();
+
+        // element i is the value at w^bitrev(i): put it at its natural position and interpolate
+        let bitrev = bit_reversal_permutation(&(0..16).collect::<Vec<usize>>());
+        let mut natural = vec![Fr::default(); 16];
+        for (index, element) in elements.iter().enumerate() {
+            natural[bitrev[index]] = Fr::from_u64(*element);
+        }
+        let polynomial = Polynomial::from_coefficients(settings.domain().ifft(&natural));
+        let domain = settings.domain().elements();
+        assert_eq!(polynomial.evaluate(domain[bitrev[1]]), Fr::from_u64(elements[1]));
+        let monomial = KZG::commit(settings.monomial(), &polynomial).unwrap().element;
+        assert_eq!(commitment.to_vec(), monomial.compress());
+
+        // pinned from the monomial path above, so the ordering can't change unnoticed
+        assert_eq!(
+            hex::encode(commitment),
+            "9693c754cfbf6e0d5c99d9e5e7b4198bc87864f13a260cc72beac94930f0055163675c88cdbc4b4f0a3835be01d80eaa"
+        );
+
+        // in natural order the blob would be another polynomial
+        let unpermuted = settings.commit_blob(&elements.iter().map(|e| Fr::from_u64(*e)).collect::<Vec<_>>()).unwrap();
+        assert_ne!(unpermuted.compress(), commitment.to_vec());
+    }
+
+    #[test]
+    fn versioned_hashes() {
+        // the commitment to the zero blob, the point at infinity, as referenced on mainnet
+        let mut infinity = [0u8; BYTES_PER_COMMITMENT];
+        infinity[0] = 0xc0;
+        let hash = kzg_to_versioned_hash(&infinity);
+        assert_eq!(hex::encode(hash), "010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014");
+        assert!(verify_versioned_hash(&infinity, &hash));
+
+        // the commitment to the constant polynomial 1, the G1 generator
+        let generator: KzgCommitmentBytes = P1::generator().compress().try_into().unwrap();
+        assert_eq!(
+            hex::encode(kzg_to_versioned_hash(&generator)),
+            "01cf478a431837728dcec3461f4f53b8749cdc4e03496dcaed459dea82b82eb8"
+        );
+        assert!(!verify_versioned_hash(&generator, &hash));
+
+        // any change to the commitment changes the hash
+        for index in 0..BYTES_PER_COMMITMENT {
+            for flip in [0x01, 0x80, 0xff] {
+                let mut changed = generator;
+                changed[index] ^= flip;
+                assert!(!verify_versioned_hash(&changed, &kzg_to_versioned_hash(&generator)), "byte {}", index);
+            }
+        }
+
+        // the version byte is part of the hash
+        let mut wrong_version = hash;
+        wrong_version[0] = 0x02;
+        assert!(!verify_versioned_hash(&infinity, &wrong_version));
+    }
+
+    #[test]
+    fn rejects_bad_blobs_and_settings() {
+        let settings = small_settings();
+        let mut blob = blob_of(&[1; 16]);
+        // the curve order itself
+        blob[5 * 32..6 * 32].copy_from_slice(&hex::decode(
+            "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001",
+        ).unwrap());
+        assert!(matches!(
+            commit_blob_bytes(&blob, &settings),
+            Err(Eip4844Error::NonCanonicalFieldElement { index: 5 })
+        ));
+
+        assert!(matches!(
+            commit_blob_bytes(&blob_of(&[1; 8]), &settings),
+            Err(Eip4844Error::Kzg(KZGErrors::InvalidBlobLength { expected: 16, found: 8 }))
+        ));
+
+        let blob: Box<Blob> = vec![0u8; BYTES_PER_BLOB].into_boxed_slice().try_into().unwrap();
+        assert!(matches!(
+            blob_to_kzg_commitment(&blob, &settings),
+            Err(Eip4844Error::WrongSettingsSize { expected: 4096, found: 16 })
+        ));
+    }
+}
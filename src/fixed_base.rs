@@ -0,0 +1,319 @@
+//! Fixed-base scalar multiplication with precomputed window tables.
+//!
+//! A setup multiplies the same generator by thousands of scalars. Splitting each scalar into
+//! 4-bit windows and precomputing `k * 16^j * base` for every window `j` and digit `k` turns each
+//! multiplication into one table lookup and one addition per window: 64 additions instead of the
+//! 255 doublings and additions of a plain scalar multiplication.
+//!
+//! [`CommitKey`] does the same for every G1 power of a setup, so that committing many polynomials
+//! against one setup costs only table lookups and additions.
+
+use std::fmt;
+use std::ops::{Add, Mul};
+use std::sync::OnceLock;
+
+use blst::{blst_p1, blst_p1_affine};
+use oblast_demo::{Fr, P1, P2};
+
+use crate::group;
+use crate::kzg_commit::{self, KZGErrors, PP};
+use crate::msm::{self, SCALAR_BITS};
+use crate::polynomial::Polynomial;
+use crate::scalar;
+
+/// bits per window
+const WINDOW_BITS: usize = 4;
+/// multiples of the window base in each table row, including the zero multiple
+const WINDOW_SIZE: usize = 1 << WINDOW_BITS;
+/// windows needed to cover a 256-bit scalar
+const WINDOWS: usize = 256 / WINDOW_BITS;
+
+/// `k * 16^j * base` for every window `j < 64` and digit `k < 16`.
+///
+/// The lookups are indexed by the scalar's digits, so unlike `Fr * P1` the running time and
+/// memory accesses depend on the scalar.
+#[derive(Clone, Debug)]
+pub struct WindowTable<P> {
+    rows: Vec<[P; WINDOW_SIZE]>,
+}
+
+impl<P> WindowTable<P>
+where
+    P: Copy + Default + Add<Output = P>,
+    Fr: Mul<P, Output = P>,
+{
+    /// precomputes the table for `base` with about a thousand additions
+    pub fn new(base: P) -> Self {
+        let mut rows = Vec::with_capacity(WINDOWS);
+        let mut window_base = base;
+        for _ in 0..WINDOWS {
+            let mut row = [P::default(); WINDOW_SIZE];
+            row[1] = window_base;
+            // the point addition does not handle doubling, so `2 * base` is a scalar multiplication
+            row[2] = Fr::from_u64(2) * window_base;
+            for digit in 3..WINDOW_SIZE {
+                row[digit] = row[digit - 1] + window_base;
+            }
+            window_base = row[WINDOW_SIZE - 1] + window_base;
+            rows.push(row);
+        }
+        WindowTable { rows }
+    }
+
+    /// `scalar * base`, equal to the plain scalar multiplication.
+    ///
+    /// Windows are added from the least significant one up, so the running sum is always a
+    /// smaller multiple of the base than the next table entry and no addition is a doubling.
+    pub fn mul(&self, scalar: &Fr) -> P {
+        let bytes = scalar::fr_to_bytes_be(scalar);
+        let mut result = P::default();
+        for (index, byte) in bytes.iter().rev().enumerate() {
+            result = result + self.rows[2 * index][usize::from(byte & 0x0f)];
+            result = result + self.rows[2 * index + 1][usize::from(byte >> 4)];
+        }
+        result
+    }
+}
+
+/// the table for `[1]_1`, built on first use
+pub fn g1_generator_table() -> &'static WindowTable<P1> {
+    static TABLE: OnceLock<WindowTable<P1>> = OnceLock::new();
+    TABLE.get_or_init(|| WindowTable::new(P1::generator()))
+}
+
+/// the table for `[1]_2`, built on first use
+pub fn g2_generator_table() -> &'static WindowTable<P2> {
+    static TABLE: OnceLock<WindowTable<P2>> = OnceLock::new();
+    TABLE.get_or_init(|| WindowTable::new(P2::generator()))
+}
+
+/// Window tables for every G1 power of a setup, built once for committing many polynomials.
+///
+/// Each `[tau^i]_1` gets the multiples `k * 2^(c * j) * [tau^i]_1` for every `c`-bit window `j`
+/// and digit `0 < k < 2^c`, stored affine. A commitment then costs one addition per non-zero digit
+/// of each coefficient and no doublings; wider windows mean fewer additions for exponentially
+/// more memory (see [`CommitKey::memory_estimate`]).
+#[derive(Clone)]
+pub struct CommitKey {
+    window_bits: usize,
+    max_degree: usize,
+    /// multiples power by power, window by window, digit by digit
+    table: Vec<blst_p1_affine>,
+}
+
+impl CommitKey {
+    /// builds the tables for every G1 power of `pp` with `window_bits`-bit windows (1 to 16;
+    /// 4 to 8 are the useful range). Panics for other widths.
+    pub fn precompute(pp: &PP, window_bits: usize) -> CommitKey {
+        assert!((1..=16).contains(&window_bits), "window_bits must be between 1 and 16");
+        let per_power = Self::points_per_power(window_bits);
+        let mut table = Vec::with_capacity(pp.points_in_g1.len() * per_power);
+        for point in &pp.points_in_g1 {
+            push_power_table(&mut table, point, window_bits);
+        }
+
+        CommitKey {
+            window_bits,
+            max_degree: pp.points_in_g1.len() - 1,
+            table,
+        }
+    }
+
+    fn windows(window_bits: usize) -> usize {
+        SCALAR_BITS.div_ceil(window_bits)
+    }
+
+    fn points_per_power(window_bits: usize) -> usize {
+        Self::windows(window_bits) * ((1 << window_bits) - 1)
+    }
+
+    /// the largest degree that can be committed to
+    pub fn max_degree(&self) -> usize {
+        self.max_degree
+    }
+
+    /// the window width the key was built with
+    pub fn window_bits(&self) -> usize {
+        self.window_bits
+    }
+
+    /// the bytes the tables of a key for `pp` with `window_bits`-bit windows take, to pick a width
+    /// before building one
+    pub fn memory_estimate_for(pp: &PP, window_bits: usize) -> usize {
+        pp.points_in_g1.len() * Self::points_per_power(window_bits) * std::mem::size_of::<blst_p1_affine>()
+    }
+
+    /// the bytes taken by the tables of this key
+    pub fn memory_estimate(&self) -> usize {
+        self.table.len() * std::mem::size_of::<blst_p1_affine>()
+    }
+
+    /// the commitment point to `polynomial`, identical to [`kzg_commit::KZG::commit`] with the
+    /// setup the key was built from
+    pub fn commit(&self, polynomial: &Polynomial) -> Result<P1, KZGErrors> {
+        kzg_commit::check_degree(self.max_degree, polynomial)?;
+
+        let c = self.window_bits;
+        let per_power = Self::points_per_power(c);
+        let digits = (1 << c) - 1;
+        let length = polynomial.degree().map_or(0, |degree| degree + 1);
+        let mut result = blst_p1::default();
+        for (index, coefficient) in polynomial.coefficients[..length].iter().enumerate() {
+            let limbs = msm::limbs(coefficient);
+            let table = &self.table[index * per_power..(index + 1) * per_power];
+            for window in 0..Self::windows(c) {
+                let digit = msm::digit(&limbs, window * c, c);
+                if digit != 0 {
+                    let multiple = &table[window * digits + digit - 1];
+                    // sums of different powers can coincide, so this has to handle doubling
+                    unsafe { blst::blst_p1_add_or_double_affine(&mut result, &result, multiple) };
+                }
+            }
+        }
+
+        Ok(P1::from_raw(result))
+    }
+}
+
+/// appends the multiples of `base` for every window and non-zero digit, converted to affine with
+/// one inversion
+fn push_power_table(table: &mut Vec<blst_p1_affine>, base: &P1, window_bits: usize) {
+    let digits = (1 << window_bits) - 1;
+    let windows = CommitKey::windows(window_bits);
+    let mut window_base = blst_p1::default();
+    unsafe { blst::blst_p1_from_affine(&mut window_base, &group::g1_to_affine(base)) };
+
+    let mut multiples = Vec::with_capacity(windows * digits);
+    for _ in 0..windows {
+        let mut multiple = window_base;
+        multiples.push(multiple);
+        for _ in 1..digits {
+            unsafe { blst::blst_p1_add_or_double(&mut multiple, &multiple, &window_base) };
+            multiples.push(multiple);
+        }
+        // (2^c - 1) * base + base
+        unsafe { blst::blst_p1_add_or_double(&mut window_base, &multiple, &window_base) };
+    }
+
+    let start = table.len();
+    table.resize(start + multiples.len(), blst_p1_affine::default());
+    // a null-terminated list of one pointer marks `multiples` as a contiguous array
+    let points = [multiples.as_ptr(), std::ptr::null()];
+    unsafe { blst::blst_p1s_to_affine(table[start..].as_mut_ptr(), points.as_ptr(), multiples.len()) };
+}
+
+/// the tables are large, so only the shape is shown
+impl fmt::Debug for CommitKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CommitKey")
+            .field("window_bits", &self.window_bits)
+            .field("max_degree", &self.max_degree)
+            .field("memory_estimate", &self.memory_estimate())
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{RngCore, SeedableRng};
+
+    fn edge_and_random_scalars() -> Vec<Fr> {
+        let mut rng = StdRng::seed_from_u64(61);
+        // 0, 1, the digit boundaries and order - 1
+        let mut scalars = vec![
+            Fr::default(),
+            Fr::from_u64(1),
+            Fr::from_u64(2),
+            Fr::from_u64(15),
+            Fr::from_u64(16),
+            -Fr::from_u64(1),
+        ];
+        scalars.extend((0..20).map(|_| {
+            let mut bytes = [0u8; 64];
+            rng.fill_bytes(&mut bytes);
+            scalar::fr_from_bytes_be(&bytes)
+        }));
+        scalars
+    }
+
+    #[test]
+    fn agrees_with_scalar_multiplication() {
+        let g1 = g1_generator_table();
+        let g2 = g2_generator_table();
+        for scalar in edge_and_random_scalars() {
+            assert_eq!(g1.mul(&scalar).compress(), (scalar * P1::generator()).compress());
+            assert_eq!(g2.mul(&scalar).compress(), (scalar * P2::generator()).compress());
+        }
+
+        // any base, not just the generator
+        let base = Fr::from_u64(987_654_321) * P1::generator();
+        let table = WindowTable::new(base);
+        for scalar in edge_and_random_scalars() {
+            assert_eq!(table.mul(&scalar).compress(), (scalar * base).compress());
+        }
+    }
+
+    #[test]
+    fn setup_is_unchanged() {
+        use crate::kzg_commit::KZG;
+
+        let pp = KZG::new(&[0x61u8; 32], 20).unwrap().public_parameter;
+        let tau = scalar::fr_from_bytes_be(&[0x61u8; 32]);
+        let powers = crate::kzg_commit::powers_of_tau(tau, 20);
+        for (power, point) in powers.iter().zip(&pp.points_in_g1) {
+            assert_eq!(point.compress(), (*power * P1::generator()).compress());
+        }
+        assert_eq!(pp.points_in_g2[1].compress(), (tau * P2::generator()).compress());
+    }
+
+    #[test]
+    fn commit_key_matches_commit() {
+        use crate::kzg_commit::KZG;
+
+        let pp = KZG::new(&[0x62u8; 32], 12).unwrap().public_parameter;
+        let polynomials = [
+            Polynomial::zero(),
+            Polynomial::from(vec![7u64]),
+            // shorter than the table
+            Polynomial::from(vec![1u64, 2, 3]),
+            Polynomial::from_coefficients(edge_and_random_scalars()[..13].to_vec()),
+            Polynomial::from((1..=13u64).collect::<Vec<_>>()),
+        ];
+        for window_bits in [1, 4, 5, 8] {
+            let key = CommitKey::precompute(&pp, window_bits);
+            assert_eq!(key.max_degree(), 12);
+            assert_eq!(key.memory_estimate(), CommitKey::memory_estimate_for(&pp, window_bits));
+            for polynomial in &polynomials {
+                let expected = KZG::commit(&pp, polynomial).unwrap().element;
+                assert_eq!(key.commit(polynomial).unwrap().compress(), expected.compress());
+            }
+        }
+        assert_eq!(CommitKey::precompute(&pp, 4).commit(&Polynomial::zero()).unwrap().compress(), P1::default().compress());
+        // 64 windows of 15 multiples of 96 bytes for each of the 13 powers
+        assert_eq!(CommitKey::memory_estimate_for(&pp, 4), 13 * 64 * 15 * 96);
+
+        // equal terms: 5 * [1]_1 + 5 * [1]_1 would be a doubling
+        let degenerate = PP::from_parts(vec![P1::generator(); 3], pp.points_in_g2.clone());
+        let doubling = Polynomial::from(vec![5u64, 5, 0]);
+        let expected = (Fr::from_u64(10) * P1::generator()).compress();
+        assert_eq!(CommitKey::precompute(&degenerate, 4).commit(&doubling).unwrap().compress(), expected);
+
+        // after truncating the setup, the key is rebuilt from the smaller one
+        let truncated = pp.truncated(4).unwrap();
+        let key = CommitKey::precompute(&truncated, 4);
+        assert_eq!(key.max_degree(), 4);
+        assert!(key.memory_estimate() < CommitKey::memory_estimate_for(&pp, 4));
+        let polynomial = Polynomial::from(vec![3u64, 1, 4, 1, 5]);
+        assert_eq!(
+            key.commit(&polynomial).unwrap().compress(),
+            KZG::commit(&truncated, &polynomial).unwrap().element.compress()
+        );
+        assert!(matches!(
+            key.commit(&polynomials[4]),
+            Err(KZGErrors::PolynomialDegreeTooLarge { degree: 12, max_degree: 4 })
+        ));
+    }
+}
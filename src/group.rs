@@ -0,0 +1,278 @@
+//! Point decoding on top of `blst`.
+//!
+//! `oblast_demo` can compress points but not parse them back, so decompression (with the curve and
+//! subgroup checks) goes through `blst` directly and the result is wrapped with `from_raw`.
+
+use blst::{blst_p1, blst_p1_affine, blst_p2, blst_p2_affine, BLST_ERROR};
+use oblast_demo::{P1, P2};
+
+use crate::kzg_commit::ValidationLevel;
+
+/// size of a compressed G1 point
+pub(crate) const G1_COMPRESSED_SIZE: usize = 48;
+/// size of a compressed G2 point
+pub(crate) const G2_COMPRESSED_SIZE: usize = 96;
+/// size of an uncompressed G1 point (`x || y`, big-endian)
+pub(crate) const G1_UNCOMPRESSED_SIZE: usize = 96;
+/// size of an uncompressed G2 point (`x.c1 || x.c0 || y.c1 || y.c0`, big-endian)
+pub(crate) const G2_UNCOMPRESSED_SIZE: usize = 192;
+
+/// the base field modulus `p`, big-endian
+const FIELD_MODULUS: [u8; 48] = [
+    0x1a, 0x01, 0x11, 0xea, 0x39, 0x7f, 0xe6, 0x9a, 0x4b, 0x1b, 0xa7, 0xb6,
+    0x43, 0x4b, 0xac, 0xd7, 0x64, 0x77, 0x4b, 0x84, 0xf3, 0x85, 0x12, 0xbf,
+    0x67, 0x30, 0xd2, 0xa0, 0xf6, 0xb0, 0xf6, 0x24, 0x1e, 0xab, 0xff, 0xfe,
+    0xb1, 0x53, 0xff, 0xff, 0xb9, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xaa, 0xab,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PointError {
+    /// not a valid encoding of a point on the curve
+    BadEncoding,
+    /// a coordinate is not below the field modulus
+    NonCanonical,
+    /// on the curve, but outside the prime-order subgroup
+    NotInSubgroup,
+}
+
+/// checks every 48-byte big-endian coordinate is below `p`, ignoring the three flag bits at the
+/// top of the first one
+fn check_canonical(bytes: &[u8]) -> Result<(), PointError> {
+    for (i, chunk) in bytes.chunks_exact(FIELD_MODULUS.len()).enumerate() {
+        let mut coordinate: [u8; 48] = chunk.try_into().unwrap();
+        if i == 0 {
+            coordinate[0] &= 0x1f;
+        }
+        // big-endian arrays compare like the numbers they encode
+        if coordinate >= FIELD_MODULUS {
+            return Err(PointError::NonCanonical);
+        }
+    }
+    Ok(())
+}
+
+/// parses a compressed G1 point with the checks of [`ValidationLevel::Full`]
+pub(crate) fn decompress_g1(bytes: &[u8; G1_COMPRESSED_SIZE]) -> Result<P1, PointError> {
+    decompress_g1_with(bytes, ValidationLevel::Full)
+}
+
+/// parses a compressed G1 point, checking the encoding is canonical and the point on the curve,
+/// and (for [`ValidationLevel::Full`]) in the prime-order subgroup
+pub(crate) fn decompress_g1_with(bytes: &[u8; G1_COMPRESSED_SIZE], level: ValidationLevel) -> Result<P1, PointError> {
+    check_canonical(bytes)?;
+    let mut affine = blst_p1_affine::default();
+    let mut point = blst_p1::default();
+
+    unsafe {
+        if blst::blst_p1_uncompress(&mut affine, bytes.as_ptr()) != BLST_ERROR::BLST_SUCCESS {
+            return Err(PointError::BadEncoding);
+        }
+        if level == ValidationLevel::Full && !blst::blst_p1_affine_in_g1(&affine) {
+            return Err(PointError::NotInSubgroup);
+        }
+        blst::blst_p1_from_affine(&mut point, &affine);
+    }
+
+    Ok(P1::from_raw(point))
+}
+
+/// parses a compressed G2 point with the checks of [`ValidationLevel::Full`]
+pub(crate) fn decompress_g2(bytes: &[u8; G2_COMPRESSED_SIZE]) -> Result<P2, PointError> {
+    decompress_g2_with(bytes, ValidationLevel::Full)
+}
+
+/// the G2 counterpart of [`decompress_g1_with`]
+pub(crate) fn decompress_g2_with(bytes: &[u8; G2_COMPRESSED_SIZE], level: ValidationLevel) -> Result<P2, PointError> {
+    check_canonical(bytes)?;
+    let mut affine = blst_p2_affine::default();
+    let mut point = blst_p2::default();
+
+    unsafe {
+        if blst::blst_p2_uncompress(&mut affine, bytes.as_ptr()) != BLST_ERROR::BLST_SUCCESS {
+            return Err(PointError::BadEncoding);
+        }
+        if level == ValidationLevel::Full && !blst::blst_p2_affine_in_g2(&affine) {
+            return Err(PointError::NotInSubgroup);
+        }
+        blst::blst_p2_from_affine(&mut point, &affine);
+    }
+
+    Ok(P2::from_raw(point))
+}
+
+/// parses an uncompressed G1 point, with the same checks as [`decompress_g1`]
+pub(crate) fn deserialize_g1(bytes: &[u8; G1_UNCOMPRESSED_SIZE]) -> Result<P1, PointError> {
+    check_canonical(bytes)?;
+    let mut affine = blst_p1_affine::default();
+    let mut point = blst_p1::default();
+
+    unsafe {
+        if blst::blst_p1_deserialize(&mut affine, bytes.as_ptr()) != BLST_ERROR::BLST_SUCCESS {
+            return Err(PointError::BadEncoding);
+        }
+        if !blst::blst_p1_affine_in_g1(&affine) {
+            return Err(PointError::NotInSubgroup);
+        }
+        blst::blst_p1_from_affine(&mut point, &affine);
+    }
+
+    Ok(P1::from_raw(point))
+}
+
+/// parses an uncompressed G2 point, with the same checks as [`decompress_g2`]
+pub(crate) fn deserialize_g2(bytes: &[u8; G2_UNCOMPRESSED_SIZE]) -> Result<P2, PointError> {
+    check_canonical(bytes)?;
+    let mut affine = blst_p2_affine::default();
+    let mut point = blst_p2::default();
+
+    unsafe {
+        if blst::blst_p2_deserialize(&mut affine, bytes.as_ptr()) != BLST_ERROR::BLST_SUCCESS {
+            return Err(PointError::BadEncoding);
+        }
+        if !blst::blst_p2_affine_in_g2(&affine) {
+            return Err(PointError::NotInSubgroup);
+        }
+        blst::blst_p2_from_affine(&mut point, &affine);
+    }
+
+    Ok(P2::from_raw(point))
+}
+
+/// the affine `blst` form of a point, for code that calls into `blst` directly. `oblast_demo`
+/// doesn't expose its inner point, so this goes through the compressed encoding; the point is
+/// already in the subgroup, so only the decompression itself is done
+pub(crate) fn g1_to_affine(point: &P1) -> blst_p1_affine {
+    let compressed = point.compress();
+    let mut affine = blst_p1_affine::default();
+    let result = unsafe { blst::blst_p1_uncompress(&mut affine, compressed.as_ptr()) };
+    debug_assert_eq!(result, BLST_ERROR::BLST_SUCCESS, "a point re-encodes to a valid encoding");
+    affine
+}
+
+/// `a + b` for any two points, equal ones included, where the `+` of `oblast_demo` only adds
+/// distinct points
+pub(crate) fn g1_add(a: &P1, b: &P1) -> P1 {
+    let mut sum = blst_p1::default();
+    unsafe {
+        blst::blst_p1_from_affine(&mut sum, &g1_to_affine(a));
+        blst::blst_p1_add_or_double_affine(&mut sum, &sum, &g1_to_affine(b));
+    }
+    P1::from_raw(sum)
+}
+
+/// same as [`g1_to_affine`] for G2
+pub(crate) fn g2_to_affine(point: &P2) -> blst_p2_affine {
+    let compressed = point.compress();
+    let mut affine = blst_p2_affine::default();
+    let result = unsafe { blst::blst_p2_uncompress(&mut affine, compressed.as_ptr()) };
+    debug_assert_eq!(result, BLST_ERROR::BLST_SUCCESS, "a point re-encodes to a valid encoding");
+    affine
+}
+
+/// same as [`g1_add`] for G2
+pub(crate) fn g2_add(a: &P2, b: &P2) -> P2 {
+    let mut sum = blst_p2::default();
+    unsafe {
+        blst::blst_p2_from_affine(&mut sum, &g2_to_affine(a));
+        blst::blst_p2_add_or_double_affine(&mut sum, &sum, &g2_to_affine(b));
+    }
+    P2::from_raw(sum)
+}
+
+/// true when an in-memory G1 point is in the prime-order subgroup (the identity included); points
+/// built by this crate always are, so this only matters for points from elsewhere
+pub(crate) fn g1_in_subgroup(point: &P1) -> bool {
+    decompress_g1(point.compress().as_slice().try_into().unwrap()).is_ok()
+}
+
+/// same as [`g1_in_subgroup`] for G2
+pub(crate) fn g2_in_subgroup(point: &P2) -> bool {
+    decompress_g2(point.compress().as_slice().try_into().unwrap()).is_ok()
+}
+
+/// a compressed G1 point on the curve but outside the prime-order subgroup, for tests
+#[cfg(test)]
+pub(crate) fn non_subgroup_g1() -> [u8; G1_COMPRESSED_SIZE] {
+    (1u8..)
+        .map(|x| {
+            let mut bytes = [0u8; G1_COMPRESSED_SIZE];
+            bytes[0] = 0x80;
+            bytes[47] = x;
+            bytes
+        })
+        .find(|bytes| decompress_g1(bytes) == Err(PointError::NotInSubgroup))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oblast_demo::Fr;
+
+    #[test]
+    fn g1_round_trip() {
+        for point in [P1::generator(), Fr::from_u64(12345) * P1::generator(), P1::default()] {
+            let compressed: [u8; 48] = point.compress().try_into().unwrap();
+            assert_eq!(decompress_g1(&compressed).unwrap().compress(), point.compress());
+        }
+    }
+
+    #[test]
+    fn g2_round_trip() {
+        for point in [P2::generator(), Fr::from_u64(12345) * P2::generator(), P2::default()] {
+            let compressed: [u8; 96] = point.compress().try_into().unwrap();
+            assert_eq!(decompress_g2(&compressed).unwrap().compress(), point.compress());
+        }
+    }
+
+    #[test]
+    fn uncompressed_round_trip() {
+        let point = Fr::from_u64(777) * P1::generator();
+        let mut affine = blst_p1_affine::default();
+        let mut bytes = [0u8; G1_UNCOMPRESSED_SIZE];
+        unsafe {
+            blst::blst_p1_uncompress(&mut affine, point.compress().as_ptr());
+            blst::blst_p1_affine_serialize(bytes.as_mut_ptr(), &affine);
+        }
+        assert_eq!(deserialize_g1(&bytes).unwrap().compress(), point.compress());
+
+        let point = Fr::from_u64(777) * P2::generator();
+        let mut affine = blst_p2_affine::default();
+        let mut bytes = [0u8; G2_UNCOMPRESSED_SIZE];
+        unsafe {
+            blst::blst_p2_uncompress(&mut affine, point.compress().as_ptr());
+            blst::blst_p2_affine_serialize(bytes.as_mut_ptr(), &affine);
+        }
+        assert_eq!(deserialize_g2(&bytes).unwrap().compress(), point.compress());
+
+        // a coordinate off the curve
+        bytes[191] ^= 1;
+        assert_eq!(deserialize_g2(&bytes).map(|_| ()), Err(PointError::BadEncoding));
+    }
+
+    #[test]
+    fn rejects_non_canonical_and_non_subgroup_points() {
+        // x = p, with the compression flag set
+        let mut x_is_modulus = FIELD_MODULUS;
+        x_is_modulus[0] |= 0x80;
+        assert_eq!(decompress_g1(&x_is_modulus), Err(PointError::NonCanonical));
+
+        // the imaginary part of x = p
+        let mut g2: [u8; 96] = P2::generator().compress().try_into().unwrap();
+        g2[48..].copy_from_slice(&FIELD_MODULUS);
+        assert_eq!(decompress_g2(&g2), Err(PointError::NonCanonical));
+
+        // on the curve, so only the subgroup check catches it
+        let outside = non_subgroup_g1();
+        assert!(decompress_g1_with(&outside, ValidationLevel::DecompressOnly).is_ok());
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        // the compression flag is missing
+        assert_eq!(decompress_g1(&[0u8; 48]), Err(PointError::BadEncoding));
+        assert_eq!(decompress_g2(&[0u8; 96]), Err(PointError::BadEncoding));
+        // every coordinate is above p
+        assert_eq!(decompress_g2(&[0xffu8; 96]), Err(PointError::NonCanonical));
+    }
+}
@@ -0,0 +1,239 @@
+//! Separate prover and verifier halves of a setup.
+//!
+//! A prover needs every `[tau^i]_1`; a verifier only needs the generators and `[tau]_2`, a few
+//! hundred bytes that can be shipped to verifiers on their own.
+
+use oblast_demo::{Fr, P1, P2};
+
+use crate::group::{self, G1_COMPRESSED_SIZE, G2_COMPRESSED_SIZE};
+use crate::kzg_commit::{self, KZGErrors, Opening, SrsPoints, PP};
+use crate::polynomial::Polynomial;
+
+/// magic bytes at the start of a serialized `VerifierKey`
+const VK_MAGIC: &[u8; 4] = b"KZGV";
+/// current version of the serialized `VerifierKey` format
+const VK_VERSION: u8 = 1;
+/// magic + version + degree + digest + `[1]_1` + `[1]_2` + `[tau]_2`
+pub const VERIFIER_KEY_SIZE: usize = 4 + 1 + 8 + 32 + G1_COMPRESSED_SIZE + 2 * G2_COMPRESSED_SIZE;
+
+/// The G1 powers `[tau^i]_1`, everything needed to commit and open
+#[derive(Clone, Debug)]
+pub struct ProverKey {
+    points_in_g1: Vec<P1>,
+}
+
+/// What a verifier needs: the generators, `[tau]_2`, the supported degree and the digest of the
+/// setup the key was taken from
+#[derive(Clone, Debug)]
+pub struct VerifierKey {
+    /// `[1]_1`
+    pub g1: P1,
+    /// `[1]_2`
+    pub g2: P2,
+    /// `[tau]_2`
+    pub g2_tau: P2,
+    /// the largest degree the matching prover key commits to
+    pub max_degree: usize,
+    /// [`PP::digest`] of the full setup
+    pub digest: [u8; 32],
+}
+
+/// points are compared through their compressed encoding, like [`PP`]
+impl PartialEq for VerifierKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.max_degree == other.max_degree
+            && self.digest == other.digest
+            && self.g1.compress() == other.g1.compress()
+            && self.g2.compress() == other.g2.compress()
+            && self.g2_tau.compress() == other.g2_tau.compress()
+    }
+}
+
+impl PP {
+    /// the verifier half of the setup, without giving up the setup
+    pub fn verifier_key(&self) -> VerifierKey {
+        VerifierKey {
+            g1: self.points_in_g1[0],
+            g2: self.points_in_g2[0],
+            g2_tau: self.point_in_g2(),
+            max_degree: self.max_degree(),
+            digest: self.digest(),
+        }
+    }
+
+    /// splits the setup into the G1 powers for the prover and the small key for verifiers
+    pub fn split(self) -> (ProverKey, VerifierKey) {
+        let verifier_key = self.verifier_key();
+        let prover_key = ProverKey {
+            points_in_g1: self.points_in_g1,
+        };
+        (prover_key, verifier_key)
+    }
+}
+
+impl SrsPoints for ProverKey {
+    fn g1_point(&self, index: usize) -> Result<P1, KZGErrors> {
+        Ok(self.points_in_g1[index])
+    }
+}
+
+impl ProverKey {
+    /// the largest degree that can be committed to
+    pub fn max_degree(&self) -> usize {
+        self.points_in_g1.len() - 1
+    }
+
+    /// the commitment point to `polynomial`, identical to [`kzg_commit::KZG::commit`] with the
+    /// full setup
+    pub fn commit(&self, polynomial: &Polynomial) -> Result<P1, KZGErrors> {
+        kzg_commit::commit_point(self, self.max_degree(), polynomial)
+    }
+
+    /// opens `polynomial` at `z`, identical to [`kzg_commit::Commitment::open_at`] with the full
+    /// setup
+    pub fn open(&self, polynomial: &Polynomial, z: Fr) -> Result<Opening, KZGErrors> {
+        kzg_commit::open_polynomial(self, self.max_degree(), polynomial, z)
+    }
+}
+
+impl VerifierKey {
+    /// serializes the key as: magic `KZGV`, a version byte, the degree as a big-endian u64, the
+    /// setup digest, then the compressed `[1]_1`, `[1]_2` and `[tau]_2`
+    pub fn to_bytes(&self) -> [u8; VERIFIER_KEY_SIZE] {
+        let mut bytes = Vec::with_capacity(VERIFIER_KEY_SIZE);
+        bytes.extend_from_slice(VK_MAGIC);
+        bytes.push(VK_VERSION);
+        bytes.extend_from_slice(&(self.max_degree as u64).to_be_bytes());
+        bytes.extend_from_slice(&self.digest);
+        bytes.extend_from_slice(&self.g1.compress());
+        bytes.extend_from_slice(&self.g2.compress());
+        bytes.extend_from_slice(&self.g2_tau.compress());
+        bytes.try_into().unwrap()
+    }
+
+    /// parses the output of [`VerifierKey::to_bytes`]; the generators must be the standard ones
+    pub fn from_bytes(bytes: &[u8]) -> Result<VerifierKey, KZGErrors> {
+        if bytes.len() < VERIFIER_KEY_SIZE {
+            return Err(KZGErrors::TruncatedInput);
+        }
+        if bytes.len() > VERIFIER_KEY_SIZE {
+            return Err(KZGErrors::TrailingBytes);
+        }
+        if &bytes[..4] != VK_MAGIC {
+            return Err(KZGErrors::InvalidSerializationMagic);
+        }
+        if bytes[4] != VK_VERSION {
+            return Err(KZGErrors::UnsupportedSerializationVersion(bytes[4]));
+        }
+
+        let max_degree = u64::from_be_bytes(bytes[5..13].try_into().unwrap());
+        let digest = bytes[13..45].try_into().unwrap();
+        let (g1_bytes, g2_bytes) = bytes[45..].split_at(G1_COMPRESSED_SIZE);
+        let g1 = group::decompress_g1(g1_bytes.try_into().unwrap()).map_err(|error| kzg_commit::point_error(error, 0))?;
+        let (g2_bytes, g2_tau_bytes) = g2_bytes.split_at(G2_COMPRESSED_SIZE);
+        let g2 = group::decompress_g2(g2_bytes.try_into().unwrap()).map_err(|error| kzg_commit::point_error(error, 1))?;
+        let g2_tau =
+            group::decompress_g2(g2_tau_bytes.try_into().unwrap()).map_err(|error| kzg_commit::point_error(error, 2))?;
+
+        if g1.compress() != P1::generator().compress() || g2.compress() != P2::generator().compress() {
+            return Err(KZGErrors::FirstPointIsNotGenerator);
+        }
+
+        Ok(VerifierKey {
+            g1,
+            g2,
+            g2_tau,
+            max_degree: usize::try_from(max_degree).map_err(|_| KZGErrors::TruncatedInput)?,
+            digest,
+        })
+    }
+}
+
+impl Opening {
+    /// this function verifies the opening with only a [`VerifierKey`] and the commitment point
+    pub fn verify_with_key(&self, vk: &VerifierKey, commitment_point: &P1, z: &Fr) -> bool {
+        // e(C - [y]_1, [1]_2) == e(proof, [tau]_2 - [z]_2)
+        let commitment_minus_y = group::g1_add(commitment_point, &-(self.value * vk.g1));
+        let tau_minus_z = group::g2_add(&vk.g2_tau, &-(*z * vk.g2));
+        oblast_demo::verify_pairings(commitment_minus_y, vk.g2, self.proof, tau_minus_z)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg_commit::{commitment_from_bytes, KZG, OPENING_SIZE};
+    use crate::scalar;
+
+    #[test]
+    fn verifier_from_serialized_key() {
+        // the prover side
+        let pp = KZG::new(&[0x45u8; 32], 32).unwrap().public_parameter;
+        let polynomial = Polynomial::from(vec![1u64, 1, 2, 3, 5, 8, 13, 21, 34]);
+        let expected = KZG::commit(&pp, &polynomial).unwrap().element;
+        let (prover_key, verifier_key) = pp.clone().split();
+
+        let commitment = prover_key.commit(&polynomial).unwrap();
+        assert_eq!(commitment.compress(), expected.compress());
+        let z = Fr::from_u64(1234);
+        let opening = prover_key.open(&polynomial, z).unwrap();
+
+        let vk_bytes = verifier_key.to_bytes();
+        let commitment_bytes: [u8; 48] = commitment.compress().try_into().unwrap();
+        let opening_bytes: [u8; OPENING_SIZE] = opening.to_bytes();
+
+        // the verifier side: only the serialized three-point key and the proof
+        let vk = VerifierKey::from_bytes(&vk_bytes).unwrap();
+        assert_eq!(vk, verifier_key);
+        assert_eq!(vk.digest, pp.digest());
+        assert_eq!(vk.max_degree, 32);
+        let commitment = commitment_from_bytes(&commitment_bytes).unwrap();
+        let opening = Opening::from_bytes(&opening_bytes).unwrap();
+
+        assert!(opening.verify_with_key(&vk, &commitment, &z));
+        assert!(!opening.verify_with_key(&vk, &commitment, &(z + Fr::from_u64(1))));
+
+        // agrees with the old API
+        let full = KZG::commit(&pp, &polynomial).unwrap();
+        assert!(opening.verify(&z, &full));
+
+        // a key from another setup rejects it
+        let other = KZG::new(&[0x46u8; 32], 32).unwrap().public_parameter.verifier_key();
+        assert!(!opening.verify_with_key(&other, &commitment, &z));
+
+        // a constant claimed to open to its negation with the identity as proof
+        let constant = prover_key.commit(&Polynomial::from(vec![4u64])).unwrap();
+        let forged = Opening { value: -Fr::from_u64(4), proof: P1::default() };
+        assert!(!forged.verify_with_key(&vk, &constant, &z));
+
+        // and an honest opening at -tau, where [tau]_2 - [z]_2 doubles
+        let minus_tau = -scalar::fr_from_bytes_be(&[0x45u8; 32]);
+        let opening = prover_key.open(&polynomial, minus_tau).unwrap();
+        assert!(opening.verify_with_key(&vk, &commitment, &minus_tau));
+
+        assert!(matches!(
+            prover_key.commit(&Polynomial::from(vec![1u64; 34])),
+            Err(KZGErrors::PolynomialDegreeTooLarge { degree: 33, max_degree: 32 })
+        ));
+    }
+
+    #[test]
+    fn rejects_malformed_keys() {
+        let bytes = KZG::new(&[0x47u8; 32], 4).unwrap().public_parameter.verifier_key().to_bytes();
+        assert_eq!(bytes.len(), 285);
+
+        assert!(matches!(VerifierKey::from_bytes(&bytes[..284]), Err(KZGErrors::TruncatedInput)));
+        assert!(matches!(VerifierKey::from_bytes(&[&bytes[..], &[0]].concat()), Err(KZGErrors::TrailingBytes)));
+
+        // [tau]_1 in place of the G1 generator
+        let mut not_generator = bytes;
+        let tau_g1 = KZG::new(&[0x47u8; 32], 4).unwrap().public_parameter.points_in_g1[1].compress();
+        not_generator[45..93].copy_from_slice(&tau_g1);
+        assert!(matches!(VerifierKey::from_bytes(&not_generator), Err(KZGErrors::FirstPointIsNotGenerator)));
+
+        let mut bad_point = bytes;
+        bad_point[200] ^= 0xff;
+        assert!(matches!(VerifierKey::from_bytes(&bad_point), Err(KZGErrors::PointNotInSubgroup { index: 2 })));
+    }
+}
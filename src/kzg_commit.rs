@@ -1,257 +1,4553 @@
-use oblast_demo::{curve_order, verify_pairings, Scalar, P1, P2, Fr};
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::ops::{Add, ControlFlow, Mul, Neg, Sub};
+use std::sync::Arc;
+
+use oblast_demo::{curve_order, verify_pairings, P1, P2, Fr};
 use num_bigint::BigUint;
 use rand::prelude::*;
+use sha2::{Digest, Sha256};
+
+use crate::backend::{self, Oblast, PairingBackend};
+use crate::fixed_base;
+use crate::group::{self, G1_COMPRESSED_SIZE, G2_COMPRESSED_SIZE};
+use crate::msm;
+use crate::polynomial; // Important for generating Tau (during power of tau)
+use crate::scalar::{self, FrBytes};
+use crate::secret::{self, SecretBytes};
+use crate::transcript::Transcript;
+
+/// CURVE: BLS12-381 (G1, G2, GT)
+
+
+
+
+/// [0, 1, 2, 3, 4, 5, 6] mod 7
+/// How many point exist in a elliptic curve group? --> `curve_order` 255bits
+/// e(input1, input2) -> output [bilinear pairing]
+/// `Scalar` is a wrapper around `BigUint` that ensures that the value is less than the curve order.
+/// `P1` is a point in G1, `P2` is a point in G2, `Fr` is a scalar.
+
+
+
+
+
+/// Objective:
+/// 1. Define need data-stuctures for KZG (PP -> Public parameter, KZG, Commitment, Opening)
+/// 2. Implement a method to generate a public parameter for KZG
+/// 3. Implement a method to commit to a polynomial
+/// 4. Implement a method to open a polynomial
+/// 5. Implement a method for computing qoutent polynomial 
+/// 6. Implement a method for verifying commitment proofs
+/// 7. Test the implementation
+
+
+
+#[allow(clippy::empty_line_after_doc_comments)]
+#[derive(Clone)]
+pub struct PP<B: PairingBackend = Oblast> {
+    /// Powers of Tau for P1 
+    pub points_in_g1: Vec<B::G1>,
+    /// Powers of Tau for P2: `[1]_2, [tau]_2, ...` (at least two)
+    pub points_in_g2: Vec<B::G2>,
+    /// the largest degree that can be committed to; always `points_in_g1.len() - 1`
+    max_degree: usize,
+}
+
+
+#[derive(Clone)]
+pub  struct KZG<B: PairingBackend = Oblast> {
+    /// Shared Referenced String
+    pub public_parameter: PP<B>
+}
+
+/// Proof that two commitments (possibly under different setups) are to the same polynomial,
+/// see [`prove_equal`]
+#[derive(Debug)]
+pub struct EqualityProof {
+    /// The common value of both polynomials at the challenge point
+    pub value: Fr,
+    /// Opening proof against the first setup
+    pub proof_1: P1,
+    /// Opening proof against the second setup
+    pub proof_2: P1,
+}
+
+/// Proof that a committed polynomial has degree at most `bound`, see [`prove_degree_bound`]
+#[derive(Clone, Debug)]
+pub struct DegreeBoundProof {
+    /// The proven bound on the degree
+    pub bound: usize,
+    /// Commitment to `x^(D - bound) * f(x)`, with `D` the maximum degree of the setup
+    pub shifted: P1,
+}
+
+/// A borrowed, zero-copy prefix of a [`PP`] (see [`PP::as_slice`])
+#[derive(Clone, Copy, Debug)]
+pub struct PPView<'a> {
+    points_in_g1: &'a [P1],
+    points_in_g2: &'a [P2],
+}
+
+pub struct Commitment<'a, S = PP, B: PairingBackend = Oblast> {
+    /// The commitment point 
+    pub element: B::G1,
+    /// The Polynomial committed to 
+    pub polynomial: &'a polynomial::Polynomial<B::Scalar>,
+    /// Public parameter used during the commitment process
+    pub public_parameter: &'a S,
+}
+
+/// An evaluation and its proof; `B` is the curve library, see [`crate::backend`]
+#[derive(Clone, Debug)]
+pub struct Opening<B: PairingBackend = Oblast> {
+    /// The value of the polynomial at the point
+    pub value: B::Scalar,
+    /// This is the proof of an Evaluation
+    pub proof: B::G1,
+}
+
+/// Openings of several commitments at different points folded into two G1 points, see
+/// [`fold_proofs`]
+#[derive(Clone, Debug)]
+pub struct FoldedProof {
+    /// `sum gamma^i proof_i`, the commitment to `h = sum gamma^i (f_i - y_i) / (X - z_i)`
+    pub proof: P1,
+    /// the opening of `L(X) = sum gamma^i (f_i - y_i) / (zeta - z_i) - h(X)` at `zeta`
+    pub quotient: P1,
+    /// the claimed evaluations `y_i`, in the order of the commitments
+    pub values: Vec<Fr>,
+    /// the folding challenge `gamma`
+    pub challenge: Fr,
+}
+
+/// An opening bound to an application context (see [`Commitment::open_at_with_context`])
+#[derive(Debug)]
+pub struct ContextOpening {
+    /// The opening at the requested point
+    pub opening: Opening,
+    /// An extra opening at a point derived from the context
+    pub binding: Opening,
+}
+
+
+// ======================
+// CUSTOM DEFINED ERROR;
+// ======================
+#[derive(Debug)]
+pub enum KZGErrors {
+    SecretMustBeLessThanTheOrderOfTheGroup,
+    /// tau = 0 collapses every power of tau beyond the first to the identity point
+    SecretMustBeNonZero,
+    /// a non-membership proof was requested for a value the polynomial actually takes
+    EvaluationEqualsClaimedValue,
+    /// a 32-byte scalar encoding is not below the curve order
+    NonCanonicalScalar,
+    /// serialized parameters don't start with the expected magic bytes
+    InvalidSerializationMagic,
+    /// serialized parameters use a format version this crate doesn't understand
+    UnsupportedSerializationVersion(u8),
+    /// the input ends before everything announced by the header has been read
+    TruncatedInput,
+    /// the input continues after everything announced by the header has been read
+    TrailingBytes,
+    /// the point at this position (G1 points first, then G2) is not a valid compressed point on the curve
+    InvalidPoint { index: usize },
+    /// a coordinate of the point at this position is not below the field modulus
+    NonCanonicalPoint { index: usize },
+    /// the point at this position is on the curve but outside the prime-order subgroup
+    PointNotInSubgroup { index: usize },
+    /// the first G1 point, where `[1]_1` belongs, is the point at infinity
+    IdentityInGeneratorSlot,
+    /// a hex-encoded scalar or point could not be decoded
+    PointDecodeError,
+    /// public parameters need at least one G1 point
+    EmptyPublicParameter,
+    /// the recorded maximum degree doesn't match the number of G1 points
+    InconsistentMaxDegree { max_degree: usize, points: usize },
+    /// the polynomial has a higher degree than the public parameters support
+    PolynomialDegreeTooLarge { degree: usize, max_degree: usize },
+    /// the first G1 or G2 point of the public parameters is not the generator
+    FirstPointIsNotGenerator,
+    /// the points are not successive powers of a single tau
+    NotPowersOfTau,
+    /// public parameters need at least `[1]_2` and `[tau]_2`
+    NotEnoughG2Powers { found: usize },
+    /// a G2 power beyond the ones generated was requested
+    G2PowerOutOfRange { index: usize, available: usize },
+    /// a smaller setup was requested with a degree above the one available
+    DegreeExceedsSetup { requested: usize, available: usize },
+    /// the serialized parameters don't hash to the digest stored in their header
+    DigestMismatch,
+    /// evaluation domains must have a power-of-two size of at most `2^32`
+    InvalidDomainSize(usize),
+    /// more evaluations were given than the domain has points
+    TooManyEvaluations { evaluations: usize, domain_size: usize },
+    /// a blob must hold exactly one field element per domain point
+    InvalidBlobLength { expected: usize, found: usize },
+    /// the parallel slices passed to a batch function have different lengths (`values` counts the
+    /// points for [`fold_proofs`])
+    MismatchedBatchLengths { openings: usize, values: usize, commitments: usize },
+    /// a long-running operation was stopped by its progress callback
+    Cancelled,
+    /// a setup builder was given no degree
+    MissingDegree,
+    /// a setup builder was given both an explicit tau and a random source
+    ConflictingTauSources,
+    /// the opening quotient `(f(x) - y) / (x - z)` left a remainder, so `y` is not `f(z)`
+    InexactDivision,
+    /// the requested setup degree does not fit in a `usize`
+    DegreeOverflow,
+    /// a point passed to [`Opening::verify_detailed`] is outside the prime-order subgroup
+    VerificationInputNotInSubgroup { input: &'static str },
+    /// the requested setup degree is above the builder's limit ([`MAX_SETUP_DEGREE`] by default)
+    DegreeTooLarge { degree: usize, limit: usize },
+    /// the polynomial at `index` of a batch has a higher degree than the public parameters support
+    BatchPolynomialDegreeTooLarge { index: usize, degree: usize, max_degree: usize },
+    /// [`polynomial::Polynomial::from_coefficients_checked`] was given no coefficients
+    EmptyCoefficients,
+    /// [`crate::domain::Evaluations`] need exactly one value per domain point
+    EvaluationCountMismatch { evaluations: usize, domain_size: usize },
+    /// evaluations over a domain of size `found` were used with a setup or other evaluations over
+    /// a domain of size `expected`
+    DomainMismatch { expected: usize, found: usize },
+    /// commitments made under setups with different [`PP::digest`]s were combined, or a commitment
+    /// was checked against a setup it is neither the same as nor a prefix of
+    SetupMismatch,
+    /// polynomial division was given an empty dividend, or a divisor that is empty or zero
+    EmptyPolynomial,
+    /// a linear combination was given a different number of weights than points
+    MismatchedWeights { points: usize, weights: usize },
+    /// a vector entry past the end of the domain was opened
+    IndexOutOfRange { index: usize, size: usize },
+    /// a coset given to [`crate::domain::divide_by_vanishing_on_coset`] has a point where the
+    /// vanishing polynomial is zero
+    CosetIntersectsDomain,
+    /// the commitment at `index` of a batch was made under a setup with another `[tau]_2` than
+    /// the first one
+    BatchSetupMismatch { index: usize },
+    /// reading or writing a stream failed; the message of the underlying `io::Error`
+    Io(String),
+}
+
+impl fmt::Display for KZGErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KZGErrors::SecretMustBeLessThanTheOrderOfTheGroup => f.write_str("tau must be less than the curve order"),
+            KZGErrors::SecretMustBeNonZero => f.write_str("tau must be non-zero"),
+            KZGErrors::EvaluationEqualsClaimedValue => {
+                f.write_str("the polynomial takes the claimed value, so it cannot be proven different")
+            }
+            KZGErrors::NonCanonicalScalar => f.write_str("scalar encoding is not below the curve order"),
+            KZGErrors::InvalidSerializationMagic => f.write_str("input does not start with the expected magic bytes"),
+            KZGErrors::UnsupportedSerializationVersion(version) => {
+                write!(f, "unsupported serialization format version {}", version)
+            }
+            KZGErrors::TruncatedInput => f.write_str("input ends before the announced data"),
+            KZGErrors::TrailingBytes => f.write_str("input continues after the announced data"),
+            KZGErrors::InvalidPoint { index } => write!(f, "point {} is not a valid compressed curve point", index),
+            KZGErrors::NonCanonicalPoint { index } => {
+                write!(f, "point {} has a coordinate that is not below the field modulus", index)
+            }
+            KZGErrors::PointNotInSubgroup { index } => write!(f, "point {} is outside the prime-order subgroup", index),
+            KZGErrors::IdentityInGeneratorSlot => f.write_str("the first G1 point is the point at infinity"),
+            KZGErrors::PointDecodeError => f.write_str("hex-encoded scalar or point could not be decoded"),
+            KZGErrors::EmptyPublicParameter => f.write_str("public parameters need at least one G1 point"),
+            KZGErrors::InconsistentMaxDegree { max_degree, points } => {
+                write!(f, "maximum degree {} does not match {} G1 points", max_degree, points)
+            }
+            KZGErrors::PolynomialDegreeTooLarge { degree, max_degree } => {
+                write!(f, "polynomial degree {} exceeds the setup's maximum degree {}", degree, max_degree)
+            }
+            KZGErrors::FirstPointIsNotGenerator => f.write_str("the first G1 or G2 point is not the generator"),
+            KZGErrors::NotPowersOfTau => f.write_str("the points are not successive powers of one tau"),
+            KZGErrors::NotEnoughG2Powers { found } => write!(f, "need at least 2 G2 powers, found {}", found),
+            KZGErrors::G2PowerOutOfRange { index, available } => {
+                write!(f, "G2 power {} requested, only {} available", index, available)
+            }
+            KZGErrors::DegreeExceedsSetup { requested, available } => {
+                write!(f, "degree {} requested, the setup supports up to {}", requested, available)
+            }
+            KZGErrors::DigestMismatch => f.write_str("serialized parameters do not match the digest in their header"),
+            KZGErrors::InvalidDomainSize(size) => {
+                write!(f, "domain size {} is not a power of two of at most 2^32", size)
+            }
+            KZGErrors::TooManyEvaluations { evaluations, domain_size } => {
+                write!(f, "{} evaluations for a domain of size {}", evaluations, domain_size)
+            }
+            KZGErrors::InvalidBlobLength { expected, found } => {
+                write!(f, "blob has {} field elements, expected {}", found, expected)
+            }
+            KZGErrors::MismatchedBatchLengths { openings, values, commitments } => write!(
+                f,
+                "batch lengths differ: {} openings, {} values, {} commitments",
+                openings, values, commitments
+            ),
+            KZGErrors::Cancelled => f.write_str("cancelled by the progress callback"),
+            KZGErrors::MissingDegree => f.write_str("no degree was given to the setup builder"),
+            KZGErrors::ConflictingTauSources => f.write_str("the setup builder was given both a tau and a random source"),
+            KZGErrors::InexactDivision => f.write_str("the opening quotient left a remainder"),
+            KZGErrors::DegreeOverflow => f.write_str("the requested setup degree overflows usize"),
+            KZGErrors::VerificationInputNotInSubgroup { input } => {
+                write!(f, "the {} is outside the prime-order subgroup", input)
+            }
+            KZGErrors::DegreeTooLarge { degree, limit } => {
+                write!(f, "setup degree {} is above the limit of {}", degree, limit)
+            }
+            KZGErrors::BatchPolynomialDegreeTooLarge { index, degree, max_degree } => write!(
+                f,
+                "polynomial {} of the batch has degree {}, above the setup's maximum degree {}",
+                index, degree, max_degree
+            ),
+            KZGErrors::EmptyCoefficients => f.write_str("a polynomial needs at least one coefficient"),
+            KZGErrors::EvaluationCountMismatch { evaluations, domain_size } => {
+                write!(f, "{} evaluations for a domain of size {}, expected one per point", evaluations, domain_size)
+            }
+            KZGErrors::DomainMismatch { expected, found } => {
+                write!(f, "evaluations over a domain of size {}, expected size {}", found, expected)
+            }
+            KZGErrors::SetupMismatch => f.write_str("the commitments were made under different setups"),
+            KZGErrors::EmptyPolynomial => f.write_str("cannot divide an empty polynomial or by a zero one"),
+            KZGErrors::MismatchedWeights { points, weights } => {
+                write!(f, "{} weights for a linear combination of {} points", weights, points)
+            }
+            KZGErrors::IndexOutOfRange { index, size } => {
+                write!(f, "index {} is out of range for a vector of {} entries", index, size)
+            }
+            KZGErrors::CosetIntersectsDomain => {
+                f.write_str("the vanishing polynomial is zero at a point of the coset")
+            }
+            KZGErrors::BatchSetupMismatch { index } => {
+                write!(f, "commitment {} of the batch comes from another setup than the first", index)
+            }
+            KZGErrors::Io(message) => write!(f, "I/O error: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for KZGErrors {}
+
+/// a stream that ends early is the same [`KZGErrors::TruncatedInput`] the byte decoders report;
+/// any other failure keeps its message in [`KZGErrors::Io`]
+impl From<io::Error> for KZGErrors {
+    fn from(error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::UnexpectedEof => KZGErrors::TruncatedInput,
+            _ => KZGErrors::Io(error.to_string()),
+        }
+    }
+}
+
+/// for callers working in `io::Result`: truncated input is `UnexpectedEof`, an [`KZGErrors::Io`]
+/// keeps its message and every other error is `InvalidData` with the error as its source
+impl From<KZGErrors> for io::Error {
+    fn from(error: KZGErrors) -> Self {
+        match error {
+            KZGErrors::TruncatedInput => io::Error::new(io::ErrorKind::UnexpectedEof, error),
+            KZGErrors::Io(message) => io::Error::other(message),
+            error => io::Error::new(io::ErrorKind::InvalidData, error),
+        }
+    }
+}
+
+
+// ======================
+// SERIALIZATION
+// ======================
+/// magic bytes at the start of a serialized `PP`
+pub(crate) const PP_MAGIC: &[u8; 4] = b"KZGP";
+/// current version of the serialized `PP` format
+pub(crate) const PP_VERSION: u8 = 3;
+/// magic + version + degree + number of G2 powers + digest
+pub(crate) const PP_HEADER_SIZE: usize = 4 + 1 + 8 + 8 + 32;
+/// version 2 had no digest
+const PP_V2_HEADER_SIZE: usize = 4 + 1 + 8 + 8;
+/// version 1 stored only `[tau]_2`, so it had no G2 count
+const PP_V1_HEADER_SIZE: usize = 4 + 1 + 8;
+/// version of the encoding hashed by [`PP::digest`]; never changes for existing setups
+pub(crate) const PP_DIGEST_VERSION: u8 = 1;
+
+/// How thoroughly [`PP::from_bytes_with_validation`] checks each point
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ValidationLevel {
+    /// canonical encoding, on the curve and in the prime-order subgroup
+    #[default]
+    Full,
+    /// canonical encoding and on the curve, skipping the subgroup check that dominates load time.
+    /// Only for files that are already trusted (e.g. written locally by [`PP::to_bytes`]): the
+    /// header digest still has to match, but it guards against corruption, not against an
+    /// attacker, who can recompute it.
+    DecompressOnly,
+}
+
+/// points are compared through their compressed encoding, since equal points can have different
+/// projective coordinates (e.g. a freshly deserialized point vs a computed one)
+impl PartialEq for PP {
+    fn eq(&self, other: &Self) -> bool {
+        self.max_degree == other.max_degree
+            && self.points_in_g1.len() == other.points_in_g1.len()
+            && self.points_in_g2.len() == other.points_in_g2.len()
+            && self
+                .points_in_g2
+                .iter()
+                .zip(&other.points_in_g2)
+                .all(|(a, b)| a.compress() == b.compress())
+            && self
+                .points_in_g1
+                .iter()
+                .zip(&other.points_in_g1)
+                .all(|(a, b)| a.compress() == b.compress())
+    }
+}
+
+/// equal setups, compared as in the [`PP`] impl above
+impl PartialEq for KZG {
+    fn eq(&self, other: &Self) -> bool {
+        self.public_parameter == other.public_parameter
+    }
+}
+
+/// setups this small are printed in full by `{:#?}`
+const DEBUG_FULL_MAX_POINTS: usize = 8;
+
+/// prints bytes as hex, without the quotes a `String` would get
+struct HexBytes<'a>(&'a [u8]);
+
+impl fmt::Debug for HexBytes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hex::encode(self.0))
+    }
+}
+
+/// prints a compressed point as its first and last 4 bytes of hex
+struct AbbreviatedPoint(Vec<u8>);
+
+impl fmt::Debug for AbbreviatedPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let encoded = hex::encode(&self.0);
+        write!(f, "{}..{}", &encoded[..8], &encoded[encoded.len() - 8..])
+    }
+}
+
+/// a setup can hold thousands of points, so `{:?}` prints a summary: the degree, the
+/// [`PP::digest`] and the first and last G1 points abbreviated. `{:#?}` prints every compressed
+/// point for setups of at most 8 G1 points, and the summary otherwise.
+impl fmt::Debug for PP {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() && self.points_in_g1.len() <= DEBUG_FULL_MAX_POINTS {
+            let g1: Vec<Vec<u8>> = self.points_in_g1.iter().map(|point| point.compress()).collect();
+            let g2: Vec<Vec<u8>> = self.points_in_g2.iter().map(|point| point.compress()).collect();
+            return f
+                .debug_struct("PP")
+                .field("max_degree", &self.max_degree)
+                .field("points_in_g1", &g1.iter().map(|point| HexBytes(point)).collect::<Vec<_>>())
+                .field("points_in_g2", &g2.iter().map(|point| HexBytes(point)).collect::<Vec<_>>())
+                .finish();
+        }
+
+        let mut summary = f.debug_struct("PP");
+        summary
+            .field("max_degree", &self.max_degree)
+            .field("digest", &HexBytes(&self.digest()))
+            .field("g1_points", &self.points_in_g1.len())
+            .field("g2_points", &self.points_in_g2.len());
+        if let (Some(first), Some(last)) = (self.points_in_g1.first(), self.points_in_g1.last()) {
+            summary
+                .field("first_g1", &AbbreviatedPoint(first.compress()))
+                .field("last_g1", &AbbreviatedPoint(last.compress()));
+        }
+        summary.finish()
+    }
+}
+
+/// prints its setup with the summarizing [`PP`] `Debug`
+impl fmt::Debug for KZG {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KZG").field("public_parameter", &self.public_parameter).finish()
+    }
+}
+
+/// prints the compressed commitment point and the degree of the polynomial, leaving out the
+/// borrowed polynomial and setup
+impl<S> fmt::Debug for Commitment<'_, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Commitment")
+            .field("element", &HexBytes(&self.element.compress()))
+            .field("degree", &self.polynomial.degree())
+            .finish()
+    }
+}
+
+impl<B: PairingBackend> PP<B> {
+    /// `[tau]_2`, the G2 point every single-point opening is checked against.
+    ///
+    /// Panics if `points_in_g2` was emptied after construction; [`PP::check_consistency`] reports that.
+    pub fn point_in_g2(&self) -> B::G2 {
+        self.points_in_g2[1]
+    }
+
+    /// the largest degree that can be committed to with these parameters
+    pub fn max_degree(&self) -> usize {
+        self.max_degree
+    }
+
+    /// checks the recorded maximum degree still matches the G1 points (which are public, so they
+    /// may have been modified after construction)
+    pub fn check_consistency(&self) -> Result<(), KZGErrors> {
+        if self.points_in_g1.is_empty() {
+            return Err(KZGErrors::EmptyPublicParameter);
+        }
+        if self.max_degree != self.points_in_g1.len() - 1 {
+            return Err(KZGErrors::InconsistentMaxDegree {
+                max_degree: self.max_degree,
+                points: self.points_in_g1.len(),
+            });
+        }
+        if self.points_in_g2.len() < 2 {
+            return Err(KZGErrors::NotEnoughG2Powers { found: self.points_in_g2.len() });
+        }
+        Ok(())
+    }
+}
+
+impl PP {
+    /// builds parameters from an externally computed G1 basis (e.g. points exported by a ceremony
+    /// library) and the matching `[tau]_2`; `points[i]` is expected to be `[tau^i]_1`.
+    ///
+    /// Nothing here checks that the points actually form a powers-of-tau progression, so they must
+    /// come from a source you trust.
+    pub fn from_g1_points(points: Vec<P1>, g2_tau: P2) -> Result<PP, KZGErrors> {
+        if points.is_empty() {
+            return Err(KZGErrors::EmptyPublicParameter);
+        }
+
+        Ok(PP::from_parts(points, vec![P2::generator(), g2_tau]))
+    }
+
+    /// assembles parameters whose maximum degree is implied by `points_in_g1`, which must not be
+    /// empty; `points_in_g2` must hold at least `[1]_2` and `[tau]_2`
+    pub(crate) fn from_parts(points_in_g1: Vec<P1>, points_in_g2: Vec<P2>) -> PP {
+        assert!(!points_in_g1.is_empty(), "public parameters need at least one G1 point");
+        assert!(points_in_g2.len() >= 2, "public parameters need at least two G2 powers");
+        PP {
+            max_degree: points_in_g1.len() - 1,
+            points_in_g1,
+            points_in_g2,
+        }
+    }
+
+    /// `[tau^i]_2`, as needed for degree-bound and multi-point checks
+    pub fn g2_power(&self, i: usize) -> Result<P2, KZGErrors> {
+        self.points_in_g2.get(i).copied().ok_or(KZGErrors::G2PowerOutOfRange {
+            index: i,
+            available: self.points_in_g2.len(),
+        })
+    }
+
+    /// SHA-256 fingerprint of the setup, for checking two parties use the same parameters.
+    ///
+    /// It hashes a fixed encoding that doesn't follow the serialization format: magic `KZGP`, a
+    /// digest version byte, the degree and the number of G2 powers as big-endian u64s, then every
+    /// compressed G1 and G2 point in order. So the digest of a setup stays the same across releases
+    /// and platforms.
+    pub fn digest(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(PP_MAGIC);
+        hasher.update([PP_DIGEST_VERSION]);
+        hasher.update((self.max_degree as u64).to_be_bytes());
+        hasher.update((self.points_in_g2.len() as u64).to_be_bytes());
+        for point in &self.points_in_g1 {
+            hasher.update(point.compress());
+        }
+        for point in &self.points_in_g2 {
+            hasher.update(point.compress());
+        }
+        hasher.finalize().into()
+    }
+
+    /// the first 8 bytes of [`PP::digest`] as hex, short enough for logs
+    pub fn short_id(&self) -> String {
+        hex::encode(&self.digest()[..8])
+    }
+
+    /// serializes the parameters as: magic `KZGP`, a version byte, the degree and the number of G2
+    /// powers as big-endian u64s, the 32-byte [`PP::digest`], the 48-byte compressed G1 powers in
+    /// order and finally the 96-byte compressed G2 powers in order
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let degree = self.max_degree;
+        let mut bytes = Vec::with_capacity(
+            PP_HEADER_SIZE
+                + self.points_in_g1.len() * G1_COMPRESSED_SIZE
+                + self.points_in_g2.len() * G2_COMPRESSED_SIZE,
+        );
+
+        bytes.extend_from_slice(PP_MAGIC);
+        bytes.push(PP_VERSION);
+        bytes.extend_from_slice(&(degree as u64).to_be_bytes());
+        bytes.extend_from_slice(&(self.points_in_g2.len() as u64).to_be_bytes());
+        bytes.extend_from_slice(&self.digest());
+        for point in &self.points_in_g1 {
+            bytes.extend_from_slice(&point.compress());
+        }
+        for point in &self.points_in_g2 {
+            bytes.extend_from_slice(&point.compress());
+        }
+
+        bytes
+    }
+
+    /// parses the output of [`PP::to_bytes`], decompressing (and so validating) every point and
+    /// checking the digest in the header. Version 1 (a single `[tau]_2`) and version 2 (no digest)
+    /// encodings are still accepted.
+    pub fn from_bytes(bytes: &[u8]) -> Result<PP, KZGErrors> {
+        PP::from_bytes_with_validation(bytes, ValidationLevel::Full)
+    }
+
+    /// same as [`PP::from_bytes`] with the point checks of `level`. Encodings without a digest
+    /// (versions 1 and 2) are always fully validated.
+    pub fn from_bytes_with_validation(bytes: &[u8], level: ValidationLevel) -> Result<PP, KZGErrors> {
+        let PPHeader { header_size, degree, g2_count, digest, g2_size, expected_len } = PPHeader::parse(bytes)?;
+        if bytes.len() < expected_len {
+            return Err(KZGErrors::TruncatedInput);
+        }
+        if bytes.len() > expected_len {
+            return Err(KZGErrors::TrailingBytes);
+        }
+
+        let body = &bytes[header_size..];
+        let (g1_bytes, g2_bytes) = body.split_at(body.len() - g2_size);
+
+        let level = if digest.is_some() { level } else { ValidationLevel::Full };
+        let (points_in_g1, mut points_in_g2) = decode_points(g1_bytes, g2_bytes, level)?;
+        check_generator_slot(&points_in_g1)?;
+        if g2_count == 1 {
+            points_in_g2.insert(0, P2::generator());
+        }
+
+        let public_parameter = PP {
+            points_in_g1,
+            points_in_g2,
+            max_degree: degree as usize,
+        };
+        public_parameter.check_consistency()?;
+        if digest.is_some_and(|digest| digest != public_parameter.digest()) {
+            return Err(KZGErrors::DigestMismatch);
+        }
+
+        Ok(public_parameter)
+    }
+}
+
+impl PP {
+    /// writes [`PP::to_bytes`] to `writer`
+    pub fn write_to<W: Write>(&self, mut writer: W) -> Result<(), KZGErrors> {
+        writer.write_all(&self.to_bytes())?;
+        Ok(())
+    }
+
+    /// reads one encoding written by [`PP::write_to`] (or [`PP::to_bytes`]) from `reader` and parses
+    /// it like [`PP::from_bytes`]. Only the length announced by the header is read, so the reader is
+    /// left at whatever follows; a stream that ends before that is [`KZGErrors::TruncatedInput`].
+    pub fn read_from<R: Read>(mut reader: R) -> Result<PP, KZGErrors> {
+        let mut bytes = vec![0u8; PP_V1_HEADER_SIZE];
+        reader.read_exact(&mut bytes)?;
+        bytes.resize(PPHeader::size(&bytes)?, 0);
+        reader.read_exact(&mut bytes[PP_V1_HEADER_SIZE..])?;
+
+        // grown as the data arrives rather than allocated up front, since the header is not
+        // trusted yet
+        let remaining = PPHeader::parse(&bytes)?.expected_len - bytes.len();
+        reader.take(remaining as u64).read_to_end(&mut bytes)?;
+        PP::from_bytes(&bytes)
+    }
+}
+
+/// the fields of a serialized `PP` header, in any of the supported versions
+struct PPHeader {
+    header_size: usize,
+    degree: u64,
+    /// 1 for version 1, which only stores `[tau]_2`
+    g2_count: u64,
+    /// absent before version 3
+    digest: Option<[u8; 32]>,
+    g2_size: usize,
+    /// the length of the whole encoding
+    expected_len: usize,
+}
+
+impl PPHeader {
+    /// the header size for the version in `bytes`, which must hold at least the magic and version
+    fn size(bytes: &[u8]) -> Result<usize, KZGErrors> {
+        if bytes.len() < PP_V1_HEADER_SIZE {
+            return Err(KZGErrors::TruncatedInput);
+        }
+        if &bytes[..4] != PP_MAGIC {
+            return Err(KZGErrors::InvalidSerializationMagic);
+        }
+        match bytes[4] {
+            1 => Ok(PP_V1_HEADER_SIZE),
+            2 => Ok(PP_V2_HEADER_SIZE),
+            PP_VERSION => Ok(PP_HEADER_SIZE),
+            version => Err(KZGErrors::UnsupportedSerializationVersion(version)),
+        }
+    }
+
+    /// the header at the start of `bytes`; the points after it don't have to be there yet
+    fn parse(bytes: &[u8]) -> Result<PPHeader, KZGErrors> {
+        let header_size = PPHeader::size(bytes)?;
+        if bytes.len() < header_size {
+            return Err(KZGErrors::TruncatedInput);
+        }
+
+        let degree = u64::from_be_bytes(bytes[5..13].try_into().unwrap());
+        let (g2_count, digest) = if bytes[4] == 1 {
+            (1, None)
+        } else {
+            let g2_count = u64::from_be_bytes(bytes[13..21].try_into().unwrap());
+            if g2_count < 2 {
+                return Err(KZGErrors::NotEnoughG2Powers { found: g2_count as usize });
+            }
+            let digest: Option<[u8; 32]> = (bytes[4] == PP_VERSION).then(|| bytes[21..53].try_into().unwrap());
+            (g2_count, digest)
+        };
+
+        let g2_size = usize::try_from(g2_count)
+            .ok()
+            .and_then(|count| count.checked_mul(G2_COMPRESSED_SIZE))
+            .ok_or(KZGErrors::TruncatedInput)?;
+        let expected_len = usize::try_from(degree)
+            .ok()
+            .and_then(|degree| degree.checked_add(1))
+            .and_then(|count| count.checked_mul(G1_COMPRESSED_SIZE))
+            .and_then(|size| size.checked_add(header_size))
+            .and_then(|size| size.checked_add(g2_size))
+            .ok_or(KZGErrors::TruncatedInput)?;
+
+        Ok(PPHeader { header_size, degree, g2_count, digest, g2_size, expected_len })
+    }
+}
+
+/// the error for a point that failed to decode at `index`
+pub(crate) fn point_error(error: group::PointError, index: usize) -> KZGErrors {
+    match error {
+        group::PointError::BadEncoding => KZGErrors::InvalidPoint { index },
+        group::PointError::NonCanonical => KZGErrors::NonCanonicalPoint { index },
+        group::PointError::NotInSubgroup => KZGErrors::PointNotInSubgroup { index },
+    }
+}
+
+/// `[1]_1` may be replaced by another point in a malformed setup, but never by the identity, which
+/// would make every commitment ignore the constant coefficient
+pub(crate) fn check_generator_slot(points_in_g1: &[P1]) -> Result<(), KZGErrors> {
+    match points_in_g1.first() {
+        Some(point) if point.compress() == P1::default().compress() => Err(KZGErrors::IdentityInGeneratorSlot),
+        _ => Ok(()),
+    }
+}
+
+/// decompresses back-to-back compressed G1 points followed by compressed G2 points; invalid points
+/// are reported by their position, G1 points first
+pub(crate) fn decode_points(
+    g1_bytes: &[u8],
+    g2_bytes: &[u8],
+    level: ValidationLevel,
+) -> Result<(Vec<P1>, Vec<P2>), KZGErrors> {
+    let points_in_g1 = g1_bytes
+        .chunks_exact(G1_COMPRESSED_SIZE)
+        .enumerate()
+        .map(|(index, chunk)| {
+            group::decompress_g1_with(chunk.try_into().unwrap(), level).map_err(|error| point_error(error, index))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let points_in_g2 = g2_bytes
+        .chunks_exact(G2_COMPRESSED_SIZE)
+        .enumerate()
+        .map(|(index, chunk)| {
+            group::decompress_g2_with(chunk.try_into().unwrap(), level)
+                .map_err(|error| point_error(error, points_in_g1.len() + index))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((points_in_g1, points_in_g2))
+}
+
+
+/// how many G1 points [`KZG::setup_with_progress`] computes between two progress reports
+pub const SETUP_PROGRESS_INTERVAL: usize = 256;
+
+/// Reported by [`KZG::setup_with_progress`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SetupProgress {
+    /// G1 points computed so far
+    pub computed: usize,
+    /// G1 points in the finished setup (`degree + 1`)
+    pub total: usize,
+}
+
+/// the largest setup degree built unless the builder is given another limit: `2^20` takes about
+/// 50 MB of G1 points, and a mistyped degree fails with [`KZGErrors::DegreeTooLarge`] instead of
+/// running out of memory
+pub const MAX_SETUP_DEGREE: usize = 1 << 20;
+
+/// the G2 powers generated unless asked otherwise: `[1]_2` and `[tau]_2`
+pub const DEFAULT_G2_POWERS: usize = 2;
+
+/// domain-separation tag hashed in front of the seed by [`KZG::insecure_setup_from_seed`], so no
+/// seed can reproduce a tau chosen any other way
+const INSECURE_SETUP_TAG: &[u8] = b"kzg-insecure-test-setup";
+
+/// the seed behind [`KZG::insecure_test_setup`]
+const INSECURE_TEST_SEED: &[u8] = b"kzg-impl fixed test seed";
+
+/// the tau of [`KZG::insecure_tiny_setup`]
+#[cfg(feature = "testing")]
+pub const TINY_INSECURE_TAU: u8 = 5;
+
+impl KZG {
+    /// creating a new KZG instance from a known tau.
+    ///
+    /// This exists for testing and for reproducing reference vectors: whoever knows `tau` can forge
+    /// proofs, so production setups should come from [`KZG::new_rand`] (or a ceremony).
+    ///
+    /// ```
+    /// use kzg_impl::kzg_commit::KZG;
+    /// use kzg_impl::polynomial::{Fr, Polynomial};
+    ///
+    /// let kzg = KZG::new(&[7u8; 32], 4).unwrap();
+    /// let polynomial = Polynomial::from_coefficients(vec![Fr::from_u64(1), Fr::from_u64(2), Fr::from_u64(3)]);
+    ///
+    /// let commitment = KZG::commit(&kzg.public_parameter, &polynomial).unwrap();
+    /// let point = Fr::from_u64(5);
+    /// let opening = commitment.open_at(point).unwrap();
+    ///
+    /// assert_eq!(opening.value, Fr::from_u64(86));
+    /// assert!(opening.verify(&point, &commitment));
+    /// ```
+    pub fn new(tau: &[u8; 32], degree: usize) -> Result<KZG, KZGErrors> {
+        KZG::setup(tau, degree)
+    }
+
+    /// this is the random generation function, and the one to use in production: tau is sampled
+    /// uniformly below the curve order and dropped once the powers of tau are computed
+    ///
+    /// ```
+    /// use kzg_impl::kzg_commit::KZG;
+    /// use kzg_impl::polynomial::{Fr, Polynomial};
+    ///
+    /// let kzg = KZG::new_rand(8).unwrap();
+    /// assert_eq!(kzg.degree(), 8);
+    ///
+    /// let polynomial = Polynomial::from_coefficients(vec![Fr::from_u64(4), Fr::from_u64(0), Fr::from_u64(9)]);
+    /// let commitment = KZG::commit(&kzg.public_parameter, &polynomial).unwrap();
+    /// let opening = commitment.open_at(Fr::from_u64(3)).unwrap();
+    /// assert!(opening.verify(&Fr::from_u64(3), &commitment));
+    /// ```
+    pub fn new_rand(degree: usize) -> Result<KZG, KZGErrors> {
+        KZG::new_rand_with_rng(&mut thread_rng(), degree)
+    }
+
+    /// same as [`KZG::new_rand`] but drawing tau from the given cryptographically secure RNG
+    /// (e.g. `OsRng`, a hardware RNG, or a seeded `StdRng` for reproducible tests)
+    pub fn new_rand_with_rng<R: RngCore + CryptoRng>(rng: &mut R, degree: usize) -> Result<KZG, KZGErrors> {
+        KZG::builder().degree(degree).random(rng).build()
+    }
+
+    /// same as [`KZG::new_rand`] but also hands back the sampled tau, so a random setup can be
+    /// reproduced later with [`KZG::new`].
+    ///
+    /// SECURITY: the returned tau is the toxic waste of the setup. Anyone holding it can forge
+    /// openings for any commitment made against this setup, so only use this in tests and never
+    /// persist or log the bytes for a setup that protects anything.
+    pub fn new_rand_with_tau(degree: usize) -> Result<(KZG, [u8; 32]), KZGErrors> {
+        let secret = sample_secret(&mut thread_rng());
+        let kzg = KZG::builder().degree(degree).tau_bytes(*secret).build()?;
+
+        Ok((kzg, *secret))
+    }
+
+    /// this function takes in tau (big-endian bytes) and computes the powers of tau up to `degree`
+    pub fn setup(tau: &[u8; 32], degree: usize) -> Result<KZG, KZGErrors> {
+        KZG::builder().degree(degree).tau_bytes(*tau).build()
+    }
+
+    /// same as [`KZG::setup`] but also generating `[tau^i]_2` for `i < g2_powers` (at least 2),
+    /// for degree-bound proofs and multi-point openings
+    pub fn setup_with_g2_powers(tau: &[u8; 32], degree: usize, g2_powers: usize) -> Result<KZG, KZGErrors> {
+        KZG::builder().degree(degree).tau_bytes(*tau).g2_powers(g2_powers).build()
+    }
+
+    /// same as [`KZG::new_rand`] with `g2_powers` G2 powers, see [`KZG::setup_with_g2_powers`]
+    pub fn new_rand_with_g2_powers(degree: usize, g2_powers: usize) -> Result<KZG, KZGErrors> {
+        KZG::builder().degree(degree).random(&mut thread_rng()).g2_powers(g2_powers).build()
+    }
+
+    /// INSECURE, for tests and fixtures only: a setup whose tau is
+    /// `SHA-256("kzg-insecure-test-setup" || seed)` reduced mod the curve order.
+    ///
+    /// The same seed gives the same setup on every run and platform. Since the seed determines
+    /// tau, anyone who knows it can forge proofs, so never use such a setup to secure anything.
+    pub fn insecure_setup_from_seed(seed: &[u8], degree: usize) -> Result<KZG, KZGErrors> {
+        let mut hasher = Sha256::new();
+        hasher.update(INSECURE_SETUP_TAG);
+        hasher.update(seed);
+        let tau = scalar::fr_from_bytes_be(&hasher.finalize());
+
+        KZG::builder().degree(degree).tau_bytes(tau.to_bytes_be()).build()
+    }
+
+    /// INSECURE, for tests only: [`KZG::insecure_setup_from_seed`] with a fixed seed
+    pub fn insecure_test_setup(degree: usize) -> Result<KZG, KZGErrors> {
+        KZG::insecure_setup_from_seed(INSECURE_TEST_SEED, degree)
+    }
+
+    /// INSECURE, for examples and documentation only (behind the `testing` feature): a setup with
+    /// the fixed, publicly known tau `TINY_INSECURE_TAU = 5`.
+    ///
+    /// It takes nothing but the degree, so examples need neither an RNG nor hex bytes. Anyone can
+    /// forge proofs against it.
+    #[cfg(feature = "testing")]
+    pub fn insecure_tiny_setup(degree: usize) -> KZG {
+        let mut tau = [0u8; 32];
+        tau[31] = TINY_INSECURE_TAU;
+        KZG::builder().degree(degree).tau_bytes(tau).build().expect("5 is a valid tau")
+    }
+
+    /// the maximum degree of a polynomial that can be committed to with this setup
+    pub fn degree(&self) -> usize {
+        self.public_parameter.max_degree()
+    }
+
+    /// same as [`KZG::setup`], reporting progress while the G1 powers are computed.
+    ///
+    /// `progress` is called after every [`SETUP_PROGRESS_INTERVAL`] G1 points (and once more for the
+    /// last, shorter block); returning `ControlFlow::Break` stops the setup with
+    /// [`KZGErrors::Cancelled`]. With the `parallel` feature each block is computed across the pool
+    /// and cancellation is checked between blocks. The result is identical to [`KZG::setup`].
+    pub fn setup_with_progress(
+        tau: &[u8; 32],
+        degree: usize,
+        progress: impl FnMut(SetupProgress) -> ControlFlow<()>,
+    ) -> Result<KZG, KZGErrors> {
+        KZG::builder().degree(degree).tau_bytes(*tau).with_progress(progress).build()
+    }
+
+    /// this function takes in tau and computes the powers of tau; every constructor ends up here
+    /// through [`crate::builder::KZGBuilder`]
+    pub(crate) fn setup_with_callback(
+        tau: &[u8; 32],
+        degree: usize,
+        g2_powers: usize,
+        progress: Option<&mut dyn FnMut(SetupProgress) -> ControlFlow<()>>,
+    ) -> Result<KZG, KZGErrors> {
+        if g2_powers < 2 {
+            return Err(KZGErrors::NotEnoughG2Powers { found: g2_powers });
+        }
+
+        let modulus = curve_order();
+        let bytes_tau = BigUint::from_bytes_be(tau);
+
+
+        if bytes_tau >= modulus {
+            return Err(KZGErrors::SecretMustBeLessThanTheOrderOfTheGroup);
+        }
+
+        if bytes_tau == BigUint::default() {
+            return Err(KZGErrors::SecretMustBeNonZero);
+        }
+
+        // the only BigUint -> field conversion; every further power is a single field multiplication
+        let mut tau_limbs = bytes_tau.to_u64_digits();
+        let tau = scalar::fr_from_u64_limbs(&tau_limbs);
+        secret::wipe(&mut tau_limbs);
+
+        // obtaining the "power of tau" (a part of the public parameter)
+        let powers = powers_of_tau(tau, degree);
+        let points_in_g1 = match progress {
+            None => g1_powers(&powers),
+            Some(progress) => {
+                let mut points = Vec::with_capacity(powers.len());
+                for block in powers.chunks(SETUP_PROGRESS_INTERVAL) {
+                    points.extend(g1_powers(block));
+                    let report = SetupProgress {
+                        computed: points.len(),
+                        total: powers.len(),
+                    };
+                    if progress(report).is_break() {
+                        return Err(KZGErrors::Cancelled);
+                    }
+                }
+                points
+            }
+        };
+
+        let g2 = fixed_base::g2_generator_table();
+        let points_in_g2 = powers_of_tau(tau, g2_powers - 1).iter().map(|power| g2.mul(power)).collect();
+
+        let public_parameter = PP::from_parts(points_in_g1, points_in_g2);
+
+        Ok(
+            KZG {
+                public_parameter
+            }
+        )
+    }
+
+    /// this function takes in a public parameter (a [`PP`] or a [`PPView`] of one) and a polynomial and returns a commitment, this commitment is this struct is a point on the G1 curve
+    pub fn commit<'a, S: Srs>(
+        public_parameter: &'a S,
+        polynomial: &'a polynomial::Polynomial,
+    ) -> Result<Commitment<'a, S>, KZGErrors> {
+        public_parameter.check_consistency()?;
+        let element = commit_point(public_parameter, public_parameter.max_degree(), polynomial)?;
+
+        Ok(Commitment {
+            element,
+            polynomial,
+            public_parameter,
+        })
+    }
+
+    /// this function commits to many polynomials under the same setup, returning the commitment
+    /// points in the same order. Every polynomial is checked against the setup degree as in
+    /// [`KZG::commit`]; with the `parallel` feature the polynomials are spread across the rayon pool
+    pub fn commit_many(public_parameter: &PP, polynomials: &[&polynomial::Polynomial]) -> Result<Vec<P1>, KZGErrors> {
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+
+            polynomials
+                .par_iter()
+                .map(|polynomial| KZG::commit(public_parameter, polynomial).map(|commitment| commitment.element))
+                .collect()
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            polynomials
+                .iter()
+                .map(|polynomial| KZG::commit(public_parameter, polynomial).map(|commitment| commitment.element))
+                .collect()
+        }
+    }
+
+    /// this function commits to every polynomial of `polynomials` under the same setup, converting
+    /// the setup points for the MSM once for the whole batch instead of once per polynomial; with
+    /// the `parallel` feature the polynomials are spread across the rayon pool. All the degrees are
+    /// checked before any commitment is computed, and the first polynomial above the setup degree
+    /// is reported by its index. Each commitment is the one [`KZG::commit`] returns
+    pub fn commit_batch<'a>(
+        public_parameter: &'a PP,
+        polynomials: &'a [polynomial::Polynomial],
+    ) -> Result<Vec<Commitment<'a>>, KZGErrors> {
+        public_parameter.check_consistency()?;
+        let max_degree = public_parameter.max_degree();
+        let mut lengths = Vec::with_capacity(polynomials.len());
+        for (index, polynomial) in polynomials.iter().enumerate() {
+            let length = match polynomial.degree() {
+                Some(degree) if degree > max_degree => {
+                    return Err(KZGErrors::BatchPolynomialDegreeTooLarge { index, degree, max_degree })
+                }
+                degree => degree.map_or(0, |degree| degree + 1),
+            };
+            lengths.push(length);
+        }
+
+        // only the polynomials long enough for Pippenger need the converted points
+        let longest = lengths.iter().copied().filter(|&length| length >= msm::PIPPENGER_THRESHOLD).max();
+        let bases = msm::to_affine(&public_parameter.points_in_g1[..longest.unwrap_or(0)]);
+        let commit_one = |(polynomial, &length): (&'a polynomial::Polynomial, &usize)| {
+            let coefficients = &polynomial.coefficients[..length];
+            let element = if length < msm::PIPPENGER_THRESHOLD {
+                msm::naive(coefficients, &public_parameter.points_in_g1[..length])
+            } else {
+                msm::pippenger_affine(coefficients, &bases[..length])
+            };
+            Commitment {
+                element,
+                polynomial,
+                public_parameter,
+            }
+        };
+
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+
+            Ok(polynomials.par_iter().zip(&lengths).map(commit_one).collect())
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            Ok(polynomials.iter().zip(&lengths).map(commit_one).collect())
+        }
+    }
+}
+
+
+impl<'a, S: Srs> Commitment<'a, S> {
+    /// this function takes in a point and returns an opening, this opening is a struct that contains the value of the polynomial at the point and the proof of the evaluation  
+    pub fn open_at(&self, point: Fr) -> Result<Opening, KZGErrors> {
+        // the fields are public, so the polynomial may not be the one `KZG::commit` checked
+        self.public_parameter.check_consistency()?;
+        open_polynomial(self.public_parameter, self.public_parameter.max_degree(), self.polynomial, point)
+    }
+
+    /// same as [`Commitment::open_at`], also returning the quotient `q(x) = (f(x) - f(z)) / (x - z)`
+    /// whose commitment is the proof, for callers that go on to commit to or open `q` themselves
+    pub fn open_at_with_quotient(&self, z: Fr) -> Result<(Opening, polynomial::Polynomial), KZGErrors> {
+        self.public_parameter.check_consistency()?;
+        open_polynomial_with_quotient(self.public_parameter, self.public_parameter.max_degree(), self.polynomial, z)
+    }
+
+    /// same as [`Commitment::open_at`] for a point received as 32 big-endian bytes; non-canonical
+    /// encodings (>= curve order) are rejected instead of being reduced
+    pub fn open_at_bytes(&self, point: &[u8; 32]) -> Result<Opening, KZGErrors> {
+        self.open_at(Fr::from_bytes_checked(point)?)
+    }
+
+    /// opens at `z` and binds the proof to `context` (e.g. a protocol instance or session id).
+    ///
+    /// A plain KZG opening only says "f(z) = y" and can be replayed anywhere that statement is
+    /// accepted. Here a Fiat–Shamir challenge point `zeta = H(context, C, z, f(z))` is derived and
+    /// the polynomial is additionally opened at `zeta`. Producing the binding opening for a
+    /// different context requires opening at an unpredictable new point, which needs the
+    /// polynomial itself, so a captured proof cannot be re-used under another context.
+    /// Note that this reveals `f(zeta)` as well as `f(z)`.
+    pub fn open_at_with_context(&self, z: Fr, context: &[u8]) -> Result<ContextOpening, KZGErrors> {
+        let opening = self.open_at(z)?;
+        let zeta = context_challenge(context, &self.element, &z, &opening.value);
+        let binding = self.open_at(zeta)?;
+
+        Ok(ContextOpening { opening, binding })
+    }
+
+    /// this function proves that the committed polynomial does NOT evaluate to `y` at `z`.
+    /// It works by opening at `z` to the true value `f(z)`, so it reveals `f(z)` to the verifier.
+    /// Returns the opening together with the claimed (wrong) value `y`.
+    pub fn open_not_equal(&self, z: Fr, y: Fr) -> Result<(Opening, Fr), KZGErrors> {
+        let opening = self.open_at(z)?;
+        if opening.value == y {
+            return Err(KZGErrors::EvaluationEqualsClaimedValue);
+        }
+
+        Ok((opening, y))
+    }
+
+    /// this function opens the difference `f_self - f_other` at `z`, for proving how an updated
+    /// polynomial (`self`) changed from an older one (`other`) committed under the same setup.
+    /// By the additive homomorphism, the verifier checks it against `C_self - C_other`
+    /// (see [`Opening::verify_delta`])
+    pub fn open_delta(&self, other: &Commitment<S>, z: Fr) -> Result<Opening, KZGErrors> {
+        self.public_parameter.check_consistency()?;
+        let difference = self.polynomial - other.polynomial;
+        open_polynomial(self.public_parameter, self.public_parameter.max_degree(), &difference, z)
+    }
+}
+
+
+// ===================================
+// GENERIC BACKENDS
+// ===================================
+/// the protocol over any [`PairingBackend`], through the steps of [`crate::backend`]; with the
+/// default backend these give the same points as [`KZG::new`], [`KZG::commit`] and
+/// [`Commitment::open_at`]
+impl<B: PairingBackend> KZG<B> {
+    /// the powers `[tau^i]_1` up to `degree` and `[1]_2, [tau]_2`, computed by the backend from a
+    /// known, non-zero `tau`. As with [`KZG::new`], whoever knows `tau` can forge proofs
+    pub fn setup_with_backend(tau: &B::Scalar, degree: usize) -> Result<KZG<B>, KZGErrors> {
+        if *tau == B::scalar_from_u64(0) {
+            return Err(KZGErrors::SecretMustBeNonZero);
+        }
+
+        let mut points_in_g1 = Vec::with_capacity(degree + 1);
+        let mut power = B::scalar_from_u64(1);
+        for _ in 0..=degree {
+            points_in_g1.push(B::g1_mul(&power, &B::g1_generator()));
+            power = power * *tau;
+        }
+        let points_in_g2 = vec![B::g2_generator(), B::g2_mul(tau, &B::g2_generator())];
+
+        Ok(KZG {
+            public_parameter: PP {
+                max_degree: degree,
+                points_in_g1,
+                points_in_g2,
+            },
+        })
+    }
+
+    /// [`KZG::commit`] with the backend's point arithmetic
+    pub fn commit_with_backend<'a>(
+        public_parameter: &'a PP<B>,
+        polynomial: &'a polynomial::Polynomial<B::Scalar>,
+    ) -> Result<Commitment<'a, PP<B>, B>, KZGErrors> {
+        public_parameter.check_consistency()?;
+        let coefficients = backend_coefficients::<B>(public_parameter, polynomial)?;
+
+        Ok(Commitment {
+            element: backend::commit::<B>(&public_parameter.points_in_g1, coefficients),
+            polynomial,
+            public_parameter,
+        })
+    }
+}
+
+impl<B: PairingBackend> Commitment<'_, PP<B>, B> {
+    /// [`Commitment::open_at`] with the backend's field and point arithmetic
+    pub fn open_with_backend(&self, z: B::Scalar) -> Result<Opening<B>, KZGErrors> {
+        self.public_parameter.check_consistency()?;
+        let coefficients = backend_coefficients::<B>(self.public_parameter, self.polynomial)?;
+        let (value, proof) = backend::open::<B>(&self.public_parameter.points_in_g1, coefficients, &z);
+        Ok(Opening { value, proof })
+    }
+
+    /// [`Opening::verify`] against this commitment, with the backend's pairing check
+    pub fn verify_with_backend(&self, z: &B::Scalar, opening: &Opening<B>) -> bool {
+        opening.verify_with_backend(z, &self.element, &self.public_parameter.point_in_g2())
+    }
+}
+
+/// the coefficients of `polynomial` up to its degree, which must be supported by `public_parameter`
+fn backend_coefficients<'a, B: PairingBackend>(
+    public_parameter: &PP<B>,
+    polynomial: &'a polynomial::Polynomial<B::Scalar>,
+) -> Result<&'a [B::Scalar], KZGErrors> {
+    let coefficients = backend::significant::<B>(&polynomial.coefficients);
+    let max_degree = public_parameter.max_degree();
+    match coefficients.len().checked_sub(1) {
+        Some(degree) if degree > max_degree => Err(KZGErrors::PolynomialDegreeTooLarge { degree, max_degree }),
+        _ => Ok(coefficients),
+    }
+}
+
+// ===================================
+// SETUP VIEWS
+// ===================================
+/// Access to the G1 commitment basis one point at a time, so setups that don't hold every point in
+/// memory (like the memory-mapped `MappedPP`) can be committed against
+pub trait SrsPoints {
+    /// `[tau^index]_1`, for `index <= max_degree()`; lazily decoded setups may fail here
+    fn g1_point(&self, index: usize) -> Result<P1, KZGErrors>;
+}
+
+/// The parts of a setup that committing and verifying need, so [`KZG::commit`] accepts both an owned
+/// [`PP`] and a borrowed [`PPView`]
+pub trait Srs: SrsPoints {
+    /// `[tau]_2`
+    fn point_in_g2(&self) -> P2;
+    /// the largest degree that can be committed to
+    fn max_degree(&self) -> usize;
+    /// checked before committing, for setups whose parts can go out of sync
+    fn check_consistency(&self) -> Result<(), KZGErrors> {
+        Ok(())
+    }
+}
+
+impl SrsPoints for PP {
+    fn g1_point(&self, index: usize) -> Result<P1, KZGErrors> {
+        Ok(self.points_in_g1[index])
+    }
+}
+
+impl Srs for PP {
+    fn point_in_g2(&self) -> P2 {
+        PP::point_in_g2(self)
+    }
+
+    fn max_degree(&self) -> usize {
+        self.max_degree
+    }
+
+    fn check_consistency(&self) -> Result<(), KZGErrors> {
+        PP::check_consistency(self)
+    }
+}
+
+impl SrsPoints for PPView<'_> {
+    fn g1_point(&self, index: usize) -> Result<P1, KZGErrors> {
+        Ok(self.points_in_g1[index])
+    }
+}
+
+impl Srs for PPView<'_> {
+    fn point_in_g2(&self) -> P2 {
+        self.points_in_g2[1]
+    }
+
+    fn max_degree(&self) -> usize {
+        self.points_in_g1.len() - 1
+    }
+}
+
+impl<'a> PPView<'a> {
+    /// `[tau^i]_1` for `i = 0..=max_degree()`
+    pub fn g1_basis(&self) -> &'a [P1] {
+        self.points_in_g1
+    }
+}
+
+impl PP {
+    /// an owned copy of the setup restricted to polynomials of degree at most `max_degree`
+    /// (all the G2 powers are kept)
+    pub fn truncated(&self, max_degree: usize) -> Result<PP, KZGErrors> {
+        let view = self.as_slice(max_degree)?;
+        Ok(PP::from_parts(view.points_in_g1.to_vec(), view.points_in_g2.to_vec()))
+    }
+
+    /// a borrowed view of the first `max_degree + 1` powers, without copying any points;
+    /// commitments against it are identical to commitments against the full setup
+    pub fn as_slice(&self, max_degree: usize) -> Result<PPView<'_>, KZGErrors> {
+        self.check_consistency()?;
+        if max_degree > self.max_degree {
+            return Err(KZGErrors::DegreeExceedsSetup {
+                requested: max_degree,
+                available: self.max_degree,
+            });
+        }
+
+        Ok(PPView {
+            points_in_g1: &self.points_in_g1[..=max_degree],
+            points_in_g2: &self.points_in_g2,
+        })
+    }
+
+    /// true when both setups are the same, compared through [`PP::digest`]
+    pub fn same_setup(&self, other: &PP) -> bool {
+        self.digest() == other.digest()
+    }
+
+    /// true when this setup is `other` with fewer powers: its G1 points are the first G1 points of
+    /// `other` and its G2 points the first G2 points of `other`, so commitments and proofs made
+    /// under it also verify under `other`. Every setup is a prefix of itself.
+    pub fn is_prefix_of(&self, other: &PP) -> bool {
+        self.points_in_g1.len() <= other.points_in_g1.len()
+            && self.points_in_g2.len() <= other.points_in_g2.len()
+            && self
+                .points_in_g1
+                .iter()
+                .zip(&other.points_in_g1)
+                .all(|(a, b)| a.compress() == b.compress())
+            && self
+                .points_in_g2
+                .iter()
+                .zip(&other.points_in_g2)
+                .all(|(a, b)| a.compress() == b.compress())
+    }
+}
+
+
+// ===================================
+// FREE FUNCTIONS
+// ===================================
+/// `[1, tau, tau^2, ..., tau^degree]`, each power obtained from the previous one
+pub(crate) fn powers_of_tau(tau: Fr, degree: usize) -> Vec<Fr> {
+    let mut powers = Vec::with_capacity(degree + 1);
+    let mut current = Fr::from_u64(1);
+    for _ in 0..=degree {
+        powers.push(current);
+        current *= tau;
+    }
+    powers
+}
+
+/// multiplies the G1 generator by every power of tau, one after the other
+fn g1_powers_serial(powers_of_tau: &[Fr]) -> Vec<P1> {
+    // the window table of the generator in the first group (this is the cyclic group)
+    let g1 = fixed_base::g1_generator_table();
+
+    powers_of_tau.iter().map(|power| g1.mul(power)).collect()
+}
+
+/// the G1 part of the setup; the scalar multiplications dominate setup time
+#[cfg(not(feature = "parallel"))]
+fn g1_powers(powers_of_tau: &[Fr]) -> Vec<P1> {
+    g1_powers_serial(powers_of_tau)
+}
+
+/// the G1 part of the setup, with the scalar multiplications spread across the rayon pool in
+/// chunks; chunks are collected in order, so the output matches [`g1_powers_serial`]
+#[cfg(feature = "parallel")]
+fn g1_powers(powers_of_tau: &[Fr]) -> Vec<P1> {
+    use rayon::prelude::*;
+
+    const CHUNK_SIZE: usize = 64;
+
+    powers_of_tau
+        .par_chunks(CHUNK_SIZE)
+        .flat_map_iter(g1_powers_serial)
+        .collect()
+}
+
+/// draws a secret uniformly from [1, curve order) by rejection sampling; every draw, including the
+/// rejected ones, lives in the same wiped-on-drop buffer
+pub(crate) fn sample_secret<R: RngCore + CryptoRng>(rng: &mut R) -> SecretBytes {
+    let mut secret = SecretBytes::new([0u8; 32]);
+    rng.fill_bytes(&mut secret[..]);
+
+    let mut s = BigUint::from_bytes_be(&secret[..]);
+
+    // rejection sampling keeps tau uniform in [1, curve order)
+    let modulus :BigUint = curve_order();
+    while s >= modulus || s == BigUint::default() {
+        rng.fill_bytes(&mut secret[..]);
+        s = BigUint::from_bytes_be(&secret[..]);
+    }
+
+    secret
+}
+
+/// the polynomial's true degree (trailing zero coefficients don't count) must fit the setup
+pub(crate) fn check_degree(max_degree: usize, polynomial: &polynomial::Polynomial) -> Result<(), KZGErrors> {
+    match polynomial.degree() {
+        Some(degree) if degree > max_degree => Err(KZGErrors::PolynomialDegreeTooLarge { degree, max_degree }),
+        _ => Ok(()),
+    }
+}
+
+/// `sum f_i [tau^i]_1` over a basis supporting polynomials up to `max_degree`
+pub(crate) fn commit_point<S: SrsPoints>(
+    basis: &S,
+    max_degree: usize,
+    polynomial: &polynomial::Polynomial,
+) -> Result<P1, KZGErrors> {
+    check_degree(max_degree, polynomial)?;
+    if polynomial.is_zero() {
+        return Ok(P1::default());
+    }
+
+    // coefficients past the degree are zero, and the basis may not reach them
+    let length = polynomial.degree().map_or(0, |degree| degree + 1);
+    let coefficients = &polynomial.coefficients[..length];
+
+    let points = (0..length).map(|index| basis.g1_point(index)).collect::<Result<Vec<_>, _>>()?;
+    Ok(msm::linear_combination(coefficients, &points))
+}
+
+/// the value at `z` and the commitment to `(f(x) - f(z)) / (x - z)` as the proof
+pub(crate) fn open_polynomial<S: SrsPoints>(
+    basis: &S,
+    max_degree: usize,
+    polynomial: &polynomial::Polynomial,
+    z: Fr,
+) -> Result<Opening, KZGErrors> {
+    open_polynomial_with_quotient(basis, max_degree, polynomial, z).map(|(opening, _)| opening)
+}
+
+/// [`open_polynomial`], also returning the quotient the proof commits to
+fn open_polynomial_with_quotient<S: SrsPoints>(
+    basis: &S,
+    max_degree: usize,
+    polynomial: &polynomial::Polynomial,
+    z: Fr,
+) -> Result<(Opening, polynomial::Polynomial), KZGErrors> {
+    check_degree(max_degree, polynomial)?;
+    // the zero polynomial opens to zero everywhere, with the identity as the quotient commitment
+    if polynomial.is_zero() {
+        let opening = Opening {
+            value: Fr::default(),
+            proof: P1::default(),
+        };
+        return Ok((opening, polynomial::Polynomial::zero()));
+    }
+    let result = polynomial.evalaute(z);
+    let quotient_polynomial = opening_quotient(polynomial, z, result)?;
+
+    let opening = Opening {
+        value: result,
+        proof: commit_point(basis, max_degree, &quotient_polynomial)?,
+    };
+    Ok((opening, quotient_polynomial))
+}
+
+/// `(f(x) - y) / (x - z)`, which divides exactly only if `f(z) = y`.
+///
+/// The long division drops its remainder, so the result is checked before it is committed to:
+/// the remainder must be zero and the quotient of a degree `d >= 1` polynomial must have degree
+/// `d - 1` (a constant polynomial has the zero quotient). Otherwise the proof would be for some
+/// other polynomial and fail to verify, and [`KZGErrors::InexactDivision`] is returned instead.
+fn opening_quotient(polynomial: &polynomial::Polynomial, z: Fr, value: Fr) -> Result<polynomial::Polynomial, KZGErrors> {
+    let numerator = polynomial.sub_constant(value);
+    let divisor_coefficients = vec![-z, Fr::from_u64(1)];
+    let divisor = polynomial::Polynomial::from_coefficients(divisor_coefficients);
+    let (quotient, remainder) = compute_quotient(&numerator, &divisor)?;
+
+    let expected_degree = polynomial.degree().and_then(|degree| degree.checked_sub(1));
+    if remainder.iter().any(|coefficient| *coefficient != Fr::default()) || quotient.degree() != expected_degree {
+        return Err(KZGErrors::InexactDivision);
+    }
+    Ok(quotient)
+}
+
+/// This is a simple function for dividing a polynomial and returning the q, together with the
+/// coefficients of the remainder (one fewer than the divisor has, trailing zero coefficients of
+/// the divisor aside).
+///
+/// The quotient is only non-zero when the degree of the dividend is at least the degree of the
+/// divisor; below that it is the zero polynomial and the whole dividend is the remainder. An empty
+/// dividend, or a divisor that is empty or zero, is [`KZGErrors::EmptyPolynomial`].
+fn compute_quotient(
+    dividend: &polynomial::Polynomial,
+    divisor: &polynomial::Polynomial,
+) -> Result<(polynomial::Polynomial, Vec<Fr>), KZGErrors> {
+    if dividend.coefficients.is_empty() {
+        return Err(KZGErrors::EmptyPolynomial);
+    }
+    // the leading coefficient is divided by, so it must be the last non-zero one
+    let divisor_pos = divisor.degree().ok_or(KZGErrors::EmptyPolynomial)?;
+    let divisor = &divisor.coefficients[..=divisor_pos];
+
+    let mut dividend = dividend.coefficients.clone();
+    let mut coefficients = vec![Fr::default(); (dividend.len() + 1).saturating_sub(divisor.len())];
+
+    // position `difference` of the quotient cancels position `difference + divisor_pos` of what is
+    // left of the dividend, from the top down
+    for difference in (0..coefficients.len()).rev() {
+        let term_quotient = dividend[difference + divisor_pos] / divisor[divisor_pos];
+        coefficients[difference] = term_quotient;
+
+        for (i, x) in divisor.iter().enumerate() {
+            let y = *x * term_quotient;
+            let z = dividend[difference + i];
+            dividend[difference + i] = z - y;
+        }
+    }
+
+    dividend.resize(divisor_pos, Fr::default());
+    Ok((polynomial::Polynomial { coefficients }, dividend))
+}
+
+/// the pairing check behind every single-point verification: `e(C - [y]_1, [1]_2) == e(proof, [s - z]_2)`.
+/// Both differences are taken with add-or-double: `C` equals `-[y]_1` for a constant `c` claimed
+/// to open to `-c`, and `[s]_2` equals `-[z]_2` at `z = -s`
+fn check_opening(commitment: P1, z: &Fr, value: &Fr, proof: P1, g2_tau: P2) -> bool {
+    // Compute [f(s) - y]_1 for LHS
+    let y_p1 = fixed_base::g1_generator_table().mul(value);
+    let commitment_minus_y = group::g1_add(&commitment, &-y_p1);
+
+    // Compute [s - z]_2 for RHS
+    let z_p2 = fixed_base::g2_generator_table().mul(z);
+    let s_minus_z = group::g2_add(&g2_tau, &-z_p2);
+
+    verify_pairings(commitment_minus_y, P2::generator(), proof, s_minus_z)
+}
+
+impl<B: PairingBackend> Opening<B> {
+    /// this function checks the opening with the operations of the backend `B` alone, from the
+    /// commitment point and `[tau]_2`
+    pub fn verify_with_backend(&self, z: &B::Scalar, commitment_point: &B::G1, g2_tau: &B::G2) -> bool {
+        backend::verify_opening::<B>(commitment_point, z, &self.value, &self.proof, g2_tau)
+    }
+}
+
+impl Opening {
+    /// this function takes in an input and a commitment (a [`Commitment`] or an [`OwnedCommitment`])
+    /// and returns a boolean value, this boolean value is true if the proof is valid and false otherwise
+    pub fn verify<C: VerifiableCommitment>(&self, input: &Fr, commitment: &C) -> bool {
+        check_opening(commitment.point(), input, &self.value, self.proof, commitment.g2_tau())
+    }
+
+    /// this function verifies the opening from the raw verifier inputs alone: the commitment point
+    /// and the `[tau]_2` element of the setup. no polynomial or [`Commitment`] is needed, which is
+    /// what a verifier receiving bytes over the wire actually has. the commitment may be a
+    /// [`CommitmentPoint`] parsed with its checks as well as a `P1`
+    pub fn verify_against(&self, z: Fr, commitment_point: impl Into<P1>, g2_tau: P2) -> bool {
+        check_opening(commitment_point.into(), &z, &self.value, self.proof, g2_tau)
+    }
+
+    /// this function checks an opening from [`Commitment::open_delta`]: `self.value` is
+    /// `f_new(z) - f_old(z)` and the proof opens the commitment `C_new - C_old`
+    pub fn verify_delta(&self, z: Fr, new_commitment: P1, old_commitment: P1, g2_tau: P2) -> bool {
+        check_opening(group::g1_add(&new_commitment, &-old_commitment), &z, &self.value, self.proof, g2_tau)
+    }
+
+    /// [`Opening::verify_detailed`] against a commitment whose setup must be `expected` or a
+    /// truncation of it ([`PP::same_setup`], [`PP::is_prefix_of`]); any other setup is
+    /// [`KZGErrors::SetupMismatch`] rather than a failed check
+    pub fn verify_strict(&self, z: &Fr, commitment: &Commitment, expected: &PP) -> Result<bool, KZGErrors> {
+        let setup = commitment.public_parameter;
+        if !setup.same_setup(expected) && !setup.is_prefix_of(expected) {
+            return Err(KZGErrors::SetupMismatch);
+        }
+        self.verify_detailed(z, commitment)
+    }
+
+    /// same check as [`Opening::verify`], telling malformed inputs apart from a failed proof: an
+    /// inconsistent setup or a commitment, proof or `[tau]_2` outside the prime-order subgroup is
+    /// an error, and `Ok(false)` means the inputs are well-formed but the pairings don't match
+    pub fn verify_detailed<S: Srs>(&self, z: &Fr, commitment: &Commitment<S>) -> Result<bool, KZGErrors> {
+        commitment.public_parameter.check_consistency()?;
+        let g2_tau = commitment.public_parameter.point_in_g2();
+        for (input, valid) in [
+            ("commitment", group::g1_in_subgroup(&commitment.element)),
+            ("proof", group::g1_in_subgroup(&self.proof)),
+            ("[tau]_2", group::g2_in_subgroup(&g2_tau)),
+        ] {
+            if !valid {
+                return Err(KZGErrors::VerificationInputNotInSubgroup { input });
+            }
+        }
+
+        Ok(check_opening(commitment.element, z, &self.value, self.proof, g2_tau))
+    }
+
+    /// this function checks a non-membership proof from [`Commitment::open_not_equal`]: the opening
+    /// must be valid at `z` and the revealed value must differ from `y`
+    pub fn verify_not_equal<S: Srs>(&self, z: &Fr, y: &Fr, commitment: &Commitment<S>) -> bool {
+        self.value != *y && self.verify(z, commitment)
+    }
+}
+
+/// the binding point of a context opening, derived from everything the verifier checks
+fn context_challenge(context: &[u8], commitment: &P1, z: &Fr, value: &Fr) -> Fr {
+    let mut transcript = Transcript::new(b"kzg-context-opening");
+    transcript.append_bytes(context);
+    transcript.append_point(commitment);
+    transcript.append_scalar(z);
+    transcript.append_scalar(value);
+    transcript.challenge()
+}
+
+impl ContextOpening {
+    /// verifies both the opening at `z` and the binding opening at the point derived from
+    /// `context`; fails if the proof was produced for another context
+    pub fn verify<S: Srs>(&self, z: &Fr, context: &[u8], commitment: &Commitment<S>) -> bool {
+        let zeta = context_challenge(context, &commitment.element, z, &self.opening.value);
+
+        self.opening.verify(z, commitment) && self.binding.verify(&zeta, commitment)
+    }
+}
+
+
+// ===================================
+// CACHED VERIFIER
+// ===================================
+/// A verifier bound to one setup, for checking many openings against it.
+///
+/// [`Opening::verify`] computes `y * [1]_1` and `z * [1]_2` with full scalar multiplications.
+/// This struct takes them from the window tables of [`fixed_base::g1_generator_table`] and
+/// [`fixed_base::g2_generator_table`] instead, 64 additions each, leaving the pairing check as
+/// the main cost of each proof; the tables are built once per process, by the first verifier.
+/// `[tau]_2` is copied out of the setup, so the verifier can outlive it.
+#[derive(Clone)]
+pub struct Verifier {
+    g1_table: &'static fixed_base::WindowTable<P1>,
+    g2_table: &'static fixed_base::WindowTable<P2>,
+    g2_tau: P2,
+}
+
+impl Verifier {
+    /// keeps `[tau]_2` of `public_parameter`, building the generator tables if no verifier has yet
+    pub fn new<S: Srs>(public_parameter: &S) -> Verifier {
+        Verifier {
+            g1_table: fixed_base::g1_generator_table(),
+            g2_table: fixed_base::g2_generator_table(),
+            g2_tau: public_parameter.point_in_g2(),
+        }
+    }
+
+    /// this function checks an opening at `z` of the commitment `commitment_point`, with the same
+    /// result as [`Opening::verify`] against a commitment from the same setup
+    pub fn verify(&self, opening: &Opening, z: &Fr, commitment_point: &P1) -> bool {
+        // e(C - [y]_1, [1]_2) == e(proof, [tau - z]_2)
+        let commitment_minus_y = group::g1_add(commitment_point, &-self.g1_table.mul(&opening.value));
+        let tau_minus_z = group::g2_add(&self.g2_tau, &-self.g2_table.mul(z));
+        verify_pairings(commitment_minus_y, P2::generator(), opening.proof, tau_minus_z)
+    }
+}
+
+/// prints `[tau]_2`, leaving out the shared tables
+impl fmt::Debug for Verifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Verifier")
+            .field("g2_tau", &hex::encode(self.g2_tau.compress()))
+            .finish()
+    }
+}
+
+
+// ===================================
+// OPENING CACHE
+// ===================================
+/// A commitment that keeps every opening it computes, keyed by the big-endian bytes of the point,
+/// for provers asked for the same points again and again.
+///
+/// The commitment is only reachable through [`CachingCommitment::commitment`], so the polynomial
+/// can't be swapped under the cached openings.
+#[derive(Debug)]
+pub struct CachingCommitment<'a, S = PP> {
+    commitment: Commitment<'a, S>,
+    openings: HashMap<[u8; 32], Opening>,
+    computed: usize,
+}
+
+impl<'a, S: Srs> CachingCommitment<'a, S> {
+    /// wraps `commitment` with an empty cache
+    pub fn new(commitment: Commitment<'a, S>) -> Self {
+        CachingCommitment {
+            commitment,
+            openings: HashMap::new(),
+            computed: 0,
+        }
+    }
+
+    /// the wrapped commitment
+    pub fn commitment(&self) -> &Commitment<'a, S> {
+        &self.commitment
+    }
+
+    /// this function returns the opening at `z` like [`Commitment::open_at`], computing it on the
+    /// first request for `z` and returning a copy of the stored one afterwards
+    pub fn open_at_cached(&mut self, z: Fr) -> Result<Opening, KZGErrors> {
+        let key = z.to_bytes_be();
+        if let Some(opening) = self.openings.get(&key) {
+            return Ok(opening.clone());
+        }
+
+        let opening = self.commitment.open_at(z)?;
+        self.computed += 1;
+        self.openings.insert(key, opening.clone());
+        Ok(opening)
+    }
+
+    /// the number of distinct points with a stored opening
+    pub fn cached_openings(&self) -> usize {
+        self.openings.len()
+    }
+
+    /// how many openings have been computed rather than served from the cache
+    pub fn computed_openings(&self) -> usize {
+        self.computed
+    }
+
+    /// drops every stored opening
+    pub fn clear(&mut self) {
+        self.openings.clear();
+    }
+}
+
+
+// ===================================
+// SHARED SETUPS
+// ===================================
+/// A commitment owning its polynomial and a shared handle to its setup, see [`KZG::commit_arc`].
+///
+/// Unlike [`Commitment`] it borrows nothing, so it can be moved into spawned threads or tasks
+/// while one [`PP`] is shared between them.
+#[derive(Clone)]
+pub struct OwnedCommitment {
+    /// The commitment point
+    pub element: P1,
+    /// The Polynomial committed to
+    pub polynomial: polynomial::Polynomial,
+    /// Public parameter used during the commitment process
+    pub public_parameter: Arc<PP>,
+}
+
+impl KZG {
+    /// same as [`KZG::commit`], but the commitment keeps `polynomial` and the `Arc` to the setup
+    pub fn commit_arc(public_parameter: Arc<PP>, polynomial: polynomial::Polynomial) -> Result<OwnedCommitment, KZGErrors> {
+        let element = KZG::commit(public_parameter.as_ref(), &polynomial)?.element;
+        Ok(OwnedCommitment {
+            element,
+            polynomial,
+            public_parameter,
+        })
+    }
+}
+
+impl OwnedCommitment {
+    /// a borrowed [`Commitment`] to the same polynomial, for the APIs that take one
+    pub fn as_commitment(&self) -> Commitment<'_> {
+        Commitment {
+            element: self.element,
+            polynomial: &self.polynomial,
+            public_parameter: &self.public_parameter,
+        }
+    }
+
+    /// same as [`Commitment::open_at`]
+    pub fn open_at(&self, point: Fr) -> Result<Opening, KZGErrors> {
+        self.as_commitment().open_at(point)
+    }
+}
+
+impl Commitment<'_> {
+    /// an [`OwnedCommitment`] with copies of the polynomial and the setup, to keep after the
+    /// borrowed ones are gone. Copying the setup costs as much memory as the setup itself; to share
+    /// one setup between many commitments, commit with [`KZG::commit_arc`] instead.
+    pub fn to_owned(&self) -> OwnedCommitment {
+        OwnedCommitment {
+            element: self.element,
+            polynomial: self.polynomial.clone(),
+            public_parameter: Arc::new(self.public_parameter.clone()),
+        }
+    }
+}
+
+/// What [`Opening::verify`] checks an opening against: the commitment point and `[tau]_2`
+pub trait VerifiableCommitment {
+    /// the commitment point
+    fn point(&self) -> P1;
+    /// `[tau]_2` of the setup the commitment was made with
+    fn g2_tau(&self) -> P2;
+}
+
+impl<S: Srs> VerifiableCommitment for Commitment<'_, S> {
+    fn point(&self) -> P1 {
+        self.element
+    }
+
+    fn g2_tau(&self) -> P2 {
+        self.public_parameter.point_in_g2()
+    }
+}
+
+impl VerifiableCommitment for OwnedCommitment {
+    fn point(&self) -> P1 {
+        self.element
+    }
+
+    fn g2_tau(&self) -> P2 {
+        self.public_parameter.point_in_g2()
+    }
+}
+
+/// prints like [`Commitment`]
+impl fmt::Debug for OwnedCommitment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_commitment().fmt(f)
+    }
+}
+
+
+// ===================================
+// HOMOMORPHIC OPERATIONS
+// ===================================
+/// A bare commitment point, for combining commitments: `com(f) + com(g) == com(f + g)`,
+/// `com(f) - com(g) == com(f - g)` and `c * com(f) == com(c * f)` under the same setup.
+///
+/// Nothing records the setup here, so combining points from different setups gives a point that
+/// commits to nothing; [`OwnedCommitment::checked_add`] refuses that.
+#[derive(Clone, Copy)]
+pub struct CommitmentPoint(pub P1);
+
+impl From<P1> for CommitmentPoint {
+    fn from(point: P1) -> Self {
+        CommitmentPoint(point)
+    }
+}
+
+impl<S> From<&Commitment<'_, S>> for CommitmentPoint {
+    fn from(commitment: &Commitment<'_, S>) -> Self {
+        CommitmentPoint(commitment.element)
+    }
+}
+
+impl From<&OwnedCommitment> for CommitmentPoint {
+    fn from(commitment: &OwnedCommitment) -> Self {
+        CommitmentPoint(commitment.element)
+    }
+}
+
+/// also adds a point to itself, which the point addition of `oblast_demo` doesn't
+impl Add for CommitmentPoint {
+    type Output = CommitmentPoint;
+
+    fn add(self, other: CommitmentPoint) -> CommitmentPoint {
+        CommitmentPoint(group::g1_add(&self.0, &other.0))
+    }
+}
+
+impl Sub for CommitmentPoint {
+    type Output = CommitmentPoint;
+
+    fn sub(self, other: CommitmentPoint) -> CommitmentPoint {
+        self + -other
+    }
+}
+
+impl Neg for CommitmentPoint {
+    type Output = CommitmentPoint;
+
+    fn neg(self) -> CommitmentPoint {
+        CommitmentPoint(-self.0)
+    }
+}
+
+/// `c * com(f) == com(c * f)`
+impl Mul<Fr> for CommitmentPoint {
+    type Output = CommitmentPoint;
+
+    fn mul(self, c: Fr) -> CommitmentPoint {
+        CommitmentPoint(c * self.0)
+    }
+}
+
+impl Mul<CommitmentPoint> for Fr {
+    type Output = CommitmentPoint;
+
+    fn mul(self, point: CommitmentPoint) -> CommitmentPoint {
+        point * self
+    }
+}
+
+/// the point of `com(c * f)`; the borrowed commitment has no polynomial to scale in place
+impl<S> Mul<&Commitment<'_, S>> for Fr {
+    type Output = CommitmentPoint;
+
+    fn mul(self, commitment: &Commitment<'_, S>) -> CommitmentPoint {
+        CommitmentPoint::from(commitment) * self
+    }
+}
+
+/// `com(c * f)` under the same setup, so it still combines with the other commitments of that
+/// setup through [`OwnedCommitment::checked_add`]
+impl Mul<Fr> for &OwnedCommitment {
+    type Output = OwnedCommitment;
+
+    fn mul(self, c: Fr) -> OwnedCommitment {
+        OwnedCommitment {
+            element: c * self.element,
+            polynomial: self.polynomial.scale(c),
+            public_parameter: Arc::clone(&self.public_parameter),
+        }
+    }
+}
+
+impl Mul<&OwnedCommitment> for Fr {
+    type Output = OwnedCommitment;
+
+    fn mul(self, commitment: &OwnedCommitment) -> OwnedCommitment {
+        commitment * self
+    }
+}
+
+/// compares the compressed encodings, like [`PP`]
+impl PartialEq for CommitmentPoint {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.compress() == other.0.compress()
+    }
+}
+
+impl Eq for CommitmentPoint {}
+
+/// prints the compressed point as hex
+impl fmt::Debug for CommitmentPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CommitmentPoint").field(&HexBytes(&self.0.compress())).finish()
+    }
+}
+
+/// `sum weights[i] * points[i]`, by multi-scalar multiplication for longer inputs; the random
+/// linear combination every batch check folds its commitments and proofs with. The slices must
+/// have the same length ([`KZGErrors::MismatchedWeights`]); no points give the identity.
+pub fn linear_combination(points: &[P1], weights: &[Fr]) -> Result<P1, KZGErrors> {
+    if points.len() != weights.len() {
+        return Err(KZGErrors::MismatchedWeights {
+            points: points.len(),
+            weights: weights.len(),
+        });
+    }
+    Ok(msm::linear_combination(weights, points))
+}
+
+impl CommitmentPoint {
+    /// `sum scalars[i] * points[i]` with one multi-scalar multiplication, the commitment to the
+    /// same combination of the polynomials; see [`linear_combination`]
+    pub fn linear_combination(points: &[CommitmentPoint], scalars: &[Fr]) -> Result<CommitmentPoint, KZGErrors> {
+        let points: Vec<P1> = points.iter().map(|point| point.0).collect();
+        linear_combination(&points, scalars).map(CommitmentPoint)
+    }
+
+    /// the commitment after coefficient `index` of the polynomial changed from `old` to `new`,
+    /// `C + (new - old) * [tau^index]_1`, without recommitting. Fails with
+    /// [`KZGErrors::PolynomialDegreeTooLarge`] for an index beyond the setup's maximum degree.
+    pub fn update_coefficient(&self, pp: &PP, index: usize, old: Fr, new: Fr) -> Result<CommitmentPoint, KZGErrors> {
+        let power = pp.points_in_g1.get(index).ok_or(KZGErrors::PolynomialDegreeTooLarge {
+            degree: index,
+            max_degree: pp.max_degree(),
+        })?;
+        Ok(*self + CommitmentPoint((new - old) * *power))
+    }
+
+    /// [`CommitmentPoint::update_coefficient`] for several `(index, old, new)` changes, with one
+    /// multi-scalar multiplication over the powers involved. Changes to the same index add up, so
+    /// they must chain (the `old` of each is the `new` of the one before). An index beyond the
+    /// setup is [`KZGErrors::BatchPolynomialDegreeTooLarge`] with the position of its update.
+    pub fn update_coefficients(&self, pp: &PP, updates: &[(usize, Fr, Fr)]) -> Result<CommitmentPoint, KZGErrors> {
+        let mut powers = Vec::with_capacity(updates.len());
+        let mut differences = Vec::with_capacity(updates.len());
+        for (position, (index, old, new)) in updates.iter().enumerate() {
+            let power = pp.points_in_g1.get(*index).ok_or(KZGErrors::BatchPolynomialDegreeTooLarge {
+                index: position,
+                degree: *index,
+                max_degree: pp.max_degree(),
+            })?;
+            powers.push(*power);
+            differences.push(*new - *old);
+        }
+        Ok(*self + CommitmentPoint(linear_combination(&powers, &differences)?))
+    }
+}
+
+impl OwnedCommitment {
+    /// the commitment to the sum of both polynomials, computed from the two points. Fails with
+    /// [`KZGErrors::SetupMismatch`] unless both were made under the same setup (the same `Arc`, or
+    /// setups with equal digests).
+    pub fn checked_add(&self, other: &OwnedCommitment) -> Result<OwnedCommitment, KZGErrors> {
+        self.combine(other, CommitmentPoint::add, |f, g| f + g)
+    }
+
+    /// same as [`OwnedCommitment::checked_add`] for the difference of the polynomials
+    pub fn checked_sub(&self, other: &OwnedCommitment) -> Result<OwnedCommitment, KZGErrors> {
+        self.combine(other, CommitmentPoint::sub, |f, g| f - g)
+    }
+
+    /// [`CommitmentPoint::linear_combination`] of commitments made under `pp`, keeping the
+    /// combined polynomial so it can be opened. A commitment under another setup is
+    /// [`KZGErrors::SetupMismatch`]; no commitments give the commitment to zero.
+    pub fn linear_combination(
+        pp: &Arc<PP>,
+        commitments: &[OwnedCommitment],
+        scalars: &[Fr],
+    ) -> Result<OwnedCommitment, KZGErrors> {
+        if commitments.iter().any(|commitment| !commitment.is_under(pp)) {
+            return Err(KZGErrors::SetupMismatch);
+        }
+        let points: Vec<P1> = commitments.iter().map(|commitment| commitment.element).collect();
+        let element = linear_combination(&points, scalars)?;
+
+        let polynomial = commitments
+            .iter()
+            .zip(scalars)
+            .fold(polynomial::Polynomial::zero(), |sum, (commitment, scalar)| {
+                &sum + &commitment.polynomial.scale(*scalar)
+            });
+        Ok(OwnedCommitment {
+            element,
+            polynomial,
+            public_parameter: Arc::clone(pp),
+        })
+    }
+
+    fn is_under(&self, pp: &Arc<PP>) -> bool {
+        Arc::ptr_eq(&self.public_parameter, pp) || self.public_parameter.digest() == pp.digest()
+    }
+
+    fn combine(
+        &self,
+        other: &OwnedCommitment,
+        points: fn(CommitmentPoint, CommitmentPoint) -> CommitmentPoint,
+        polynomials: fn(&polynomial::Polynomial, &polynomial::Polynomial) -> polynomial::Polynomial,
+    ) -> Result<OwnedCommitment, KZGErrors> {
+        if !self.is_under(&other.public_parameter) {
+            return Err(KZGErrors::SetupMismatch);
+        }
+
+        Ok(OwnedCommitment {
+            element: points(self.into(), other.into()).0,
+            polynomial: polynomials(&self.polynomial, &other.polynomial),
+            public_parameter: Arc::clone(&self.public_parameter),
+        })
+    }
+}
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+// setups, polynomials and proofs can be shared across threads; this fails to compile if a
+// change to the point types or the setup ever takes that away
+const _: fn() = || {
+    assert_send_sync::<PP>();
+    assert_send_sync::<KZG>();
+    assert_send_sync::<polynomial::Polynomial>();
+    assert_send_sync::<Commitment<'static>>();
+    assert_send_sync::<OwnedCommitment>();
+    assert_send_sync::<Opening>();
+};
+
+
+// ===================================
+// PROOF SERIALIZATION
+// ===================================
+/// a serialized [`Opening`]: the 32-byte big-endian value, then the 48-byte compressed proof
+pub const OPENING_SIZE: usize = 32 + G1_COMPRESSED_SIZE;
+
+impl<'a, S: Srs> Commitment<'a, S> {
+    /// the compressed commitment point; the identity (the commitment to the zero polynomial) is
+    /// `c0` followed by zeros
+    pub fn to_bytes(&self) -> [u8; G1_COMPRESSED_SIZE] {
+        self.element.compress().try_into().unwrap()
+    }
+}
+
+/// parses a commitment point produced by [`Commitment::to_bytes`], accepting the identity
+pub fn commitment_from_bytes(bytes: &[u8; G1_COMPRESSED_SIZE]) -> Result<P1, KZGErrors> {
+    group::decompress_g1(bytes).map_err(|_| KZGErrors::PointDecodeError)
+}
+
+/// a G1 point received over the wire, with the reason it was rejected: a coordinate not below the
+/// field modulus, no point on the curve (including a malformed identity), or outside the subgroup.
+/// The canonical identity `c0 00..00` is accepted; it commits to the zero polynomial and is the
+/// proof of a constant one.
+fn g1_from_wire(bytes: &[u8]) -> Result<P1, KZGErrors> {
+    let bytes: &[u8; G1_COMPRESSED_SIZE] = match bytes.len() {
+        len if len < G1_COMPRESSED_SIZE => return Err(KZGErrors::TruncatedInput),
+        len if len > G1_COMPRESSED_SIZE => return Err(KZGErrors::TrailingBytes),
+        _ => bytes.try_into().unwrap(),
+    };
+    group::decompress_g1(bytes).map_err(|error| point_error(error, 0))
+}
+
+impl CommitmentPoint {
+    /// the 48-byte compressed point
+    pub fn to_bytes(&self) -> [u8; G1_COMPRESSED_SIZE] {
+        self.0.compress().try_into().unwrap()
+    }
+
+    /// parses a compressed point with the full checks, reported for index 0:
+    /// [`KZGErrors::NonCanonicalPoint`], [`KZGErrors::InvalidPoint`] or
+    /// [`KZGErrors::PointNotInSubgroup`]
+    pub fn from_bytes(bytes: &[u8; G1_COMPRESSED_SIZE]) -> Result<CommitmentPoint, KZGErrors> {
+        g1_from_wire(bytes).map(CommitmentPoint)
+    }
+
+    /// same as [`CommitmentPoint::from_bytes`] for a slice, which must be exactly 48 bytes
+    /// ([`KZGErrors::TruncatedInput`] or [`KZGErrors::TrailingBytes`] otherwise)
+    pub fn from_slice(bytes: &[u8]) -> Result<CommitmentPoint, KZGErrors> {
+        g1_from_wire(bytes).map(CommitmentPoint)
+    }
+}
+
+impl From<CommitmentPoint> for P1 {
+    fn from(point: CommitmentPoint) -> P1 {
+        point.0
+    }
+}
+
+/// The proof point of an opening, parsed with the same checks as a [`CommitmentPoint`]
+#[derive(Clone, Copy)]
+pub struct ProofPoint(pub P1);
+
+impl ProofPoint {
+    /// the 48-byte compressed point
+    pub fn to_bytes(&self) -> [u8; G1_COMPRESSED_SIZE] {
+        self.0.compress().try_into().unwrap()
+    }
+
+    /// same as [`CommitmentPoint::from_bytes`]
+    pub fn from_bytes(bytes: &[u8; G1_COMPRESSED_SIZE]) -> Result<ProofPoint, KZGErrors> {
+        g1_from_wire(bytes).map(ProofPoint)
+    }
+
+    /// same as [`CommitmentPoint::from_slice`]
+    pub fn from_slice(bytes: &[u8]) -> Result<ProofPoint, KZGErrors> {
+        g1_from_wire(bytes).map(ProofPoint)
+    }
+}
+
+impl From<ProofPoint> for P1 {
+    fn from(point: ProofPoint) -> P1 {
+        point.0
+    }
+}
+
+/// compares the compressed encodings, like [`PP`]
+impl PartialEq for ProofPoint {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_bytes() == other.to_bytes()
+    }
+}
+
+impl Eq for ProofPoint {}
+
+/// prints the compressed point as hex
+impl fmt::Debug for ProofPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ProofPoint").field(&HexBytes(&self.to_bytes())).finish()
+    }
+}
+
+impl Opening {
+    /// this function builds an opening from its value and a proof point parsed with
+    /// [`ProofPoint::from_bytes`], e.g. when value and proof arrive separately
+    pub fn new(value: Fr, proof: ProofPoint) -> Opening {
+        Opening { value, proof: proof.into() }
+    }
+
+    /// this function serializes the opening as the value followed by the compressed proof
+    pub fn to_bytes(&self) -> [u8; OPENING_SIZE] {
+        let mut bytes = [0u8; OPENING_SIZE];
+        bytes[..32].copy_from_slice(&self.value.to_bytes_be());
+        bytes[32..].copy_from_slice(&self.proof.compress());
+        bytes
+    }
+
+    /// this function parses the output of [`Opening::to_bytes`]; the value must be canonical and
+    /// the proof a point of the subgroup (the identity included)
+    pub fn from_bytes(bytes: &[u8; OPENING_SIZE]) -> Result<Opening, KZGErrors> {
+        let value = Fr::from_bytes_checked(bytes[..32].try_into().unwrap())?;
+        let proof = commitment_from_bytes(bytes[32..].try_into().unwrap())?;
+        Ok(Opening { value, proof })
+    }
+}
+
+
+// ===================================
+// HEX CONVENIENCE LAYER
+// ===================================
+/// decodes exactly `N` bytes of hex (an optional `0x` prefix is allowed)
+fn decode_hex_array<const N: usize>(input: &str) -> Result<[u8; N], KZGErrors> {
+    let input = input.strip_prefix("0x").unwrap_or(input);
+    let bytes = hex::decode(input).map_err(|_| KZGErrors::PointDecodeError)?;
+    bytes.try_into().map_err(|_| KZGErrors::PointDecodeError)
+}
+
+fn decode_hex_scalar(input: &str) -> Result<Fr, KZGErrors> {
+    Fr::from_bytes_checked(&decode_hex_array(input)?).map_err(|_| KZGErrors::PointDecodeError)
+}
+
+fn decode_hex_g1(input: &str) -> Result<P1, KZGErrors> {
+    group::decompress_g1(&decode_hex_array(input)?).map_err(|_| KZGErrors::PointDecodeError)
+}
+
+impl<'a, S: Srs> Commitment<'a, S> {
+    /// same as [`Commitment::open_at`] for a point given as 64 hex characters (32 big-endian bytes)
+    pub fn open_at_hex(&self, point_hex: &str) -> Result<Opening, KZGErrors> {
+        self.open_at(decode_hex_scalar(point_hex)?)
+    }
+}
+
+/// verifies an opening where everything but the setup arrives hex-encoded: the point and value as
+/// 32 big-endian bytes, the commitment and proof as 48-byte compressed G1 points.
+/// `Ok(false)` means the inputs decoded but the proof is invalid.
+pub fn verify_at_hex(
+    public_parameter: &PP,
+    point_hex: &str,
+    value_hex: &str,
+    commitment_hex: &str,
+    proof_hex: &str,
+) -> Result<bool, KZGErrors> {
+    let point = decode_hex_scalar(point_hex)?;
+    let value = decode_hex_scalar(value_hex)?;
+    let commitment = decode_hex_g1(commitment_hex)?;
+    let proof = decode_hex_g1(proof_hex)?;
+
+    Ok(check_opening(commitment, &point, &value, proof, public_parameter.point_in_g2()))
+}
+
+
+// ===================================
+// BATCH VERIFICATION
+// ===================================
+/// this function verifies `k` openings of `k` commitments at the same point `z` with a single
+/// pairing check, instead of `k` separate ones.
+///
+/// With a challenge `r` derived from the transcript of all commitments, values and proofs, it
+/// checks `e(sum r^i (C_i - [y_i]_1), [1]_2) == e(sum r^i proof_i, [tau - z]_2)`. The commitments
+/// must come from the same setup, with the same `[tau]_2` ([`KZGErrors::BatchSetupMismatch`]
+/// otherwise); `values[i]` is the claimed evaluation for `commitments[i]`.
+///
+/// slices of different lengths are an error; an empty batch verifies nothing and gives `Ok(false)`
+pub fn verify_same_point_batch<S: Srs>(
+    openings: &[Opening],
+    values: &[Fr],
+    commitments: &[Commitment<S>],
+    z: Fr,
+) -> Result<bool, KZGErrors> {
+    check_batch_lengths(openings.len(), values.len(), commitments.len())?;
+    if openings.is_empty() {
+        return Ok(false);
+    }
+    let g2_tau = commitments[0].public_parameter.point_in_g2();
+    let g2_tau_bytes = g2_tau.compress();
+    if let Some(index) = commitments
+        .iter()
+        .position(|commitment| commitment.public_parameter.point_in_g2().compress() != g2_tau_bytes)
+    {
+        return Err(KZGErrors::BatchSetupMismatch { index });
+    }
+
+    let mut transcript = Transcript::new(b"kzg-same-point-batch");
+    transcript.append_scalar(&z);
+    for ((opening, value), commitment) in openings.iter().zip(values).zip(commitments) {
+        transcript.append_point(&commitment.element);
+        transcript.append_scalar(value);
+        transcript.append_point(&opening.proof);
+    }
+    let r = transcript.challenge();
+
+    let mut powers = Vec::with_capacity(openings.len());
+    let mut combined_value = Fr::default();
+    let mut power = Fr::from_u64(1);
+    for value in values {
+        powers.push(power);
+        combined_value += power * *value;
+        power *= r;
+    }
+    let commitment_points: Vec<P1> = commitments.iter().map(|commitment| commitment.element).collect();
+    let proofs: Vec<P1> = openings.iter().map(|opening| opening.proof).collect();
+    let combined_commitment = linear_combination(&commitment_points, &powers)?;
+    let combined_proof = linear_combination(&proofs, &powers)?;
+
+    // [sum r^i (C_i - y_i)]_1
+    let lhs = group::g1_add(&combined_commitment, &-(combined_value * P1::generator()));
+
+    // [s - z]_2
+    let s_minus_z = group::g2_add(&g2_tau, &-(z * P2::generator()));
+
+    Ok(verify_pairings(lhs, P2::generator(), combined_proof, s_minus_z))
+}
+
+/// batch inputs are parallel slices, so they must all have the same length
+fn check_batch_lengths(openings: usize, values: usize, commitments: usize) -> Result<(), KZGErrors> {
+    if openings != values || openings != commitments {
+        return Err(KZGErrors::MismatchedBatchLengths {
+            openings,
+            values,
+            commitments,
+        });
+    }
+    Ok(())
+}
+
+// ===================================
+// FOLDED OPENINGS
+// ===================================
+/// the transcript both sides derive the folding challenges from; it holds no proof, since the
+/// verifier of a [`FoldedProof`] never sees the individual ones
+fn folding_transcript<S: Srs>(points: &[Fr], values: &[Fr], commitments: &[Commitment<S>]) -> Transcript {
+    let mut transcript = Transcript::new(b"kzg-folded-openings");
+    for ((z, value), commitment) in points.iter().zip(values).zip(commitments) {
+        transcript.append_point(&commitment.element);
+        transcript.append_scalar(z);
+        transcript.append_scalar(value);
+    }
+    transcript
+}
+
+fn scaled(polynomial: &polynomial::Polynomial, factor: Fr) -> polynomial::Polynomial {
+    polynomial::Polynomial::from_coefficients(polynomial.coefficients.iter().map(|c| *c * factor).collect())
+}
+
+/// this function folds the openings of `commitments[i]` at `points[i]` into one [`FoldedProof`]:
+/// two G1 points and the values, instead of a 48-byte proof per opening. The commitments must come
+/// from the same setup.
+///
+/// With `gamma` from the transcript of the commitments, points and values, the proofs fold into
+/// `W = sum gamma^i proof_i = [h(tau)]_1` for `h = sum gamma^i (f_i - y_i) / (X - z_i)`. A second
+/// challenge `zeta` bound to `W` then checks that identity at one point:
+/// `L(X) = sum gamma^i (f_i - y_i) / (zeta - z_i) - h(X)` vanishes at `zeta`, and
+/// `W' = [L(tau) / (tau - zeta)]_1` proves it. A wrong proof or value among the openings makes
+/// `W` or `W'` wrong.
+pub fn fold_proofs<S: Srs>(
+    openings: &[Opening],
+    points: &[Fr],
+    commitments: &[Commitment<S>],
+) -> Result<FoldedProof, KZGErrors> {
+    check_batch_lengths(openings.len(), points.len(), commitments.len())?;
+    let values: Vec<Fr> = openings.iter().map(|opening| opening.value).collect();
+    let mut transcript = folding_transcript(points, &values, commitments);
+    let gamma = transcript.challenge();
+
+    let mut powers = Vec::with_capacity(openings.len());
+    let mut h = polynomial::Polynomial::zero();
+    let mut power = Fr::from_u64(1);
+    for ((opening, z), commitment) in openings.iter().zip(points).zip(commitments) {
+        powers.push(power);
+        let linear = polynomial::Polynomial::from_coefficients(vec![-*z, Fr::from_u64(1)]);
+        let (quotient, _) = commitment.polynomial.sub_constant(opening.value).div_rem(&linear);
+        h = &h + &scaled(&quotient, power);
+        power *= gamma;
+    }
+    let proofs: Vec<P1> = openings.iter().map(|opening| opening.proof).collect();
+    let proof = msm::linear_combination(&powers, &proofs);
+
+    transcript.append_point(&proof);
+    let zeta = transcript.challenge();
+
+    let mut l = scaled(&h, -Fr::from_u64(1));
+    let mut power = Fr::from_u64(1);
+    for ((value, z), commitment) in values.iter().zip(points).zip(commitments) {
+        let numerator = commitment.polynomial.sub_constant(*value);
+        l = &l + &scaled(&numerator, power / (zeta - *z));
+        power *= gamma;
+    }
+    let linear = polynomial::Polynomial::from_coefficients(vec![-zeta, Fr::from_u64(1)]);
+    let (l_quotient, _) = l.div_rem(&linear);
+
+    let quotient = match commitments.first() {
+        Some(commitment) => {
+            let setup = commitment.public_parameter;
+            commit_point(setup, setup.max_degree(), &l_quotient)?
+        }
+        None => P1::default(),
+    };
+
+    Ok(FoldedProof {
+        proof,
+        quotient,
+        values,
+        challenge: gamma,
+    })
+}
+
+impl FoldedProof {
+    /// this function checks the folded openings of `commitments[i]` at `points[i]` with one
+    /// pairing check: `e([L(tau)]_1 + zeta W', [1]_2) == e(W', [tau]_2)`, where
+    /// `[L(tau)]_1 = sum gamma^i / (zeta - z_i) (C_i - [y_i]_1) - W`.
+    ///
+    /// slices of different lengths are an error; an empty batch verifies nothing and gives `Ok(false)`
+    pub fn verify<S: Srs>(&self, points: &[Fr], commitments: &[Commitment<S>]) -> Result<bool, KZGErrors> {
+        check_batch_lengths(self.values.len(), points.len(), commitments.len())?;
+        if commitments.is_empty() {
+            return Ok(false);
+        }
+
+        let mut transcript = folding_transcript(points, &self.values, commitments);
+        if transcript.challenge() != self.challenge {
+            return Ok(false);
+        }
+        transcript.append_point(&self.proof);
+        let zeta = transcript.challenge();
+        // only happens with negligible probability, but would divide by zero
+        if points.contains(&zeta) {
+            return Ok(false);
+        }
+
+        let mut weights = Vec::with_capacity(commitments.len());
+        let mut combined_value = Fr::default();
+        let mut power = Fr::from_u64(1);
+        for (value, z) in self.values.iter().zip(points) {
+            let weight = power / (zeta - *z);
+            weights.push(weight);
+            combined_value += weight * *value;
+            power *= self.challenge;
+        }
+        let commitment_points: Vec<P1> = commitments.iter().map(|commitment| commitment.element).collect();
+        let combined_commitment = linear_combination(&commitment_points, &weights)?;
+
+        let commitment_minus_y = group::g1_add(&combined_commitment, &-(combined_value * P1::generator()));
+        let l_at_tau = group::g1_add(&commitment_minus_y, &-self.proof);
+        let lhs = group::g1_add(&l_at_tau, &(zeta * self.quotient));
+        let g2_tau = commitments[0].public_parameter.point_in_g2();
+
+        Ok(verify_pairings(lhs, P2::generator(), self.quotient, g2_tau))
+    }
+}
+
+
+// ===================================
+// EQUALITY PROOFS
+// ===================================
+/// the challenge point of an equality proof, bound to both commitments and both setups
+fn equality_challenge<S1: Srs, S2: Srs>(commitment_1: &P1, pp_1: &S1, commitment_2: &P1, pp_2: &S2) -> Fr {
+    let mut transcript = Transcript::new(b"kzg-equality");
+    transcript.append_point(commitment_1);
+    transcript.append_bytes(&pp_1.point_in_g2().compress());
+    transcript.append_point(commitment_2);
+    transcript.append_bytes(&pp_2.point_in_g2().compress());
+    transcript.challenge()
+}
+
+/// proves that the commitments to `polynomial` under `pp_1` and under `pp_2` hide the same
+/// polynomial, without revealing it beyond one evaluation.
+///
+/// Both commitments are opened at a Fiat–Shamir point `z = H(C_1, C_2, ...)`. Two different
+/// polynomials of degree `d` agree on at most `d` points and `z` is only fixed once both
+/// commitments are, so matching openings at `z` show equality except with probability `d / r`.
+pub fn prove_equal<S1: Srs, S2: Srs>(
+    polynomial: &polynomial::Polynomial,
+    pp_1: &S1,
+    pp_2: &S2,
+) -> Result<EqualityProof, KZGErrors> {
+    let commitment_1 = KZG::commit(pp_1, polynomial)?;
+    let commitment_2 = KZG::commit(pp_2, polynomial)?;
+    let z = equality_challenge(&commitment_1.element, pp_1, &commitment_2.element, pp_2);
+
+    let opening_1 = commitment_1.open_at(z)?;
+    let opening_2 = commitment_2.open_at(z)?;
+
+    Ok(EqualityProof {
+        value: opening_1.value,
+        proof_1: opening_1.proof,
+        proof_2: opening_2.proof,
+    })
+}
+
+/// checks an [`EqualityProof`]: both commitments must open to the same value at the challenge point
+pub fn verify_equal<S1: Srs, S2: Srs>(
+    commitment_1: &Commitment<S1>,
+    commitment_2: &Commitment<S2>,
+    proof: &EqualityProof,
+) -> bool {
+    let pp_1 = commitment_1.public_parameter;
+    let pp_2 = commitment_2.public_parameter;
+    let z = equality_challenge(&commitment_1.element, pp_1, &commitment_2.element, pp_2);
+
+    check_opening(commitment_1.element, &z, &proof.value, proof.proof_1, pp_1.point_in_g2())
+        && check_opening(commitment_2.element, &z, &proof.value, proof.proof_2, pp_2.point_in_g2())
+}
+
+
+// ===================================
+// DEGREE BOUND PROOFS
+// ===================================
+/// the shift `D - bound` for a setup of maximum degree `D`, which must have `[tau^shift]_2`
+fn degree_bound_shift(pp: &PP, bound: usize) -> Result<usize, KZGErrors> {
+    if bound > pp.max_degree() {
+        return Err(KZGErrors::DegreeExceedsSetup {
+            requested: bound,
+            available: pp.max_degree(),
+        });
+    }
+    let shift = pp.max_degree() - bound;
+    pp.g2_power(shift)?;
+    Ok(shift)
+}
+
+/// proves that `polynomial` has degree at most `bound` without opening it, by committing to the
+/// shifted polynomial `x^(D - bound) * f(x)` where `D` is the setup's maximum degree.
+///
+/// The shifted polynomial has degree `D - bound + deg(f)`, so it can only be committed to with the
+/// setup's powers when `deg(f) <= bound`. The setup needs `[tau^(D - bound)]_2` for the check (see
+/// [`KZG::setup_with_g2_powers`]).
+pub fn prove_degree_bound(
+    pp: &PP,
+    polynomial: &polynomial::Polynomial,
+    bound: usize,
+) -> Result<DegreeBoundProof, KZGErrors> {
+    pp.check_consistency()?;
+    let shift = degree_bound_shift(pp, bound)?;
+    check_degree(bound, polynomial)?;
+
+    let mut coefficients = vec![Fr::default(); shift];
+    coefficients.extend_from_slice(&polynomial.coefficients);
+    let shifted = commit_point(pp, pp.max_degree(), &polynomial::Polynomial::from_coefficients(coefficients))?;
+
+    Ok(DegreeBoundProof { bound, shifted })
+}
+
+/// checks a [`DegreeBoundProof`] for the commitment point `commitment`:
+/// `e(C, [tau^(D - bound)]_2) == e(shifted, [1]_2)`. Fails if the setup lacks the G2 power
+pub fn verify_degree_bound(pp: &PP, commitment: &P1, proof: &DegreeBoundProof) -> Result<bool, KZGErrors> {
+    let shift = degree_bound_shift(pp, proof.bound)?;
+    Ok(verify_pairings(*commitment, pp.g2_power(shift)?, proof.shifted, P2::generator()))
+}
+
+
+// ===================================
+// PUBLIC PARAMETER WELL-FORMEDNESS
+// ===================================
+/// a uniformly random scalar from `thread_rng`, for checks that must not be predictable
+fn random_scalar() -> Fr {
+    let mut bytes = [0u8; 64];
+    thread_rng().fill_bytes(&mut bytes);
+    scalar::fr_from_bytes_be(&bytes)
+}
+
+impl PP {
+    /// checks that the parameters really are `[1]_1, [tau]_1, ..., [tau^n]_1` and
+    /// `[1]_2, [tau]_2, ...` for a single (unknown) tau, which is worth doing for parameters
+    /// received from an untrusted source.
+    ///
+    /// Each step `e([tau^(i+1)]_1, [1]_2) == e([tau^i]_1, [tau]_2)` is folded with powers of a
+    /// Fiat–Shamir challenge `r`, so the whole check costs two pairings:
+    /// `e(sum r^i [tau^(i+1)]_1, [1]_2) == e(sum r^i [tau^i]_1, [tau]_2)`. Extra G2 powers are
+    /// checked the same way against `[tau]_1`, for two more pairings.
+    pub fn verify_structure(&self) -> Result<(), KZGErrors> {
+        self.check_consistency()?;
+        self.check_generators()?;
+        if self.max_degree == 0 {
+            return Ok(());
+        }
+
+        let mut transcript = Transcript::new(b"kzg-pp-structure");
+        for point in &self.points_in_g1 {
+            transcript.append_point(point);
+        }
+        for point in &self.points_in_g2 {
+            transcript.append_bytes(&point.compress());
+        }
+        let r_g1 = transcript.challenge();
+        let r_g2 = transcript.challenge();
+        self.check_powers(r_g1, r_g2)
+    }
+
+    /// the full check for parameters from an untrusted source: every point must be in its
+    /// prime-order subgroup (reported as [`KZGErrors::PointNotInSubgroup`], G1 points first), and
+    /// then the powers are checked as in [`PP::verify_structure`], folded with scalars drawn from
+    /// `thread_rng` instead of a challenge derived from the points.
+    ///
+    /// Points decoded by this crate are always in the subgroup, so the first part only matters for
+    /// points built some other way; it costs a decompression per point.
+    pub fn validate(&self) -> Result<(), KZGErrors> {
+        self.check_consistency()?;
+        if let Some(index) = self.points_in_g1.iter().position(|point| !group::g1_in_subgroup(point)) {
+            return Err(KZGErrors::PointNotInSubgroup { index });
+        }
+        if let Some(index) = self.points_in_g2.iter().position(|point| !group::g2_in_subgroup(point)) {
+            return Err(KZGErrors::PointNotInSubgroup {
+                index: self.points_in_g1.len() + index,
+            });
+        }
+
+        self.check_generators()?;
+        if self.max_degree == 0 {
+            return Ok(());
+        }
+        self.check_powers(random_scalar(), random_scalar())
+    }
+
+    fn check_generators(&self) -> Result<(), KZGErrors> {
+        if self.points_in_g1[0].compress() != P1::generator().compress()
+            || self.points_in_g2[0].compress() != P2::generator().compress()
+        {
+            return Err(KZGErrors::FirstPointIsNotGenerator);
+        }
+        Ok(())
+    }
+
+    /// the folded pairing checks of [`PP::verify_structure`], with `r` folding the G1 steps and
+    /// `r_g2` the G2 ones
+    fn check_powers(&self, r: Fr, r_g2: Fr) -> Result<(), KZGErrors> {
+        let mut shifted = P1::default();
+        let mut unshifted = P1::default();
+        let mut power = Fr::from_u64(1);
+        for pair in self.points_in_g1.windows(2) {
+            unshifted = group::g1_add(&unshifted, &(power * pair[0]));
+            shifted = group::g1_add(&shifted, &(power * pair[1]));
+            power *= r;
+        }
+
+        if !verify_pairings(shifted, P2::generator(), unshifted, self.point_in_g2()) {
+            return Err(KZGErrors::NotPowersOfTau);
+        }
+
+        // e([tau]_1, [tau^i]_2) == e([1]_1, [tau^(i+1)]_2); the first step is already covered above
+        if self.points_in_g2.len() > 2 {
+            let r = r_g2;
+            let mut shifted = P2::default();
+            let mut unshifted = P2::default();
+            let mut power = Fr::from_u64(1);
+            for pair in self.points_in_g2.windows(2) {
+                unshifted = group::g2_add(&unshifted, &(power * pair[0]));
+                shifted = group::g2_add(&shifted, &(power * pair[1]));
+                power *= r;
+            }
+
+            if !verify_pairings(self.points_in_g1[1], unshifted, P1::generator(), shifted) {
+                return Err(KZGErrors::NotPowersOfTau);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+
+
+
+
+
+
+
+
+
+
+/// This this is a sample test from Ethereum SPECS for EIP4844
+#[cfg(test)]
+mod tests {
+    use crate::fixed_base::CommitKey;
+    use crate::polynomial::Polynomial;
+    use oblast_demo::Scalar;
+    use rand::rngs::StdRng;
+
+    use super::*;
+
+    /// returns `bad_byte` repeated for the first `bad_draws` calls, then a fixed valid value
+    struct ScriptedRng {
+        bad_byte: u8,
+        bad_draws: usize,
+        draws: usize,
+    }
+
+    impl RngCore for ScriptedRng {
+        fn next_u32(&mut self) -> u32 {
+            self.next_u64() as u32
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            let mut bytes = [0u8; 8];
+            self.fill_bytes(&mut bytes);
+            u64::from_le_bytes(bytes)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            let byte = if self.draws < self.bad_draws { self.bad_byte } else { 0x11 };
+            self.draws += 1;
+            dest.iter_mut().for_each(|b| *b = byte);
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    impl CryptoRng for ScriptedRng {}
+
+    #[test]
+    fn test_new_rand_with_rng_is_deterministic() {
+        let first = KZG::new_rand_with_rng(&mut StdRng::seed_from_u64(42), 8).unwrap();
+        let second = KZG::new_rand_with_rng(&mut StdRng::seed_from_u64(42), 8).unwrap();
+        assert_eq!(first, second);
+
+        let other = KZG::new_rand_with_rng(&mut StdRng::seed_from_u64(43), 8).unwrap();
+        assert_ne!(
+            first.public_parameter.points_in_g1[1].compress(),
+            other.public_parameter.points_in_g1[1].compress()
+        );
+    }
+
+    #[test]
+    fn test_new_rand_rejects_secrets_above_the_order() {
+        let mut rng = ScriptedRng { bad_byte: 0xff, bad_draws: 3, draws: 0 };
+        let kzg = KZG::new_rand_with_rng(&mut rng, 2).unwrap();
+
+        // three rejected draws and one accepted
+        assert_eq!(rng.draws, 4);
+        assert_eq!(kzg, KZG::new(&[0x11u8; 32], 2).unwrap());
+    }
+
+    fn curve_order_bytes() -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        let raw = curve_order().to_bytes_be();
+        bytes[32 - raw.len()..].copy_from_slice(&raw);
+        bytes
+    }
+
+    #[test]
+    fn test_pp_bytes_round_trip() {
+        let kzg = KZG::new(&[8u8; 32], 10).unwrap();
+        let bytes = kzg.public_parameter.to_bytes();
+        assert_eq!(bytes.len(), 53 + 11 * 48 + 2 * 96);
+
+        let restored = PP::from_bytes(&bytes).unwrap();
+        assert_eq!(restored, kzg.public_parameter);
+
+        // commitments and openings behave the same against the restored parameters
+        let polynomial = Polynomial::from(vec![5u64, 4, 3, 2, 1, 0, 9]);
+        let original = KZG::commit(&kzg.public_parameter, &polynomial).unwrap();
+        let commitment = KZG::commit(&restored, &polynomial).unwrap();
+        assert_eq!(commitment.element.compress(), original.element.compress());
+
+        let z = Fr::from_u64(77);
+        let opening = commitment.open_at(z).unwrap();
+        assert_eq!(opening.proof.compress(), original.open_at(z).unwrap().proof.compress());
+        assert!(opening.verify(&z, &commitment));
+        assert!(opening.verify(&z, &original));
+    }
+
+    #[test]
+    fn test_commit_many() {
+        let kzg = KZG::new(&[0x36u8; 32], 16).unwrap();
+        let columns: Vec<Polynomial> = (0..9u64)
+            .map(|i| Polynomial::from((0..=i + 4).map(|j| i * 31 + j).collect::<Vec<u64>>()))
+            .collect();
+        let polynomials: Vec<&Polynomial> = columns.iter().collect();
+
+        let commitments = KZG::commit_many(&kzg.public_parameter, &polynomials).unwrap();
+        assert_eq!(commitments.len(), columns.len());
+        for (commitment, polynomial) in commitments.iter().zip(&columns) {
+            let expected = KZG::commit(&kzg.public_parameter, polynomial).unwrap();
+            assert_eq!(commitment.compress(), expected.element.compress());
+        }
+        assert!(KZG::commit_many(&kzg.public_parameter, &[]).unwrap().is_empty());
+
+        // one polynomial over the setup degree fails the whole batch
+        let too_large = Polynomial::from(vec![1u64; 18]);
+        let mut with_too_large = polynomials.clone();
+        with_too_large.insert(4, &too_large);
+        assert!(matches!(
+            KZG::commit_many(&kzg.public_parameter, &with_too_large),
+            Err(KZGErrors::PolynomialDegreeTooLarge { degree: 17, max_degree: 16 })
+        ));
+    }
+
+    #[test]
+    fn test_commit_never_truncates() {
+        let pp = KZG::new(&[0x69u8; 32], 5).unwrap().public_parameter;
+
+        // zipping the 11 coefficients with the 6 points would silently commit to the first 6
+        let degree_10 = Polynomial::from((1..=11u64).collect::<Vec<_>>());
+        assert!(matches!(
+            KZG::commit(&pp, &degree_10),
+            Err(KZGErrors::PolynomialDegreeTooLarge { degree: 10, max_degree: 5 })
+        ));
+
+        // longer than the setup only through trailing zeros
+        let padded = Polynomial::from(vec![1u64, 2, 3, 4, 5, 6, 0, 0, 0, 0, 0]);
+        let commitment = KZG::commit(&pp, &padded).unwrap();
+        let unpadded = KZG::commit(&pp, &Polynomial::from(vec![1u64, 2, 3, 4, 5, 6])).unwrap().element;
+        assert_eq!(commitment.element.compress(), unpadded.compress());
+        let z = Fr::from_u64(69);
+        assert!(commitment.open_at(z).unwrap().verify(&z, &commitment));
+
+        // the quotient of an oversized polynomial would not fit the setup either
+        let oversized = Commitment { polynomial: &degree_10, ..commitment };
+        assert!(matches!(
+            oversized.open_at(z),
+            Err(KZGErrors::PolynomialDegreeTooLarge { degree: 10, max_degree: 5 })
+        ));
+    }
+
+    #[test]
+    fn test_commit_checks_max_degree() {
+        let kzg = KZG::new(&[22u8; 32], 3).unwrap();
+        assert_eq!(kzg.public_parameter.max_degree(), 3);
+        assert_eq!(kzg.degree(), 3);
+
+        let too_long = Polynomial::from(vec![1u64, 2, 3, 4, 5]);
+        assert!(matches!(
+            KZG::commit(&kzg.public_parameter, &too_long),
+            Err(KZGErrors::PolynomialDegreeTooLarge { degree: 4, max_degree: 3 })
+        ));
+
+        // trailing zero coefficients don't count towards the degree
+        let padded = Polynomial::from(vec![1u64, 2, 3, 4, 0, 0]);
+        assert!(KZG::commit(&kzg.public_parameter, &padded).is_ok());
+
+        let kzg_29 = KZG::new(&[23u8; 32], 29).unwrap();
+        let degree_30 = Polynomial::from((1..=31u64).collect::<Vec<_>>());
+        assert!(matches!(
+            KZG::commit(&kzg_29.public_parameter, &degree_30),
+            Err(KZGErrors::PolynomialDegreeTooLarge { degree: 30, max_degree: 29 })
+        ));
+        let mut degree_29 = (1..=30u64).collect::<Vec<_>>();
+        degree_29.resize(40, 0);
+        let degree_29 = Polynomial::from(degree_29);
+        let commitment = KZG::commit(&kzg_29.public_parameter, &degree_29).unwrap();
+        let z = Fr::from_u64(29);
+        assert!(commitment.open_at(z).unwrap().verify(&z, &commitment));
+
+        // a hand-built commitment to an oversized polynomial can't be opened either
+        let forged = Commitment {
+            element: commitment.element,
+            polynomial: &degree_30,
+            public_parameter: &kzg_29.public_parameter,
+        };
+        assert!(matches!(
+            forged.open_at(z),
+            Err(KZGErrors::PolynomialDegreeTooLarge { degree: 30, max_degree: 29 })
+        ));
+
+        let mut tampered = kzg.public_parameter.clone();
+        tampered.points_in_g1.pop();
+        assert!(matches!(
+            KZG::commit(&tampered, &Polynomial::from(vec![1u64])),
+            Err(KZGErrors::InconsistentMaxDegree { max_degree: 3, points: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_verify_structure() {
+        let kzg = KZG::new(&[23u8; 32], 9).unwrap();
+        assert!(kzg.public_parameter.verify_structure().is_ok());
+        assert!(KZG::new(&[23u8; 32], 0).unwrap().public_parameter.verify_structure().is_ok());
+
+        let mut swapped = kzg.public_parameter.clone();
+        swapped.points_in_g1.swap(4, 5);
+        assert!(matches!(swapped.verify_structure(), Err(KZGErrors::NotPowersOfTau)));
+
+        let mut replaced = kzg.public_parameter.clone();
+        replaced.points_in_g1[7] = Fr::from_u64(7) * P1::generator();
+        assert!(matches!(replaced.verify_structure(), Err(KZGErrors::NotPowersOfTau)));
+
+        let mut first = kzg.public_parameter.clone();
+        first.points_in_g1[0] = Fr::from_u64(2) * P1::generator();
+        assert!(matches!(first.verify_structure(), Err(KZGErrors::FirstPointIsNotGenerator)));
+
+        // a consistent G1 progression paired with another tau's G2 point
+        let other = KZG::new(&[24u8; 32], 9).unwrap();
+        let mut mismatched = kzg.public_parameter.clone();
+        mismatched.points_in_g2 = other.public_parameter.points_in_g2.clone();
+        assert!(matches!(mismatched.verify_structure(), Err(KZGErrors::NotPowersOfTau)));
+    }
+
+    #[test]
+    fn test_validate() {
+        let pp = KZG::setup_with_g2_powers(&[0x51u8; 32], 9, 4).unwrap().public_parameter;
+        assert!(pp.validate().is_ok());
+        assert!(KZG::new(&[0x51u8; 32], 0).unwrap().public_parameter.validate().is_ok());
+
+        // a point on the curve but outside the subgroup, built without the subgroup check
+        let outside = {
+            let bytes = group::non_subgroup_g1();
+            let mut affine = blst::blst_p1_affine::default();
+            let mut point = blst::blst_p1::default();
+            unsafe {
+                assert_eq!(blst::blst_p1_uncompress(&mut affine, bytes.as_ptr()), blst::BLST_ERROR::BLST_SUCCESS);
+                blst::blst_p1_from_affine(&mut point, &affine);
+            }
+            P1::from_raw(point)
+        };
+        let mut corrupted = pp.clone();
+        corrupted.points_in_g1[6] = outside;
+        assert!(matches!(corrupted.validate(), Err(KZGErrors::PointNotInSubgroup { index: 6 })));
+
+        // subgroup points that break the progression, in G1 and in G2
+        let mut corrupted = pp.clone();
+        corrupted.points_in_g1[3] = corrupted.points_in_g1[3] + P1::generator();
+        assert!(matches!(corrupted.validate(), Err(KZGErrors::NotPowersOfTau)));
+        let mut corrupted = pp.clone();
+        corrupted.points_in_g2[3] = corrupted.points_in_g2[2];
+        assert!(matches!(corrupted.validate(), Err(KZGErrors::NotPowersOfTau)));
+        let mut corrupted = pp.clone();
+        corrupted.points_in_g1[0] = Fr::from_u64(2) * P1::generator();
+        assert!(matches!(corrupted.validate(), Err(KZGErrors::FirstPointIsNotGenerator)));
+    }
+
+    #[test]
+    fn test_error_messages() {
+        assert_eq!(
+            KZGErrors::PolynomialDegreeTooLarge { degree: 9, max_degree: 8 }.to_string(),
+            "polynomial degree 9 exceeds the setup's maximum degree 8"
+        );
+        assert_eq!(KZGErrors::PointNotInSubgroup { index: 3 }.to_string(), "point 3 is outside the prime-order subgroup");
+        assert_eq!(
+            KZGErrors::VerificationInputNotInSubgroup { input: "proof" }.to_string(),
+            "the proof is outside the prime-order subgroup"
+        );
+        assert_eq!(KZGErrors::InexactDivision.to_string(), "the opening quotient left a remainder");
+
+        fn commit_too_large() -> Result<(), Box<dyn std::error::Error>> {
+            let pp = KZG::new(&[0x67u8; 32], 2)?.public_parameter;
+            KZG::commit(&pp, &Polynomial::from(vec![1u64; 4]))?;
+            Ok(())
+        }
+        let error = commit_too_large().unwrap_err();
+        assert_eq!(error.to_string(), "polynomial degree 3 exceeds the setup's maximum degree 2");
+        assert!(matches!(
+            error.downcast_ref::<KZGErrors>(),
+            Some(KZGErrors::PolynomialDegreeTooLarge { degree: 3, max_degree: 2 })
+        ));
+    }
+
+    /// a constant `c` with the claimed value `-c` and the identity as proof: `C - [y]_1 = 2C`, which
+    /// an addition that can't double gets wrong
+    fn forged_constant_opening(pp: &PP, c: u64) -> (P1, Opening) {
+        let commitment = KZG::commit(pp, &Polynomial::from(vec![c])).unwrap().element;
+        (commitment, Opening { value: -Fr::from_u64(c), proof: P1::default() })
+    }
+
+    #[test]
+    fn test_forged_constant_openings_are_rejected() {
+        let pp = KZG::new(&[7u8; 32], 4).unwrap().public_parameter;
+        let polynomial = Polynomial::from(vec![5u64]);
+        let commitment = KZG::commit(&pp, &polynomial).unwrap();
+        let z = Fr::from_u64(123);
+        let (_, forged) = forged_constant_opening(&pp, 5);
+        assert!(!forged.verify(&z, &commitment));
+        assert!(commitment.open_at(z).unwrap().verify(&z, &commitment));
+
+        // at z = -tau, [tau]_2 - [z]_2 doubles [tau]_2, and honest openings must still verify
+        let tau = scalar::fr_from_bytes_be(&[7u8; 32]);
+        let polynomial = Polynomial::from(vec![3u64, 1, 4, 1, 5]);
+        let commitment = KZG::commit(&pp, &polynomial).unwrap();
+        let opening = commitment.open_at(-tau).unwrap();
+        assert!(opening.verify(&-tau, &commitment));
+    }
+
+    #[test]
+    fn test_verify_detailed() {
+        let pp = KZG::new(&[0x66u8; 32], 8).unwrap().public_parameter;
+        let polynomial = Polynomial::from(vec![6u64, 6, 0, 1]);
+        let commitment = KZG::commit(&pp, &polynomial).unwrap();
+        let z = Fr::from_u64(66);
+        let opening = commitment.open_at(z).unwrap();
+        assert!(opening.verify_detailed(&z, &commitment).unwrap());
+
+        // well-formed, but for another point
+        assert!(!opening.verify_detailed(&(z + Fr::from_u64(1)), &commitment).unwrap());
+
+        // well-formed too, the identity is in the subgroup: a forgery, not a malformed input
+        let constant = Polynomial::from(vec![6u64]);
+        let constant_commitment = KZG::commit(&pp, &constant).unwrap();
+        let (_, forged) = forged_constant_opening(&pp, 6);
+        assert!(!forged.verify_detailed(&z, &constant_commitment).unwrap());
+
+        let outside = group::decompress_g1_with(&group::non_subgroup_g1(), ValidationLevel::DecompressOnly).unwrap();
+        let bad_proof = Opening { proof: outside, ..opening.clone() };
+        assert!(matches!(
+            bad_proof.verify_detailed(&z, &commitment),
+            Err(KZGErrors::VerificationInputNotInSubgroup { input: "proof" })
+        ));
+        let bad_commitment = Commitment { element: outside, ..KZG::commit(&pp, &polynomial).unwrap() };
+        assert!(matches!(
+            opening.verify_detailed(&z, &bad_commitment),
+            Err(KZGErrors::VerificationInputNotInSubgroup { input: "commitment" })
+        ));
+
+        let mut inconsistent = pp.clone();
+        inconsistent.points_in_g1.pop();
+        let commitment = Commitment {
+            element: commitment.element,
+            polynomial: &polynomial,
+            public_parameter: &inconsistent,
+        };
+        assert!(matches!(
+            opening.verify_detailed(&z, &commitment),
+            Err(KZGErrors::InconsistentMaxDegree { max_degree: 8, points: 8 })
+        ));
+    }
+
+    #[test]
+    fn test_setup_compatibility() {
+        let full = KZG::setup_with_g2_powers(&[0x63u8; 32], 16, 3).unwrap().public_parameter;
+        let truncated = full.truncated(5).unwrap();
+        let fewer_g2 = KZG::new(&[0x63u8; 32], 5).unwrap().public_parameter;
+
+        assert!(full.same_setup(&full.clone()));
+        assert!(!full.same_setup(&truncated));
+        assert!(full.is_prefix_of(&full));
+        assert!(truncated.is_prefix_of(&full));
+        assert!(!full.is_prefix_of(&truncated));
+        assert!(fewer_g2.is_prefix_of(&truncated));
+        assert!(!truncated.is_prefix_of(&fewer_g2));
+
+        // a different tau shares only the generators
+        let other = KZG::setup_with_g2_powers(&[0x64u8; 32], 16, 3).unwrap().public_parameter;
+        assert!(!other.same_setup(&full));
+        assert!(!other.truncated(5).unwrap().is_prefix_of(&full));
+        assert!(!truncated.is_prefix_of(&other));
+        // even with the same `[1]_1` alone, `[tau]_2` differs
+        assert!(!full.truncated(0).unwrap().is_prefix_of(&other));
+
+        // strict verification accepts commitments under the setup or a truncation of it
+        let polynomial = Polynomial::from(vec![5u64, 0, 3, 1]);
+        let z = Fr::from_u64(0x63);
+        for setup in [&full, &truncated, &fewer_g2] {
+            let commitment = KZG::commit(setup, &polynomial).unwrap();
+            let opening = commitment.open_at(z).unwrap();
+            assert!(opening.verify_strict(&z, &commitment, &full).unwrap());
+            assert!(!opening.verify_strict(&(z + Fr::from_u64(1)), &commitment, &full).unwrap());
+        }
+
+        // but not under a setup that contains the expected one, or another tau
+        let commitment = KZG::commit(&full, &polynomial).unwrap();
+        let opening = commitment.open_at(z).unwrap();
+        assert!(matches!(opening.verify_strict(&z, &commitment, &truncated), Err(KZGErrors::SetupMismatch)));
+        let foreign = KZG::commit(&other, &polynomial).unwrap();
+        let foreign_opening = foreign.open_at(z).unwrap();
+        assert!(foreign_opening.verify(&z, &foreign));
+        assert!(matches!(foreign_opening.verify_strict(&z, &foreign, &full), Err(KZGErrors::SetupMismatch)));
+
+        // a compatible setup doesn't make a forged opening valid
+        let constant = Polynomial::from(vec![9u64]);
+        let constant_commitment = KZG::commit(&truncated, &constant).unwrap();
+        let (_, forged) = forged_constant_opening(&truncated, 9);
+        assert!(!forged.verify_strict(&z, &constant_commitment, &full).unwrap());
+    }
+
+    #[test]
+    fn test_truncated_and_sliced_setups() {
+        let kzg = KZG::new(&[27u8; 32], 20).unwrap();
+        let full = &kzg.public_parameter;
+
+        let truncated = full.truncated(8).unwrap();
+        let view = full.as_slice(8).unwrap();
+        assert_eq!(truncated.max_degree(), 8);
+        assert_eq!(view.max_degree(), 8);
+        assert_eq!(truncated.points_in_g2.len(), full.points_in_g2.len());
+
+        // exactly at the boundary degree
+        let polynomial = Polynomial::from(vec![9u64, 8, 7, 6, 5, 4, 3, 2, 1]);
+        let expected = KZG::commit(full, &polynomial).unwrap();
+        let from_truncated = KZG::commit(&truncated, &polynomial).unwrap();
+        let from_view = KZG::commit(&view, &polynomial).unwrap();
+        assert_eq!(from_truncated.element.compress(), expected.element.compress());
+        assert_eq!(from_view.element.compress(), expected.element.compress());
+
+        let z = Fr::from_u64(31);
+        let opening = from_view.open_at(z).unwrap();
+        assert!(opening.verify(&z, &from_view));
+        assert!(opening.verify(&z, &expected));
+        assert!(from_truncated.open_at(z).unwrap().verify(&z, &from_truncated));
+
+        // one degree too many for the smaller setups
+        let too_long = Polynomial::from(vec![1u64; 10]);
+        assert!(matches!(
+            KZG::commit(&view, &too_long),
+            Err(KZGErrors::PolynomialDegreeTooLarge { degree: 9, max_degree: 8 })
+        ));
+        assert!(KZG::commit(&truncated, &too_long).is_err());
+
+        assert!(full.as_slice(20).is_ok());
+        assert!(matches!(full.truncated(21), Err(KZGErrors::DegreeExceedsSetup { requested: 21, available: 20 })));
+        assert!(matches!(full.as_slice(21), Err(KZGErrors::DegreeExceedsSetup { .. })));
+    }
+
+    #[test]
+    fn test_equality_proof() {
+        let pp_1 = KZG::new(&[28u8; 32], 8).unwrap().public_parameter;
+        let pp_2 = KZG::new(&[29u8; 32], 12).unwrap().public_parameter;
+
+        let polynomial = Polynomial::from(vec![3u64, 1, 4, 1, 5, 9, 2, 6]);
+        let commitment_1 = KZG::commit(&pp_1, &polynomial).unwrap();
+        let commitment_2 = KZG::commit(&pp_2, &polynomial).unwrap();
+        assert_ne!(commitment_1.element.compress(), commitment_2.element.compress());
+
+        let proof = prove_equal(&polynomial, &pp_1, &pp_2).unwrap();
+        assert!(verify_equal(&commitment_1, &commitment_2, &proof));
+
+        // the same setup on both sides works too
+        let same = KZG::commit(&pp_1, &polynomial).unwrap();
+        assert!(verify_equal(&commitment_1, &same, &prove_equal(&polynomial, &pp_1, &pp_1).unwrap()));
+
+        // a proof for `polynomial` says nothing about a different one
+        let other = Polynomial::from(vec![3u64, 1, 4, 1, 5, 9, 2, 7]);
+        let other_commitment = KZG::commit(&pp_2, &other).unwrap();
+        assert!(!verify_equal(&commitment_1, &other_commitment, &proof));
+        let other_proof = prove_equal(&other, &pp_1, &pp_2).unwrap();
+        assert!(!verify_equal(&commitment_1, &other_commitment, &other_proof));
+
+        // swapping the commitments changes the challenge
+        assert!(!verify_equal(&commitment_2, &commitment_1, &proof));
+
+        // the same constant under both setups, claimed to take its negation with identity proofs
+        let constant = Polynomial::from(vec![5u64]);
+        let constant_1 = KZG::commit(&pp_1, &constant).unwrap();
+        let constant_2 = KZG::commit(&pp_2, &constant).unwrap();
+        let forged = EqualityProof {
+            value: -Fr::from_u64(5),
+            proof_1: P1::default(),
+            proof_2: P1::default(),
+        };
+        assert!(!verify_equal(&constant_1, &constant_2, &forged));
+        assert!(verify_equal(&constant_1, &constant_2, &prove_equal(&constant, &pp_1, &pp_2).unwrap()));
+    }
+
+    #[test]
+    fn test_pp_from_g1_points() {
+        let kzg = KZG::new(&[21u8; 32], 6).unwrap();
+        let setup = &kzg.public_parameter;
+        let custom = PP::from_g1_points(setup.points_in_g1.clone(), setup.point_in_g2()).unwrap();
+
+        let polynomial = Polynomial::from(vec![1u64, 1, 2, 3, 5, 8, 13]);
+        let expected = KZG::commit(setup, &polynomial).unwrap();
+        let commitment = KZG::commit(&custom, &polynomial).unwrap();
+        assert_eq!(commitment.element.compress(), expected.element.compress());
+
+        let z = Fr::from_u64(4);
+        assert!(commitment.open_at(z).unwrap().verify(&z, &expected));
+
+        assert!(matches!(
+            PP::from_g1_points(Vec::new(), setup.point_in_g2()),
+            Err(KZGErrors::EmptyPublicParameter)
+        ));
+    }
+
+    #[test]
+    fn test_pp_from_bytes_rejects_malformed_input() {
+        let bytes = KZG::new(&[8u8; 32], 3).unwrap().public_parameter.to_bytes();
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b'X';
+        assert!(matches!(PP::from_bytes(&bad_magic), Err(KZGErrors::InvalidSerializationMagic)));
+
+        let mut bad_version = bytes.clone();
+        bad_version[4] = 99;
+        assert!(matches!(PP::from_bytes(&bad_version), Err(KZGErrors::UnsupportedSerializationVersion(99))));
+
+        // cut in the middle of the second G1 point
+        assert!(matches!(PP::from_bytes(&bytes[..53 + 48 + 20]), Err(KZGErrors::TruncatedInput)));
+        assert!(matches!(PP::from_bytes(&bytes[..5]), Err(KZGErrors::TruncatedInput)));
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(matches!(PP::from_bytes(&trailing), Err(KZGErrors::TrailingBytes)));
+
+        // a degree claiming far more points than present must not overflow or allocate
+        let mut huge_degree = bytes.clone();
+        huge_degree[5..13].copy_from_slice(&u64::MAX.to_be_bytes());
+        assert!(matches!(PP::from_bytes(&huge_degree), Err(KZGErrors::TruncatedInput)));
+
+        // clearing the compression flag of the third G1 point
+        let mut bad_point = bytes.clone();
+        bad_point[53 + 2 * 48] &= 0x7f;
+        assert!(matches!(PP::from_bytes(&bad_point), Err(KZGErrors::InvalidPoint { index: 2 })));
+
+        let mut bad_g2 = bytes.clone();
+        let g2_start = bytes.len() - 96;
+        bad_g2[g2_start] &= 0x7f;
+        assert!(matches!(PP::from_bytes(&bad_g2), Err(KZGErrors::InvalidPoint { index: 5 })));
+
+        let mut one_g2 = bytes.clone();
+        one_g2[13..21].copy_from_slice(&1u64.to_be_bytes());
+        assert!(matches!(PP::from_bytes(&one_g2), Err(KZGErrors::NotEnoughG2Powers { found: 1 })));
+
+        // a valid point swapped in for another no longer matches the digest
+        let mut swapped = bytes.clone();
+        let (first, second) = (53 + 48, 53 + 2 * 48);
+        let third_point = swapped[second..second + 48].to_vec();
+        swapped.copy_within(first..first + 48, second);
+        swapped[first..first + 48].copy_from_slice(&third_point);
+        assert!(matches!(PP::from_bytes(&swapped), Err(KZGErrors::DigestMismatch)));
+
+        let mut bad_digest = bytes.clone();
+        bad_digest[30] ^= 1;
+        assert!(matches!(PP::from_bytes(&bad_digest), Err(KZGErrors::DigestMismatch)));
+    }
+
+    #[test]
+    fn test_pp_stream_round_trip() {
+        let pp = KZG::new(&[0x54u8; 32], 3).unwrap().public_parameter;
+        let mut stream = Vec::new();
+        pp.write_to(&mut stream).unwrap();
+        stream.extend_from_slice(b"next");
+
+        // only the announced length is consumed
+        let mut reader = io::Cursor::new(&stream);
+        assert_eq!(PP::read_from(&mut reader).unwrap(), pp);
+        assert_eq!(reader.position() as usize, stream.len() - 4);
+
+        // every cut surfaces as an error, in the header as well as in the points
+        for cut in [0, 3, 12, 40, 53 + 48 + 20, stream.len() - 5] {
+            assert!(
+                matches!(PP::read_from(&stream[..cut]), Err(KZGErrors::TruncatedInput)),
+                "cut at {}",
+                cut
+            );
+        }
+
+        // a degree announcing far more points than the stream has
+        let mut huge_degree = stream.clone();
+        huge_degree[5..13].copy_from_slice(&(1u64 << 40).to_be_bytes());
+        assert!(matches!(PP::read_from(&huge_degree[..]), Err(KZGErrors::TruncatedInput)));
+
+        let mut bad_version = stream.clone();
+        bad_version[4] = 99;
+        assert!(matches!(PP::read_from(&bad_version[..]), Err(KZGErrors::UnsupportedSerializationVersion(99))));
+
+        let error: KZGErrors = io::Error::other("disk on fire").into();
+        assert!(matches!(&error, KZGErrors::Io(message) if message == "disk on fire"));
+        assert_eq!(io::Error::from(error).to_string(), "disk on fire");
+        assert_eq!(io::Error::from(KZGErrors::TruncatedInput).kind(), io::ErrorKind::UnexpectedEof);
+        let invalid = io::Error::from(KZGErrors::DigestMismatch);
+        assert_eq!(invalid.kind(), io::ErrorKind::InvalidData);
+        assert!(matches!(
+            invalid.into_inner().unwrap().downcast::<KZGErrors>().map(|error| *error),
+            Ok(KZGErrors::DigestMismatch)
+        ));
+
+        // a writer that fails
+        struct Full;
+        impl Write for Full {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::StorageFull, "no space"))
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+        assert!(matches!(pp.write_to(Full), Err(KZGErrors::Io(_))));
+    }
+
+    #[test]
+    fn test_pp_from_bytes_reads_version_1() {
+        let pp = KZG::new(&[8u8; 32], 3).unwrap().public_parameter;
+
+        // magic, version 1, degree, the G1 points, then only [tau]_2
+        let mut v1 = b"KZGP".to_vec();
+        v1.push(1);
+        v1.extend_from_slice(&3u64.to_be_bytes());
+        for point in &pp.points_in_g1 {
+            v1.extend_from_slice(&point.compress());
+        }
+        v1.extend_from_slice(&pp.point_in_g2().compress());
+
+        assert_eq!(PP::from_bytes(&v1).unwrap(), pp);
+
+        // version 2: the same with a G2 count but no digest
+        let mut v2 = b"KZGP".to_vec();
+        v2.push(2);
+        v2.extend_from_slice(&3u64.to_be_bytes());
+        v2.extend_from_slice(&2u64.to_be_bytes());
+        for point in &pp.points_in_g1 {
+            v2.extend_from_slice(&point.compress());
+        }
+        for point in &pp.points_in_g2 {
+            v2.extend_from_slice(&point.compress());
+        }
+
+        assert_eq!(PP::from_bytes(&v2).unwrap(), pp);
+    }
+
+    #[test]
+    fn test_pp_from_bytes_point_validation() {
+        let pp = KZG::new(&[8u8; 32], 3).unwrap().public_parameter;
+        let bytes = pp.to_bytes();
+        let g1_slot = |index: usize| 53 + index * 48..53 + (index + 1) * 48;
+
+        // on the curve, outside the subgroup
+        let outside = group::non_subgroup_g1();
+        let mut not_in_subgroup = bytes.clone();
+        not_in_subgroup[g1_slot(2)].copy_from_slice(&outside);
+        assert!(matches!(PP::from_bytes(&not_in_subgroup), Err(KZGErrors::PointNotInSubgroup { index: 2 })));
+
+        // x = p, one past the largest canonical coordinate
+        let mut non_canonical = bytes.clone();
+        non_canonical[g1_slot(1)].copy_from_slice(&hex::decode(concat!(
+            "9a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf",
+            "6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab"
+        )).unwrap());
+        assert!(matches!(PP::from_bytes(&non_canonical), Err(KZGErrors::NonCanonicalPoint { index: 1 })));
+
+        let mut identity_first = bytes.clone();
+        identity_first[g1_slot(0)].copy_from_slice(&P1::default().compress());
+        assert!(matches!(PP::from_bytes(&identity_first), Err(KZGErrors::IdentityInGeneratorSlot)));
+
+        // the same checks apply with DecompressOnly, except for the subgroup
+        assert_eq!(PP::from_bytes_with_validation(&bytes, ValidationLevel::DecompressOnly).unwrap(), pp);
+        for (input, expected) in [(&non_canonical, "NonCanonicalPoint"), (&identity_first, "IdentityInGeneratorSlot")] {
+            let error = PP::from_bytes_with_validation(input, ValidationLevel::DecompressOnly).unwrap_err();
+            assert!(format!("{:?}", error).starts_with(expected));
+        }
+        assert!(matches!(
+            PP::from_bytes_with_validation(&not_in_subgroup, ValidationLevel::DecompressOnly),
+            Err(KZGErrors::DigestMismatch)
+        ));
+
+        // a trusted file holding a non-subgroup point (with its digest) only loads without the check
+        let mut points_in_g1 = pp.points_in_g1.clone();
+        points_in_g1[2] = group::decompress_g1_with(&outside, ValidationLevel::DecompressOnly).unwrap();
+        let trusted = PP::from_parts(points_in_g1, pp.points_in_g2.clone()).to_bytes();
+        assert!(matches!(PP::from_bytes(&trusted), Err(KZGErrors::PointNotInSubgroup { index: 2 })));
+        let loaded = PP::from_bytes_with_validation(&trusted, ValidationLevel::DecompressOnly).unwrap();
+        assert_eq!(loaded.points_in_g1[2].compress(), outside);
+
+        // without a digest (version 2) every point is fully checked regardless
+        let v2 = [&b"KZGP\x02"[..], &trusted[5..21], &trusted[53..]].concat();
+        assert!(matches!(
+            PP::from_bytes_with_validation(&v2, ValidationLevel::DecompressOnly),
+            Err(KZGErrors::PointNotInSubgroup { index: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_debug_summarizes_large_setups() {
+        let kzg = KZG::new(&[0x33u8; 32], 1000).unwrap();
+        let summary = format!("{:?}", kzg.public_parameter);
+        assert!(summary.len() < 300, "{}", summary);
+        assert!(summary.starts_with("PP { max_degree: 1000, digest: "));
+        assert!(summary.contains(&hex::encode(kzg.public_parameter.digest())));
+        assert!(summary.contains("g1_points: 1001, g2_points: 2"));
+
+        let last = hex::encode(kzg.public_parameter.points_in_g1[1000].compress());
+        assert!(summary.contains(&format!("last_g1: {}..{}", &last[..8], &last[88..])));
+
+        // too large to print in full, even in alternate mode
+        assert!(format!("{:#?}", kzg).len() < 400);
+        assert!(format!("{:?}", kzg).len() < 320);
+        assert!(format!("{:?}", kzg).starts_with("KZG { public_parameter: PP { max_degree: 1000"));
+    }
+
+    #[test]
+    fn test_debug_prints_small_setups_in_full() {
+        let kzg = KZG::new(&[0x34u8; 32], 3).unwrap();
+        let full = format!("{:#?}", kzg);
+        for point in &kzg.public_parameter.points_in_g1 {
+            assert!(full.contains(&hex::encode(point.compress())));
+        }
+        for point in &kzg.public_parameter.points_in_g2 {
+            assert!(full.contains(&hex::encode(point.compress())));
+        }
+        assert!(full.contains("max_degree: 3"));
+
+        // the plain mode stays a summary
+        let summary = format!("{:?}", kzg.public_parameter);
+        assert!(!summary.contains(&hex::encode(kzg.public_parameter.points_in_g1[1].compress())));
+        assert!(summary.contains("g1_points: 4"));
+    }
+
+    #[test]
+    fn test_opening_quotient_must_divide_exactly() {
+        let polynomial = Polynomial::from(vec![7u64, 0, 3, 5]);
+        let z = Fr::from_u64(9);
+        let value = polynomial.evaluate(z);
+
+        let quotient = opening_quotient(&polynomial, z, value).unwrap();
+        assert_eq!(quotient.degree(), Some(2));
+        let divisor = Polynomial::from_coefficients(vec![-z, Fr::from_u64(1)]);
+        assert_eq!((quotient * divisor).evaluate(Fr::from_u64(4)), polynomial.sub_constant(value).evaluate(Fr::from_u64(4)));
+
+        // a wrong value leaves a remainder, which the long division would silently drop
+        assert!(matches!(
+            opening_quotient(&polynomial, z, value + Fr::from_u64(1)),
+            Err(KZGErrors::InexactDivision)
+        ));
+
+        // constants divide to the zero polynomial
+        let constant = Polynomial::constant(Fr::from_u64(3));
+        assert!(opening_quotient(&constant, z, Fr::from_u64(3)).unwrap().is_zero());
+        assert!(matches!(opening_quotient(&constant, z, Fr::from_u64(4)), Err(KZGErrors::InexactDivision)));
+    }
+
+    #[test]
+    fn test_commit_arc_across_threads() {
+        let pp = Arc::new(KZG::new(&[0x53u8; 32], 64).unwrap().public_parameter);
+
+        let workers: Vec<_> = (0..4u64)
+            .map(|worker| {
+                let pp = Arc::clone(&pp);
+                std::thread::spawn(move || {
+                    let polynomial = Polynomial::from((0..=32).map(|i| i * worker + 1).collect::<Vec<_>>());
+                    let commitment = KZG::commit_arc(pp, polynomial).unwrap();
+                    let z = Fr::from_u64(worker + 100);
+                    let opening = commitment.open_at(z).unwrap();
+                    (commitment, z, opening)
+                })
+            })
+            .collect();
+
+        for worker in workers {
+            let (commitment, z, opening) = worker.join().unwrap();
+            assert!(opening.verify(&z, &commitment.as_commitment()));
+            let borrowed = KZG::commit(pp.as_ref(), &commitment.polynomial).unwrap();
+            assert_eq!(borrowed.element.compress(), commitment.element.compress());
+            assert!(opening.verify(&z, &borrowed));
+        }
+        assert_eq!(Arc::strong_count(&pp), 1);
+    }
+
+    #[test]
+    fn test_owned_commitments_outlive_their_inputs() {
+        // the setup and polynomials are locals of this function, only the owned copies leave it
+        fn commit_all() -> Vec<OwnedCommitment> {
+            let pp = KZG::new(&[0x43u8; 32], 8).unwrap().public_parameter;
+            (1..=3u64)
+                .map(|i| {
+                    let polynomial = Polynomial::from(vec![i, 2 * i, 3 * i]);
+                    KZG::commit(&pp, &polynomial).unwrap().to_owned()
+                })
+                .collect()
+        }
+
+        let commitments = commit_all();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        std::thread::spawn(move || sender.send(commitments).unwrap());
+        let commitments = receiver.recv().unwrap();
+
+        for (i, commitment) in commitments.iter().enumerate() {
+            let z = Fr::from_u64(i as u64 + 10);
+            let opening = commitment.open_at(z).unwrap();
+            assert!(opening.verify(&z, commitment));
+            assert!(opening.verify(&z, &commitment.as_commitment()));
+            assert!(!opening.verify(&(z + Fr::from_u64(1)), commitment));
+            assert_eq!(opening.value, commitment.polynomial.evaluate(z));
+        }
+    }
+
+    #[test]
+    fn test_commitments_are_additive() {
+        let pp = Arc::new(KZG::new(&[0x44u8; 32], 8).unwrap().public_parameter);
+        let f = KZG::commit_arc(Arc::clone(&pp), Polynomial::from(vec![1u64, 2, 3, 4])).unwrap();
+        let g = KZG::commit_arc(Arc::clone(&pp), Polynomial::from(vec![9u64, 0, 7])).unwrap();
+
+        let sum = f.checked_add(&g).unwrap();
+        let expected = CommitmentPoint(KZG::commit(pp.as_ref(), &(&f.polynomial + &g.polynomial)).unwrap().element);
+        assert_eq!(CommitmentPoint::from(&sum), expected);
+        assert_eq!(CommitmentPoint::from(&f) + CommitmentPoint::from(&g), CommitmentPoint::from(&sum));
+
+        // an opening of f + g at a random point verifies against the summed points
+        let z = random_scalar();
+        let opening = sum.open_at(z).unwrap();
+        let summed = CommitmentPoint::from(&f) + CommitmentPoint::from(&g);
+        assert!(opening.verify_against(z, summed.0, pp.point_in_g2()));
+        assert!(opening.verify(&z, &sum));
+
+        let difference = f.checked_sub(&g).unwrap();
+        let expected = CommitmentPoint(KZG::commit(pp.as_ref(), &(&f.polynomial - &g.polynomial)).unwrap().element);
+        assert_eq!(CommitmentPoint::from(&difference), expected);
+
+        // a commitment minus itself is the identity, plus itself is twice it
+        let identity = CommitmentPoint(P1::default());
+        assert_eq!(CommitmentPoint::from(&f) - CommitmentPoint::from(&f), identity);
+        assert_eq!(CommitmentPoint::from(&f) + identity, CommitmentPoint::from(&f));
+        assert_eq!(CommitmentPoint::from(&f) + CommitmentPoint::from(&f), CommitmentPoint(Fr::from_u64(2) * f.element));
+        assert_eq!(CommitmentPoint::from(&f.checked_sub(&f).unwrap()), identity);
+
+        // an equal setup in another `Arc` combines, a different one doesn't
+        let copy = KZG::commit_arc(Arc::new(pp.as_ref().clone()), g.polynomial.clone()).unwrap();
+        assert!(f.checked_add(&copy).is_ok());
+        let other = Arc::new(KZG::new(&[0x45u8; 32], 8).unwrap().public_parameter);
+        let foreign = KZG::commit_arc(other, g.polynomial.clone()).unwrap();
+        assert!(matches!(f.checked_add(&foreign), Err(KZGErrors::SetupMismatch)));
+        assert!(matches!(f.checked_sub(&foreign), Err(KZGErrors::SetupMismatch)));
+    }
+
+    #[test]
+    fn test_commitment_linear_combination() {
+        let pp = Arc::new(KZG::new(&[0x48u8; 32], 31).unwrap().public_parameter);
+        let mut rng = thread_rng();
+        // around the MSM threshold, so both paths are compared with the naive fold
+        for n in [2, 5, 24, 40] {
+            let commitments: Vec<OwnedCommitment> = (0..n)
+                .map(|_| {
+                    let degree = rng.gen_range(0..32);
+                    let coefficients: Vec<Fr> = (0..=degree).map(|_| random_scalar()).collect();
+                    KZG::commit_arc(Arc::clone(&pp), Polynomial::from_coefficients(coefficients)).unwrap()
+                })
+                .collect();
+            let scalars: Vec<Fr> = (0..n).map(|_| random_scalar()).collect();
+            let points: Vec<CommitmentPoint> = commitments.iter().map(CommitmentPoint::from).collect();
+
+            let naive = points
+                .iter()
+                .zip(&scalars)
+                .fold(CommitmentPoint(P1::default()), |sum, (point, scalar)| sum + *point * *scalar);
+            let combined = CommitmentPoint::linear_combination(&points, &scalars).unwrap();
+            assert_eq!(combined, naive, "{}", n);
+
+            // the combined polynomial opens against the combined point
+            let owned = OwnedCommitment::linear_combination(&pp, &commitments, &scalars).unwrap();
+            assert_eq!(CommitmentPoint::from(&owned), combined);
+            let z = random_scalar();
+            let opening = owned.open_at(z).unwrap();
+            assert!(opening.verify_against(z, combined, pp.point_in_g2()));
+            assert!(!opening.verify_against(z + Fr::from_u64(1), combined, pp.point_in_g2()));
+        }
+
+        // no points are the identity, one point is only scaled
+        let identity = CommitmentPoint(P1::default());
+        assert_eq!(CommitmentPoint::linear_combination(&[], &[]).unwrap(), identity);
+        let empty = OwnedCommitment::linear_combination(&pp, &[], &[]).unwrap();
+        assert_eq!(CommitmentPoint::from(&empty), identity);
+        assert_eq!(empty.polynomial, Polynomial::zero());
+
+        let f = KZG::commit_arc(Arc::clone(&pp), Polynomial::from(vec![4u64, 0, 2])).unwrap();
+        let three = Fr::from_u64(3);
+        let single = CommitmentPoint::linear_combination(&[CommitmentPoint::from(&f)], &[three]).unwrap();
+        assert_eq!(single, CommitmentPoint::from(&f) * three);
+
+        assert!(matches!(
+            CommitmentPoint::linear_combination(&[CommitmentPoint::from(&f)], &[three, three]),
+            Err(KZGErrors::MismatchedWeights { points: 1, weights: 2 })
+        ));
+        assert!(matches!(
+            OwnedCommitment::linear_combination(&pp, std::slice::from_ref(&f), &[]),
+            Err(KZGErrors::MismatchedWeights { points: 1, weights: 0 })
+        ));
+
+        // an equal setup in another `Arc` is the same, a different one is refused
+        let copy = Arc::new(pp.as_ref().clone());
+        assert!(OwnedCommitment::linear_combination(&copy, std::slice::from_ref(&f), &[three]).is_ok());
+        let other = Arc::new(KZG::new(&[0x49u8; 32], 31).unwrap().public_parameter);
+        let foreign = KZG::commit_arc(other, Polynomial::from(vec![1u64])).unwrap();
+        assert!(matches!(
+            OwnedCommitment::linear_combination(&pp, &[f, foreign], &[three, three]),
+            Err(KZGErrors::SetupMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_linear_combination() {
+        let pp = KZG::new(&[0x47u8; 32], 63).unwrap().public_parameter;
+        // a repeated point, and lengths on both sides of the MSM threshold
+        let mut points = pp.points_in_g1.clone();
+        points[5] = points[4];
+        for len in [0, 1, 6, msm::PIPPENGER_THRESHOLD - 1, msm::PIPPENGER_THRESHOLD, 64] {
+            let weights: Vec<Fr> = (0..len).map(|_| random_scalar()).collect();
+            let manual = points[..len]
+                .iter()
+                .zip(&weights)
+                .fold(CommitmentPoint(P1::default()), |sum, (point, weight)| sum + CommitmentPoint(*weight * *point));
+            assert_eq!(CommitmentPoint(linear_combination(&points[..len], &weights).unwrap()), manual, "{}", len);
+        }
+
+        // equal weights on equal points
+        let twice = linear_combination(&[points[4], points[4]], &[Fr::from_u64(3), Fr::from_u64(3)]).unwrap();
+        assert_eq!(twice.compress(), (Fr::from_u64(6) * points[4]).compress());
+
+        assert!(matches!(
+            linear_combination(&points[..3], &[Fr::from_u64(1); 2]),
+            Err(KZGErrors::MismatchedWeights { points: 3, weights: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_coefficient_updates() {
+        let pp = KZG::new(&[0x48u8; 32], 64).unwrap().public_parameter;
+        let coefficients: Vec<Fr> = (0..50).map(|_| random_scalar()).collect();
+        let polynomial = Polynomial::from_coefficients(coefficients.clone());
+        let commitment = CommitmentPoint::from(&KZG::commit(&pp, &polynomial).unwrap());
+        let mut rng = thread_rng();
+
+        // one random coefficient, which may be past the current degree
+        let index = rng.gen_range(0..=pp.max_degree());
+        let mut changed = coefficients.clone();
+        changed.resize(pp.max_degree() + 1, Fr::default());
+        let (old, new) = (changed[index], random_scalar());
+        changed[index] = new;
+        let updated = commitment.update_coefficient(&pp, index, old, new).unwrap();
+        let recommitted = KZG::commit(&pp, &Polynomial::from_coefficients(changed)).unwrap().element;
+        assert_eq!(updated, CommitmentPoint(recommitted));
+
+        // ten changes at once, one of them changing an index twice
+        let mut changed = coefficients.clone();
+        let mut updates: Vec<(usize, Fr, Fr)> = Vec::new();
+        for step in 0..10 {
+            let index = if step == 9 { updates[0].0 } else { rng.gen_range(0..coefficients.len()) };
+            let new = random_scalar();
+            updates.push((index, changed[index], new));
+            changed[index] = new;
+        }
+        let updated = commitment.update_coefficients(&pp, &updates).unwrap();
+        let changed = Polynomial::from_coefficients(changed);
+        let recommitted = KZG::commit(&pp, &changed).unwrap();
+        assert_eq!(updated, CommitmentPoint::from(&recommitted));
+
+        // an opening of the updated polynomial verifies against the updated commitment
+        let z = random_scalar();
+        let opening = recommitted.open_at(z).unwrap();
+        assert!(opening.verify_against(z, updated, pp.point_in_g2()));
+
+        assert_eq!(commitment.update_coefficients(&pp, &[]).unwrap(), commitment);
+        assert!(matches!(
+            commitment.update_coefficient(&pp, 65, Fr::default(), Fr::from_u64(1)),
+            Err(KZGErrors::PolynomialDegreeTooLarge { degree: 65, max_degree: 64 })
+        ));
+        assert!(matches!(
+            commitment.update_coefficients(&pp, &[(3, Fr::default(), Fr::from_u64(1)), (70, Fr::default(), Fr::from_u64(1))]),
+            Err(KZGErrors::BatchPolynomialDegreeTooLarge { index: 1, degree: 70, max_degree: 64 })
+        ));
+    }
+
+    #[test]
+    fn test_scaled_commitments() {
+        let pp = Arc::new(KZG::new(&[0x46u8; 32], 8).unwrap().public_parameter);
+        let f = KZG::commit_arc(Arc::clone(&pp), Polynomial::from(vec![5u64, 0, 3, 8])).unwrap();
+        let c = random_scalar();
+
+        // an opening of c * f verifies against c * com(f)
+        let scaled = &f * c;
+        let scaled_polynomial = f.polynomial.scale(c);
+        let expected = KZG::commit(pp.as_ref(), &scaled_polynomial).unwrap();
+        assert_eq!(CommitmentPoint::from(&scaled), CommitmentPoint::from(&expected));
+        assert_eq!(c * CommitmentPoint::from(&f), CommitmentPoint::from(&scaled));
+        assert_eq!(c * &f.as_commitment(), CommitmentPoint::from(&(c * &f)));
+        let z = random_scalar();
+        let opening = scaled.open_at(z).unwrap();
+        assert_eq!(opening.value, c * f.polynomial.evaluate(z));
+        assert!(opening.verify_against(z, (CommitmentPoint::from(&f) * c).0, pp.point_in_g2()));
+        assert!(opening.verify(&z, &scaled));
+        assert!(Arc::ptr_eq(&scaled.public_parameter, &pp));
+
+        // by zero the identity, by one nothing changes
+        assert_eq!(CommitmentPoint::from(&f) * Fr::default(), CommitmentPoint(P1::default()));
+        assert_eq!(CommitmentPoint::from(&(&f * Fr::default())), CommitmentPoint(P1::default()));
+        assert_eq!(CommitmentPoint::from(&f) * Fr::from_u64(1), CommitmentPoint::from(&f));
+        assert_eq!((&f * Fr::from_u64(1)).polynomial, f.polynomial);
+    }
 
-use crate::polynomial; // Important for generating Tau (during power of tau)
+    #[test]
+    fn test_cloned_opening_and_commitment_debug() {
+        let pp = KZG::new(&[0x52u8; 32], 500).unwrap().public_parameter;
+        let polynomial = Polynomial::from((0..400u64).collect::<Vec<_>>());
+        let commitment = KZG::commit(&pp, &polynomial).unwrap();
+        let z = Fr::from_u64(52);
+        let opening = commitment.open_at(z).unwrap();
+        let copies = vec![opening.clone(); 3];
+        for copy in &copies {
+            assert!(copy.verify(&z, &commitment));
+        }
 
-/// CURVE: BLS12-381 (G1, G2, GT)
+        let debug = format!("{:?}", commitment);
+        assert_eq!(
+            debug,
+            format!("Commitment {{ element: {}, degree: Some(399) }}", hex::encode(commitment.element.compress()))
+        );
+        assert!(format!("{:#?}", commitment).len() < 200);
+    }
 
+    #[test]
+    fn test_pp_digest() {
+        let pp = KZG::new(&[0x29u8; 32], 4).unwrap().public_parameter;
+        assert_eq!(hex::encode(pp.digest()), "99e15d08a3d01303454d010d1761040348444b5960c5946ce315e784aef49419");
+        assert_eq!(pp.short_id(), &hex::encode(pp.digest())[..16]);
 
+        // the digest only depends on the points, not on how they were computed or loaded
+        assert_eq!(PP::from_bytes(&pp.to_bytes()).unwrap().digest(), pp.digest());
+        assert_eq!(KZG::new(&[0x29u8; 32], 4).unwrap().public_parameter.digest(), pp.digest());
 
+        assert_ne!(pp.truncated(3).unwrap().digest(), pp.digest());
 
-/// [0, 1, 2, 3, 4, 5, 6] mod 7
-/// How many point exist in a elliptic curve group? --> `curve_order` 255bits
-/// e(input1, input2) -> output [bilinear pairing]
-/// `Scalar` is a wrapper around `BigUint` that ensures that the value is less than the curve order.
-/// `P1` is a point in G1, `P2` is a point in G2, `Fr` is a scalar.
+        let mut perturbed = pp.clone();
+        perturbed.points_in_g1[2] = perturbed.points_in_g1[2] + P1::generator();
+        assert_ne!(perturbed.digest(), pp.digest());
 
+        let mut perturbed = pp.clone();
+        perturbed.points_in_g2[1] = perturbed.points_in_g2[1] + P2::generator();
+        assert_ne!(perturbed.digest(), pp.digest());
+    }
 
+    #[test]
+    fn test_g2_powers() {
+        let tau = [25u8; 32];
+        let kzg = KZG::setup_with_g2_powers(&tau, 6, 4).unwrap();
+        let pp = &kzg.public_parameter;
+        assert_eq!(pp.points_in_g2.len(), 4);
+        assert!(pp.verify_structure().is_ok());
 
+        let tau_fr = scalar::fr_from_bytes_be(&tau);
+        let mut power = Fr::from_u64(1);
+        for i in 0..4 {
+            assert_eq!(pp.g2_power(i).unwrap().compress(), (power * P2::generator()).compress());
+            power *= tau_fr;
+        }
+        assert!(matches!(pp.g2_power(4), Err(KZGErrors::G2PowerOutOfRange { index: 4, available: 4 })));
 
+        // the default setup keeps exactly [1]_2 and [tau]_2, and the G1 side is unaffected
+        let default = KZG::new(&tau, 6).unwrap();
+        assert_eq!(default.public_parameter.points_in_g2.len(), DEFAULT_G2_POWERS);
+        assert!(matches!(default.public_parameter.g2_power(2), Err(KZGErrors::G2PowerOutOfRange { .. })));
+        assert_eq!(default.public_parameter.points_in_g1, pp.points_in_g1);
 
-/// Objective:
-/// 1. Define need data-stuctures for KZG (PP -> Public parameter, KZG, Commitment, Opening)
-/// 2. Implement a method to generate a public parameter for KZG
-/// 3. Implement a method to commit to a polynomial
-/// 4. Implement a method to open a polynomial
-/// 5. Implement a method for computing qoutent polynomial 
-/// 6. Implement a method for verifying commitment proofs
-/// 7. Test the implementation
+        // Opening::verify (through point_in_g2) agrees with a check against g2_power(1)
+        let polynomial = Polynomial::from(vec![4u64, 4, 0, 4]);
+        let commitment = KZG::commit(pp, &polynomial).unwrap();
+        let z = Fr::from_u64(19);
+        let opening = commitment.open_at(z).unwrap();
+        let explicit = check_opening(commitment.element, &z, &opening.value, opening.proof, pp.g2_power(1).unwrap());
+        assert!(opening.verify(&z, &commitment));
+        assert!(explicit);
 
+        let wrong = Fr::from_u64(20);
+        let explicit = check_opening(commitment.element, &wrong, &opening.value, opening.proof, pp.g2_power(1).unwrap());
+        assert_eq!(opening.verify(&wrong, &commitment), explicit);
 
+        let mut bytes_round_trip = PP::from_bytes(&pp.to_bytes()).unwrap();
+        assert_eq!(&bytes_round_trip, pp);
 
-#[derive(Clone, Debug, PartialEq)]
-pub struct PP {
-    /// Powers of Tau for P1 
-    pub points_in_g1: Vec<P1>,
-    /// Powers of Tau for P2
-    pub point_in_g2: P2 // g2 ^ tau
-}
+        // a G2 power off the progression
+        bytes_round_trip.points_in_g2[3] = P2::generator();
+        assert!(matches!(bytes_round_trip.verify_structure(), Err(KZGErrors::NotPowersOfTau)));
 
+        assert!(matches!(KZG::setup_with_g2_powers(&tau, 6, 1), Err(KZGErrors::NotEnoughG2Powers { found: 1 })));
+    }
 
-#[derive(Clone, Debug, PartialEq)]
-pub  struct KZG {
-    /// Shared Referenced String
-    pub public_parameter: PP
-}
+    #[test]
+    fn test_hex_api_with_reference_vector() {
+        // one of the EIP-4844 vectors from `test_opening`, driven purely through hex strings
+        let secret = hex::decode("0b598c0727a94e556b8c1dcb64af40daea6971901b5dcb8b49da2fe2b533a52e").unwrap();
+        let setup = KZG::new(secret.as_slice().try_into().unwrap(), 3).unwrap().public_parameter;
+        let polynomial = Polynomial::from(vec![1u64, 2, 2]);
+        let commitment = KZG::commit(&setup, &polynomial).unwrap();
 
-#[derive(Debug)]
-pub struct Commitment<'a> {
-    /// The commitment point 
-    pub element: P1,
-    /// The Polynomial committed to 
-    pub polynomial: &'a polynomial::Polynomial,
-    /// Public parameter used during the commitment process
-    pub public_parameter: &'a PP,
-}
+        let point_hex = format!("{:064x}", 15);
+        let opening = commitment.open_at_hex(&point_hex).unwrap();
+        let value_hex = format!("0x{:064x}", 481);
+        let commitment_hex = "b3e43da9f207cb9d717f85d40b967a28254b22bb6269b551aed50444eb1aed7f93a2b519acd7076e56451dc084389323";
+        let proof_hex = "b8cea544c0d68bf429533df6126a3f9a3ce9027595df4e7fc1e00a368f8b92690251434e51a9b53b35e8e9677960e0b1";
 
-#[derive(Debug)]
-pub struct Opening {
-    /// The value of the polynomial at the point
-    pub value: Fr,
-    /// This is the proof of an Evaluation
-    pub proof: P1,
-}
+        assert_eq!(hex::encode(opening.proof.compress()), proof_hex);
+        assert!(verify_at_hex(&setup, &point_hex, &value_hex, commitment_hex, proof_hex).unwrap());
 
+        // well-formed but wrong value
+        let wrong_value_hex = format!("{:064x}", 482);
+        assert!(!verify_at_hex(&setup, &point_hex, &wrong_value_hex, commitment_hex, proof_hex).unwrap());
 
-// ======================
-// CUSTOM DEFINED ERROR;
-// ======================
-#[derive(Debug)]
-pub enum KZGErrors {
-    SecretMustBeLessThanTheOrderOfTheGroup
-}
+        // a constant claimed to open to its negation, with the identity as proof
+        let (constant, forged) = forged_constant_opening(&setup, 5);
+        assert!(!verify_at_hex(
+            &setup,
+            &point_hex,
+            &hex::encode(forged.value.to_bytes_be()),
+            &hex::encode(constant.compress()),
+            &hex::encode(forged.proof.compress()),
+        )
+        .unwrap());
+
+        // malformed inputs
+        assert!(matches!(commitment.open_at_hex("zz"), Err(KZGErrors::PointDecodeError)));
+        assert!(matches!(commitment.open_at_hex("0f"), Err(KZGErrors::PointDecodeError)));
+        assert!(matches!(
+            verify_at_hex(&setup, &point_hex, &value_hex, &commitment_hex[2..], proof_hex),
+            Err(KZGErrors::PointDecodeError)
+        ));
+        assert!(matches!(
+            verify_at_hex(&setup, &point_hex, &value_hex, commitment_hex, &"00".repeat(48)),
+            Err(KZGErrors::PointDecodeError)
+        ));
+    }
+
+    #[test]
+    fn test_context_opening() {
+        let kzg = KZG::insecure_test_setup(6).unwrap();
+        let polynomial = Polynomial::from(vec![1u64, 1, 2, 3, 5, 8]);
+        let commitment = KZG::commit(&kzg.public_parameter, &polynomial).unwrap();
+        let z = Fr::from_u64(13);
 
+        let proof = commitment.open_at_with_context(z, b"session-1").unwrap();
+        assert_eq!(proof.opening.value, polynomial.evaluate(z));
+        assert!(proof.verify(&z, b"session-1", &commitment));
 
-impl KZG {
-    /// creating a new KZG instance (randomlly computing tau and generating the public parameter)
-    fn new(tau: &[u8; 32], degree: usize) -> Result<KZG, KZGErrors> {
-        KZG::setup_internal(tau, degree)
+        // replaying the proof in another context fails
+        assert!(!proof.verify(&z, b"session-2", &commitment));
+        assert!(!proof.verify(&z, b"", &commitment));
     }
 
-    /// this is the random generation function
-    fn new_rand(degree: usize) -> Result<KZG, KZGErrors> {
-        let mut rng = thread_rng();
+    #[test]
+    fn test_open_at_bytes() {
+        let kzg = KZG::insecure_test_setup(4).unwrap();
+        let polynomial = Polynomial::from(vec![3u64, 0, 1]);
+        let commitment = KZG::commit(&kzg.public_parameter, &polynomial).unwrap();
+
+        let z = Fr::from_u64(1000);
+        let opening = commitment.open_at_bytes(&z.to_bytes_be()).unwrap();
+        assert!(opening.verify(&z, &commitment));
+
+        assert!(matches!(commitment.open_at_bytes(&[0xff; 32]), Err(KZGErrors::NonCanonicalScalar)));
+    }
+
+    #[test]
+    fn test_verify_against_serialized_inputs() {
+        let kzg = KZG::new(&[6u8; 32], 8).unwrap();
+        let polynomial = Polynomial::from(vec![2u64, 7, 1, 8, 2, 8]);
+        let commitment = KZG::commit(&kzg.public_parameter, &polynomial).unwrap();
+        let z = Fr::from_u64(31337);
+        let opening = commitment.open_at(z).unwrap();
+
+        // what goes over the wire
+        let setup_bytes = kzg.public_parameter.to_bytes();
+        let commitment_bytes: [u8; 48] = commitment.element.compress().try_into().unwrap();
+        let proof_bytes: [u8; 48] = opening.proof.compress().try_into().unwrap();
+        let z_bytes = z.to_bytes_be();
+        let value_bytes = opening.value.to_bytes_be();
 
-        let mut secret = [0u8; 32];
-        rng.fill_bytes(&mut secret);
+        // the verifier side: no polynomial, no `Commitment`
+        let setup = PP::from_bytes(&setup_bytes).unwrap();
+        let received = Opening {
+            value: Fr::from_bytes_checked(&value_bytes).unwrap(),
+            proof: group::decompress_g1(&proof_bytes).unwrap(),
+        };
+        let commitment_point = group::decompress_g1(&commitment_bytes).unwrap();
+        let z = Fr::from_bytes_checked(&z_bytes).unwrap();
+
+        assert!(received.verify_against(z, commitment_point, setup.point_in_g2()));
+        assert!(!received.verify_against(z + Fr::from_u64(1), commitment_point, setup.point_in_g2()));
+        assert!(!received.verify_against(z, commitment_point, setup.g2_power(0).unwrap()));
 
-        let mut s = BigUint::from_bytes_be(&secret);
+        let wrong_value = Opening { value: received.value + Fr::from_u64(1), proof: received.proof };
+        assert!(!wrong_value.verify_against(z, commitment_point, setup.point_in_g2()));
 
-        let modulus :BigUint = curve_order();
-        while s >= modulus {
-            rng.fill_bytes(&mut secret);
-            s = BigUint::from_bytes_be(&secret);
+        // a constant claimed to open to its negation, with the identity as proof
+        for c in [1u64, 5, 1 << 40] {
+            let (constant, forged) = forged_constant_opening(&setup, c);
+            assert!(!forged.verify_against(z, constant, setup.point_in_g2()));
+            assert!(!forged.verify_against(z, CommitmentPoint(constant), setup.point_in_g2()));
         }
+    }
+
+    #[test]
+    fn test_zero_polynomial_round_trip() {
+        let kzg = KZG::new(&[0x30u8; 32], 8).unwrap();
+        let mut infinity = [0u8; 48];
+        infinity[0] = 0xc0;
+
+        for zero in [Polynomial::zero(), Polynomial::from(vec![0u64; 5])] {
+            let commitment = KZG::commit(&kzg.public_parameter, &zero).unwrap();
+            assert_eq!(commitment.to_bytes(), infinity);
+
+            let z = Fr::from_u64(8_675_309);
+            let opening = commitment.open_at(z).unwrap();
+            assert_eq!(opening.value, Fr::default());
+            assert_eq!(opening.proof.compress(), infinity);
+
+            let commitment_point = commitment_from_bytes(&commitment.to_bytes()).unwrap();
+            let received = Opening::from_bytes(&opening.to_bytes()).unwrap();
+            assert_eq!(received.value, Fr::default());
+            assert_eq!(commitment_point.compress(), infinity);
+            assert!(received.verify_against(z, commitment_point, kzg.public_parameter.point_in_g2()));
+            assert!(received.verify(&z, &commitment));
 
+            // the zero polynomial doesn't open to anything else
+            let wrong = Opening { value: Fr::from_u64(1), proof: received.proof };
+            assert!(!wrong.verify_against(z, commitment_point, kzg.public_parameter.point_in_g2()));
+        }
 
-        KZG::setup_internal(&secret, degree)
+        // the identity is also accepted through the hex layer
+        let point_hex = hex::encode(Fr::from_u64(5).to_bytes_be());
+        let zero_hex = hex::encode([0u8; 32]);
+        let infinity_hex = hex::encode(infinity);
+        assert!(verify_at_hex(&kzg.public_parameter, &point_hex, &zero_hex, &infinity_hex, &infinity_hex).unwrap());
     }
 
-    /// this function takes in tau and computes the powers of tau
-    fn setup_internal(tau: &[u8; 32], degree: usize) -> Result<KZG, KZGErrors> {
-        let modulus = curve_order();
-        let bytes_tau = BigUint::from_bytes_be(tau);
+    #[test]
+    fn test_zero_polynomial_short_circuits() {
+        let kzg = KZG::new(&[0x35u8; 32], 4).unwrap();
+        let identity = P1::default().compress();
 
+        for zero in [Polynomial::zero(), Polynomial::from(vec![0u64; 3]), Polynomial::from(vec![0u64; 40])] {
+            assert!(zero.is_zero());
+            // trailing zeros past the setup degree are still the zero polynomial
+            let commitment = KZG::commit(&kzg.public_parameter, &zero).unwrap();
+            assert_eq!(commitment.element.compress(), identity);
 
-        if bytes_tau > modulus {
-            return Err(KZGErrors::SecretMustBeLessThanTheOrderOfTheGroup);
+            for z in [Fr::default(), Fr::from_u64(1), Fr::from_u64(77)] {
+                let opening = commitment.open_at(z).unwrap();
+                assert_eq!(opening.value, Fr::default());
+                assert_eq!(opening.proof.compress(), identity);
+                assert!(opening.verify(&z, &commitment));
+            }
         }
+    }
+
+    #[test]
+    fn test_open_at_with_quotient() {
+        let pp = KZG::new(&[0x72u8; 32], 10).unwrap().public_parameter;
+        let polynomial = Polynomial::from(vec![4u64, 8, 15, 16, 23, 42]);
+        let commitment = KZG::commit(&pp, &polynomial).unwrap();
+        let z = Fr::from_u64(72);
 
-        let mut points_in_g1 = vec![];
+        let (opening, quotient) = commitment.open_at_with_quotient(z).unwrap();
+        let opened = commitment.open_at(z).unwrap();
+        assert_eq!(opening.value, opened.value);
+        assert_eq!(opening.proof.compress(), opened.proof.compress());
 
-        // obtaining the generator in the first group (this is the cyclic group)
-        let g1 = P1::generator();
+        // q(x) * (x - z) + f(z) == f(x)
+        let divisor = Polynomial::from_coefficients(vec![-z, Fr::from_u64(1)]);
+        assert_eq!((&quotient * &divisor).sub_constant(-opening.value), polynomial);
 
-        // obtaining the "power of tau" (a part of the public parameter)
-        for i in 0..=degree {
-            let i_as_bigint = BigUint::from_slice(&[i as u32]);
-            let s_i_as_bigint = bytes_tau.modpow(&i_as_bigint, &modulus);
+        // the proof is the commitment to the returned quotient, which can itself be opened
+        let quotient_commitment = KZG::commit(&pp, &quotient).unwrap();
+        assert_eq!(quotient_commitment.element.compress(), opening.proof.compress());
+        let w = Fr::from_u64(27);
+        assert!(quotient_commitment.open_at(w).unwrap().verify(&w, &quotient_commitment));
 
-            let mut s_i_bytes = vec![0u8; 32];
-            let raw_bytes = s_i_as_bigint.to_bytes_be();
-            s_i_bytes[32 - raw_bytes.len()..].copy_from_slice(&raw_bytes);
-            let s_i_scalar = Scalar::from_fr_bytes(&s_i_bytes);
+        let zero = Polynomial::zero();
+        let (_, quotient) = KZG::commit(&pp, &zero).unwrap().open_at_with_quotient(z).unwrap();
+        assert!(quotient.is_zero());
+    }
 
-            let result = s_i_scalar * g1;
-            points_in_g1.push(result);
-        }
+    #[test]
+    fn test_zero_and_constant_polynomials_end_to_end() {
+        let pp = KZG::new(&[0x71u8; 32], 6).unwrap().public_parameter;
+        let identity = P1::default().compress();
+        let z = Fr::from_u64(7171);
 
+        // the empty coefficient vector is the zero polynomial
+        let empty = Polynomial::from_coefficients(vec![]);
+        let commitment = KZG::commit(&pp, &empty).unwrap();
+        assert_eq!(commitment.element.compress(), identity);
+        let opening = commitment.open_at(z).unwrap();
+        assert_eq!(opening.value, Fr::default());
+        assert_eq!(opening.proof.compress(), identity);
+        assert!(opening.verify(&z, &commitment));
 
-        let scalar = Scalar::from_fr_bytes(tau);
-        let result_in_g2 = scalar * P2::generator();
+        for c in [1u64, 11, 1 << 40] {
+            let constant = Polynomial::constant(Fr::from_u64(c));
+            let commitment = KZG::commit(&pp, &constant).unwrap();
+            assert_eq!(commitment.element.compress(), (Fr::from_u64(c) * P1::generator()).compress());
 
-        let public_parameter = PP {
-            points_in_g1,
-            point_in_g2: result_in_g2,
-        };
+            for z in [Fr::default(), Fr::from_u64(1), z] {
+                let opening = commitment.open_at(z).unwrap();
+                assert_eq!(opening.value, Fr::from_u64(c));
+                assert_eq!(opening.proof.compress(), identity);
+                assert!(opening.verify(&z, &commitment));
+                assert!(opening.verify_detailed(&z, &commitment).unwrap());
 
-        Ok(
-            KZG {
-                public_parameter
+                // an identity proof only fits the constant's own value
+                let wrong_value = Opening { value: Fr::from_u64(c + 1), ..opening.clone() };
+                assert!(!wrong_value.verify(&z, &commitment));
+                // and a non-identity proof doesn't fit a constant
+                let tampered = Opening { proof: P1::generator(), ..opening };
+                assert!(!tampered.verify(&z, &commitment));
             }
+        }
+
+        // a tampered identity proof for the zero polynomial
+        let zero = Polynomial::zero();
+        let commitment = KZG::commit(&pp, &zero).unwrap();
+        let tampered = Opening { value: Fr::default(), proof: pp.points_in_g1[1] };
+        assert!(!tampered.verify(&z, &commitment));
+    }
+
+    #[test]
+    fn test_opening_bytes_round_trip() {
+        let kzg = KZG::new(&[0x31u8; 32], 8).unwrap();
+        let polynomial = Polynomial::from(vec![4u64, 8, 15, 16, 23, 42]);
+        let commitment = KZG::commit(&kzg.public_parameter, &polynomial).unwrap();
+        let z = Fr::from_u64(99);
+        let opening = commitment.open_at(z).unwrap();
+
+        let bytes = opening.to_bytes();
+        let restored = Opening::from_bytes(&bytes).unwrap();
+        assert_eq!(restored.value, opening.value);
+        assert_eq!(restored.proof.compress(), opening.proof.compress());
+        let commitment_point = commitment_from_bytes(&commitment.to_bytes()).unwrap();
+        assert!(restored.verify_against(z, commitment_point, kzg.public_parameter.point_in_g2()));
+
+        let mut bad_value = bytes;
+        bad_value[..32].copy_from_slice(&[0xff; 32]);
+        assert!(matches!(Opening::from_bytes(&bad_value), Err(KZGErrors::NonCanonicalScalar)));
+
+        let mut bad_proof = bytes;
+        bad_proof[32] &= 0x7f;
+        assert!(matches!(Opening::from_bytes(&bad_proof), Err(KZGErrors::PointDecodeError)));
+    }
+
+    #[test]
+    fn test_compute_quotient_edge_cases() {
+        let f = Polynomial::from(vec![6u64, 11, 6, 1]);
+        let empty = Polynomial::from_coefficients(vec![]);
+        assert!(matches!(compute_quotient(&empty, &f), Err(KZGErrors::EmptyPolynomial)));
+        assert!(matches!(compute_quotient(&f, &empty), Err(KZGErrors::EmptyPolynomial)));
+        assert!(matches!(compute_quotient(&f, &Polynomial::from(vec![0u64, 0])), Err(KZGErrors::EmptyPolynomial)));
+
+        // a dividend of lower degree is all remainder
+        let (quotient, remainder) = compute_quotient(&Polynomial::from(vec![4u64, 5]), &f).unwrap();
+        assert!(quotient.is_zero());
+        assert_eq!(remainder, vec![Fr::from_u64(4), Fr::from_u64(5), Fr::default()]);
+
+        // (x + 1)(x + 2)(x + 3) by x + 2, by a constant, and by a divisor with a trailing zero
+        let (quotient, remainder) = compute_quotient(&f, &Polynomial::from(vec![2u64, 1])).unwrap();
+        assert_eq!(quotient, Polynomial::from(vec![3u64, 4, 1]));
+        assert_eq!(remainder, vec![Fr::default()]);
+        let (quotient, remainder) = compute_quotient(&f, &Polynomial::from(vec![2u64])).unwrap();
+        assert_eq!(quotient.scale(Fr::from_u64(2)), f);
+        assert!(remainder.is_empty());
+        let (quotient, remainder) = compute_quotient(&f, &Polynomial::from(vec![1u64, 1, 0])).unwrap();
+        assert_eq!(quotient, Polynomial::from(vec![6u64, 5, 1]));
+        assert_eq!(remainder, vec![Fr::default()]);
+    }
+
+    #[test]
+    fn test_wire_points() {
+        let pp = KZG::new(&[0x37u8; 32], 8).unwrap().public_parameter;
+        let polynomial = Polynomial::from(vec![3u64, 1, 4, 1, 5]);
+        let commitment = KZG::commit(&pp, &polynomial).unwrap();
+        let z = Fr::from_u64(26);
+        let opening = commitment.open_at(z).unwrap();
+
+        // round trips, and verification from the parsed points
+        let commitment_point = CommitmentPoint::from_bytes(&commitment.to_bytes()).unwrap();
+        assert_eq!(commitment_point, CommitmentPoint::from(&commitment));
+        assert_eq!(commitment_point.to_bytes(), commitment.to_bytes());
+        let proof_bytes: [u8; 48] = opening.proof.compress().try_into().unwrap();
+        let proof = ProofPoint::from_slice(&proof_bytes).unwrap();
+        assert_eq!(proof.to_bytes(), proof_bytes);
+        let received = Opening::new(opening.value, proof);
+        assert!(received.verify_against(z, commitment_point, pp.point_in_g2()));
+        assert!(!received.verify_against(z + Fr::from_u64(1), commitment_point, pp.point_in_g2()));
+
+        // the identity is the commitment to the zero polynomial
+        let mut identity = [0u8; 48];
+        identity[0] = 0xc0;
+        assert_eq!(CommitmentPoint::from_bytes(&identity).unwrap(), CommitmentPoint(P1::default()));
+        assert_eq!(CommitmentPoint(P1::default()).to_bytes(), identity);
+
+        // each way of being malformed has its own error
+        assert!(matches!(
+            CommitmentPoint::from_bytes(&group::non_subgroup_g1()),
+            Err(KZGErrors::PointNotInSubgroup { index: 0 })
+        ));
+        let mut modulus: [u8; 48] = hex::decode(
+            "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab",
         )
+        .unwrap()
+        .try_into()
+        .unwrap();
+        modulus[0] |= 0x80;
+        assert!(matches!(CommitmentPoint::from_bytes(&modulus), Err(KZGErrors::NonCanonicalPoint { index: 0 })));
+        let mut bad_identity = identity;
+        bad_identity[47] = 1;
+        assert!(matches!(ProofPoint::from_bytes(&bad_identity), Err(KZGErrors::InvalidPoint { index: 0 })));
+        assert!(matches!(CommitmentPoint::from_slice(&proof_bytes[..47]), Err(KZGErrors::TruncatedInput)));
+        assert!(matches!(ProofPoint::from_slice(&[]), Err(KZGErrors::TruncatedInput)));
+        assert!(matches!(ProofPoint::from_slice(&[proof_bytes.as_slice(), &[0]].concat()), Err(KZGErrors::TrailingBytes)));
     }
 
-    /// this function takes in a public parameter and a polynomial and returns a commitment, this commitment is this struct is a point on the G1 curve
-    pub fn commit<'a>(
-        public_parameter: &'a PP,
-        polynomial: &'a polynomial::Polynomial,
-    ) -> Result<Commitment<'a>, KZGErrors> {
-        let basis = &public_parameter.points_in_g1;
-        let coefficients = &polynomial.coefficients;
+    #[test]
+    fn test_delta_opening() {
+        let kzg = KZG::new(&[0x34u8; 32], 12).unwrap();
+        let old = Polynomial::from(vec![5u64, 3, 9, 0, 2, 7, 1, 4]);
+        let mut updated_coefficients = old.coefficients.clone();
+        updated_coefficients[1] = Fr::from_u64(10);
+        updated_coefficients[6] = Fr::from_u64(0);
+        updated_coefficients.push(Fr::from_u64(6));
+        let new = Polynomial::from_coefficients(updated_coefficients);
+
+        let old_commitment = KZG::commit(&kzg.public_parameter, &old).unwrap();
+        let new_commitment = KZG::commit(&kzg.public_parameter, &new).unwrap();
+        let g2_tau = kzg.public_parameter.point_in_g2();
+
+        let z = Fr::from_u64(3);
+        let delta = new_commitment.open_delta(&old_commitment, z).unwrap();
+        // 7 z - 1 z^6 + 6 z^8 at z = 3
+        assert_eq!(delta.value, new.evaluate(z) - old.evaluate(z));
+        assert_eq!(delta.value, Fr::from_u64(7 * 3 + 6 * 6561) - Fr::from_u64(729));
+        assert!(delta.verify_delta(z, new_commitment.element, old_commitment.element, g2_tau));
+
+        // the difference only verifies in the direction it was computed, and at its own point
+        assert!(!delta.verify_delta(z, old_commitment.element, new_commitment.element, g2_tau));
+        assert!(!delta.verify_delta(z + Fr::from_u64(1), new_commitment.element, old_commitment.element, g2_tau));
 
-        let mut result = P1::default();
-        for (coefficient, element) in coefficients.iter().zip(basis.iter()) {
-            let term = *coefficient * *element;
-            result = result + term;
+        // an unchanged polynomial has a zero delta
+        let unchanged = old_commitment.open_delta(&old_commitment, z).unwrap();
+        assert_eq!(unchanged.value, Fr::default());
+        assert!(unchanged.verify_delta(z, old_commitment.element, old_commitment.element, g2_tau));
+
+        // a negated polynomial, where `C_new - C_old` doubles `C_new`
+        let negated = scaled(&old, -Fr::from_u64(1));
+        let negated_commitment = KZG::commit(&kzg.public_parameter, &negated).unwrap();
+        let delta = negated_commitment.open_delta(&old_commitment, z).unwrap();
+        assert!(delta.verify_delta(z, negated_commitment.element, old_commitment.element, g2_tau));
+    }
+
+    #[test]
+    fn test_cached_verifier_agrees_with_verify() {
+        let kzg = KZG::new(&[0x36u8; 32], 10).unwrap();
+        let other = KZG::new(&[0x37u8; 32], 10).unwrap();
+        let verifier = Verifier::new(&kzg.public_parameter);
+        let polynomials = [
+            Polynomial::from(vec![2u64, 7, 1, 8]),
+            Polynomial::from(vec![0u64, 0, 0, 0, 0, 0, 0, 0, 0, 0, 9]),
+            Polynomial::zero(),
+        ];
+
+        for (i, polynomial) in polynomials.iter().enumerate() {
+            let commitment = KZG::commit(&kzg.public_parameter, polynomial).unwrap();
+            let foreign = KZG::commit(&other.public_parameter, polynomial).unwrap();
+            let z = Fr::from_u64(i as u64 + 11);
+            let opening = commitment.open_at(z).unwrap();
+            let wrong_point = z + Fr::from_u64(1);
+
+            for (z, commitment) in [(z, &commitment), (wrong_point, &commitment), (z, &foreign)] {
+                assert_eq!(verifier.verify(&opening, &z, &commitment.element), opening.verify(&z, commitment));
+            }
+            assert!(verifier.verify(&opening, &z, &commitment.element));
         }
 
-        Ok(Commitment {
-            element: result,
-            polynomial,
-            public_parameter: &public_parameter,
-        })
+        // values and points past the low windows of the tables
+        let polynomial = Polynomial::from_coefficients((0..8).map(|_| random_scalar()).collect());
+        let commitment = KZG::commit(&kzg.public_parameter, &polynomial).unwrap();
+        let z = random_scalar();
+        let opening = commitment.open_at(z).unwrap();
+        assert!(verifier.verify(&opening, &z, &commitment.element));
+        assert!(!verifier.verify(&opening, &(z + Fr::from_u64(1)), &commitment.element));
+
+        // the differences double for a constant claimed to open to its negation, and at `z = -tau`
+        let (constant_commitment, forged) = forged_constant_opening(&kzg.public_parameter, 3);
+        assert!(!verifier.verify(&forged, &z, &constant_commitment));
+        let minus_tau = -scalar::fr_from_bytes_be(&[0x36u8; 32]);
+        let opening = commitment.open_at(minus_tau).unwrap();
+        assert!(verifier.verify(&opening, &minus_tau, &commitment.element));
     }
-}
 
+    #[test]
+    fn test_non_membership() {
+        let kzg = KZG::insecure_test_setup(4).unwrap();
+        let polynomial = Polynomial::from(vec![3u64, 0, 1]);
+        let commitment = KZG::commit(&kzg.public_parameter, &polynomial).unwrap();
+
+        // f(2) = 7, so claiming 8 must be provably wrong
+        let z = Fr::from_u64(2);
+        let (opening, y) = commitment.open_not_equal(z, Fr::from_u64(8)).unwrap();
+        assert_eq!(opening.value, Fr::from_u64(7));
+        assert!(opening.verify_not_equal(&z, &y, &commitment));
 
-impl<'a> Commitment<'a> {
-    /// this function takes in a point and returns an opening, this opening is a struct that contains the value of the polynomial at the point and the proof of the evaluation  
-    pub fn open_at(self: &Self, point: Fr) -> Result<Opening, KZGErrors> {
-        let result = self.polynomial.evalaute(point);
+        // the revealed value itself cannot be claimed as "not equal"
+        assert!(!opening.verify_not_equal(&z, &opening.value, &commitment));
+        assert!(matches!(
+            commitment.open_not_equal(z, Fr::from_u64(7)),
+            Err(KZGErrors::EvaluationEqualsClaimedValue)
+        ));
+    }
 
-        // divisor `s - x` for `f(x) = y`
-        let divisor_coefficients = vec![-point, Fr::from_u64(1)];
-        let divisor = polynomial::Polynomial::from_coefficients(divisor_coefficients);
-        let quotient_polynomial = compute_quotient(self.polynomial, &divisor);
+    #[test]
+    fn test_same_point_batch() {
+        let kzg = KZG::new(&[9u8; 32], 6).unwrap();
+        let polynomials = [
+            Polynomial::from(vec![1u64, 2, 3]),
+            Polynomial::from(vec![7u64, 0, 0, 0, 0, 5]),
+            Polynomial::from(vec![4u64, 4, 4, 4]),
+        ];
+        let z = Fr::from_u64(17);
 
-        let commitment = KZG::commit(self.public_parameter, &quotient_polynomial)?;
+        let commitments = polynomials
+            .iter()
+            .map(|p| KZG::commit(&kzg.public_parameter, p).unwrap())
+            .collect::<Vec<_>>();
+        let openings = commitments.iter().map(|c| c.open_at(z).unwrap()).collect::<Vec<_>>();
+        let values = openings.iter().map(|o| o.value).collect::<Vec<_>>();
 
-        Ok(Opening {
-            value: result,
-            proof: commitment.element,
-        })
+        // agrees with verifying one by one
+        for (opening, commitment) in openings.iter().zip(&commitments) {
+            assert!(opening.verify(&z, commitment));
+        }
+        assert!(verify_same_point_batch(&openings, &values, &commitments, z).unwrap());
+
+        // a single wrong value breaks the batch
+        let mut wrong_values = values.clone();
+        wrong_values[1] += Fr::from_u64(1);
+        assert!(!verify_same_point_batch(&openings, &wrong_values, &commitments, z).unwrap());
+
+        // so does a proof for a different point
+        let mut wrong_openings = commitments.iter().map(|c| c.open_at(z).unwrap()).collect::<Vec<_>>();
+        wrong_openings[2] = commitments[2].open_at(Fr::from_u64(18)).unwrap();
+        assert!(!verify_same_point_batch(&wrong_openings, &values, &commitments, z).unwrap());
+
+        assert!(!verify_same_point_batch::<PP>(&[], &[], &[], z).unwrap());
+
+        // a commitment from another setup is refused, one from a smaller slice of the same setup
+        // isn't
+        let other = KZG::new(&[10u8; 32], 6).unwrap();
+        let mut mixed = polynomials
+            .iter()
+            .map(|p| KZG::commit(&kzg.public_parameter, p).unwrap())
+            .collect::<Vec<_>>();
+        mixed[2] = KZG::commit(&other.public_parameter, &polynomials[2]).unwrap();
+        let mixed_openings = mixed.iter().map(|c| c.open_at(z).unwrap()).collect::<Vec<_>>();
+        assert!(matches!(
+            verify_same_point_batch(&mixed_openings, &values, &mixed, z),
+            Err(KZGErrors::BatchSetupMismatch { index: 2 })
+        ));
+
+        let truncated = kzg.public_parameter.truncated(5).unwrap();
+        let mut prefixed = polynomials
+            .iter()
+            .map(|p| KZG::commit(&kzg.public_parameter, p).unwrap())
+            .collect::<Vec<_>>();
+        prefixed[0] = KZG::commit(&truncated, &polynomials[0]).unwrap();
+        assert!(verify_same_point_batch(&openings, &values, &prefixed, z).unwrap());
+
+        // a constant claimed to open to its negation: the combined difference doubles
+        let (_, forged) = forged_constant_opening(&kzg.public_parameter, 8);
+        let constant = Polynomial::from(vec![8u64]);
+        let constant_commitment = [KZG::commit(&kzg.public_parameter, &constant).unwrap()];
+        let forged_value = forged.value;
+        assert!(!verify_same_point_batch(&[forged], &[forged_value], &constant_commitment, z).unwrap());
+
+        // honest openings at -s, where [s - z]_2 doubles
+        let minus_s = -scalar::fr_from_bytes_be(&[9u8; 32]);
+        let openings = commitments.iter().map(|c| c.open_at(minus_s).unwrap()).collect::<Vec<_>>();
+        let values = openings.iter().map(|o| o.value).collect::<Vec<_>>();
+        assert!(verify_same_point_batch(&openings, &values, &commitments, minus_s).unwrap());
     }
-}
 
+    #[test]
+    fn test_folded_proofs() {
+        let kzg = KZG::insecure_test_setup(10).unwrap();
+        let polynomials = [
+            Polynomial::from(vec![1u64, 2, 3]),
+            Polynomial::from(vec![7u64, 0, 0, 0, 0, 5, 0, 0, 0, 0, 1]),
+            Polynomial::from(vec![4u64, 4, 4, 4]),
+            Polynomial::from(vec![9u64]),
+        ];
+        let points = [17u64, 18, 17, 1000].map(Fr::from_u64);
 
-// ===================================
-// FREE FUNCTIONS
-// ===================================
-/// This is a simple function for dividing a polynomial and returning the q
-fn compute_quotient(
-    dividend: &polynomial::Polynomial,
-    divisor: &polynomial::Polynomial,
-) -> polynomial::Polynomial {
-    let mut dividend = dividend.coefficients.clone();
-    let mut coefficients = vec![];
+        let commitments = polynomials
+            .iter()
+            .map(|p| KZG::commit(&kzg.public_parameter, p).unwrap())
+            .collect::<Vec<_>>();
+        let open_all = || {
+            commitments
+                .iter()
+                .zip(&points)
+                .map(|(c, z)| c.open_at(*z).unwrap())
+                .collect::<Vec<_>>()
+        };
+        let openings = open_all();
 
-    let mut dividend_pos = dividend.len() - 1;
-    let divisor_pos = divisor.coefficients.len() - 1;
-    let mut difference = dividend_pos as isize - divisor_pos as isize;
+        let folded = fold_proofs(&openings, &points, &commitments).unwrap();
+        assert_eq!(folded.values, openings.iter().map(|o| o.value).collect::<Vec<_>>());
+        assert!(folded.verify(&points, &commitments).unwrap());
 
-    while difference >= 0 {
-        let term_quotient = dividend[dividend_pos] / divisor.coefficients[divisor_pos];
-        coefficients.push(term_quotient);
+        // tampering with any single proof or value breaks the folded proof
+        for i in 0..openings.len() {
+            let mut wrong_proof = open_all();
+            wrong_proof[i].proof = wrong_proof[i].proof + P1::generator();
+            assert!(!fold_proofs(&wrong_proof, &points, &commitments).unwrap().verify(&points, &commitments).unwrap());
 
-        for i in (0..=divisor_pos).rev() {
-            let difference = difference as usize;
-            let x = divisor.coefficients[i];
-            let y = x * term_quotient;
-            let z = dividend[difference + i];
-            dividend[difference + i] = z - y;
+            let mut wrong_value = open_all();
+            wrong_value[i].value += Fr::from_u64(1);
+            assert!(!fold_proofs(&wrong_value, &points, &commitments).unwrap().verify(&points, &commitments).unwrap());
         }
 
-        dividend_pos -= 1;
-        difference -= 1;
+        // so does checking it at other points, or with edited values or challenge
+        let mut other_points = points;
+        other_points[1] = Fr::from_u64(19);
+        assert!(!folded.verify(&other_points, &commitments).unwrap());
+        let mut edited = folded.clone();
+        edited.values[2] += Fr::from_u64(1);
+        assert!(!edited.verify(&points, &commitments).unwrap());
+        let mut edited = folded.clone();
+        edited.challenge += Fr::from_u64(1);
+        assert!(!edited.verify(&points, &commitments).unwrap());
+
+        assert!(matches!(
+            fold_proofs(&openings[..3], &points, &commitments),
+            Err(KZGErrors::MismatchedBatchLengths { openings: 3, values: 4, commitments: 4 })
+        ));
+        assert!(matches!(
+            folded.verify(&points[..2], &commitments),
+            Err(KZGErrors::MismatchedBatchLengths { openings: 4, values: 2, commitments: 4 })
+        ));
+        let empty = fold_proofs::<PP>(&[], &[], &[]).unwrap();
+        assert!(!empty.verify::<PP>(&[], &[]).unwrap());
+
+        // a constant claimed to open to its negation, with identity points for both proofs: the
+        // weighted C - [y]_1 doubles
+        let constant = [KZG::commit(&kzg.public_parameter, &polynomials[3]).unwrap()];
+        let (_, forged) = forged_constant_opening(&kzg.public_parameter, 9);
+        let values = vec![forged.value];
+        let forged = FoldedProof {
+            proof: P1::default(),
+            quotient: P1::default(),
+            challenge: folding_transcript(&points[3..], &values, &constant).challenge(),
+            values,
+        };
+        assert!(!forged.verify(&points[3..], &constant).unwrap());
     }
 
-    coefficients.reverse();
-    polynomial::Polynomial { coefficients }
-}
+    #[test]
+    fn test_batch_rejects_mismatched_lengths() {
+        let kzg = KZG::new(&[10u8; 32], 4).unwrap();
+        let polynomials = [Polynomial::from(vec![1u64, 2]), Polynomial::from(vec![3u64, 4, 5])];
+        let z = Fr::from_u64(3);
+        let commitments = polynomials
+            .iter()
+            .map(|p| KZG::commit(&kzg.public_parameter, p).unwrap())
+            .collect::<Vec<_>>();
+        let openings = commitments.iter().map(|c| c.open_at(z).unwrap()).collect::<Vec<_>>();
+        let values = openings.iter().map(|o| o.value).collect::<Vec<_>>();
 
-impl Opening {
-    /// this function takes in an input and a commitment and returns a boolean value, this boolean value is true if the proof is valid and false otherwise
-    pub fn verify(&self, input: &Fr, commitment: &Commitment) -> bool {
-        // Compute [f(s) - y]_1 for LHS
-        let y_p1 = self.value * P1::generator();
-        let commitment_minus_y = commitment.element + -y_p1;
+        assert!(matches!(
+            verify_same_point_batch(&openings[..1], &values, &commitments, z),
+            Err(KZGErrors::MismatchedBatchLengths { openings: 1, values: 2, commitments: 2 })
+        ));
+        assert!(matches!(
+            verify_same_point_batch(&openings, &values[..1], &commitments, z),
+            Err(KZGErrors::MismatchedBatchLengths { openings: 2, values: 1, commitments: 2 })
+        ));
+        assert!(matches!(
+            verify_same_point_batch(&openings, &values, &commitments[..0], z),
+            Err(KZGErrors::MismatchedBatchLengths { openings: 2, values: 2, commitments: 0 })
+        ));
+    }
 
-        // Compute [s - z]_2 for RHS
-        let z_p2 = *input * P2::generator();
-        let s_minus_z = commitment.public_parameter.point_in_g2 + -z_p2;
+    /// the original setup: a fresh `modpow` and a 32-byte padding round trip for every power
+    fn reference_powers_of_tau(tau: &[u8; 32], degree: usize) -> Vec<Scalar> {
+        let modulus = curve_order();
+        let tau = BigUint::from_bytes_be(tau);
 
-        verify_pairings(commitment_minus_y, P2::generator(), self.proof, s_minus_z)
+        (0..=degree)
+            .map(|i| {
+                let power = tau.modpow(&BigUint::from(i), &modulus).to_bytes_be();
+                let mut bytes = [0u8; 32];
+                bytes[32 - power.len()..].copy_from_slice(&power);
+                Scalar::from_fr_bytes(&bytes)
+            })
+            .collect()
     }
-}
 
+    fn reference_g1_powers(tau: &[u8; 32], degree: usize) -> Vec<P1> {
+        reference_powers_of_tau(tau, degree)
+            .into_iter()
+            .map(|power| power * P1::generator())
+            .collect()
+    }
 
+    #[test]
+    fn test_incremental_powers_match_modpow() {
+        let tau = [0x2au8; 32];
+        let kzg = KZG::new(&tau, 100).unwrap();
+        let reference = reference_g1_powers(&tau, 100);
 
+        assert_eq!(kzg.public_parameter.points_in_g1.len(), reference.len());
+        for (point, expected) in kzg.public_parameter.points_in_g1.iter().zip(&reference) {
+            assert_eq!(point.compress(), expected.compress());
+        }
+        assert_eq!(
+            kzg.public_parameter.point_in_g2().compress(),
+            (Scalar::from_fr_bytes(&tau) * P2::generator()).compress()
+        );
+    }
 
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_setup_matches_serial() {
+        let degree = 512;
+        let tau = [21u8; 32];
+        let kzg = KZG::new(&tau, degree).unwrap();
+        let serial = g1_powers_serial(&powers_of_tau(scalar::fr_from_bytes_be(&tau), degree));
 
+        assert_eq!(kzg.public_parameter.points_in_g1.len(), serial.len());
+        for (parallel, serial) in kzg.public_parameter.points_in_g1.iter().zip(&serial) {
+            assert_eq!(parallel.compress(), serial.compress());
+        }
+    }
 
+    #[test]
+    fn test_setup_with_progress() {
+        let tau = [0x2bu8; 32];
+        let degree = 2 * SETUP_PROGRESS_INTERVAL + 10;
 
+        let mut reports = Vec::new();
+        let kzg = KZG::setup_with_progress(&tau, degree, |report| {
+            reports.push(report);
+            ControlFlow::Continue(())
+        })
+        .unwrap();
 
+        let total = degree + 1;
+        let computed: Vec<usize> = reports.iter().map(|report| report.computed).collect();
+        assert_eq!(computed, vec![SETUP_PROGRESS_INTERVAL, 2 * SETUP_PROGRESS_INTERVAL, total]);
+        assert!(reports.iter().all(|report| report.total == total));
 
+        let expected = KZG::setup(&tau, degree).unwrap();
+        assert_eq!(kzg.public_parameter.to_bytes(), expected.public_parameter.to_bytes());
 
+        // cancelled halfway: no setup comes back, and nothing is computed after the break
+        let mut calls = 0;
+        let cancelled = KZG::setup_with_progress(&tau, degree, |report| {
+            calls += 1;
+            if report.computed * 2 >= report.total {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert!(matches!(cancelled, Err(KZGErrors::Cancelled)));
+        assert_eq!(calls, 2);
 
-/// This this is a sample test from Ethereum SPECS for EIP4844
-#[cfg(test)]
-mod tests {
-    use crate::polynomial::Polynomial;
+        // invalid secrets are rejected before any progress is reported
+        let rejected = KZG::setup_with_progress(&[0u8; 32], degree, |_| panic!("no progress expected"));
+        assert!(matches!(rejected, Err(KZGErrors::SecretMustBeNonZero)));
+    }
 
-    use super::*;
+    #[test]
+    fn test_new_rand_with_tau_is_reproducible() {
+        let (kzg, tau) = KZG::new_rand_with_tau(6).unwrap();
+        let reproduced = KZG::new(&tau, 6).unwrap();
+
+        assert_eq!(kzg.public_parameter, reproduced.public_parameter);
+    }
+
+    #[test]
+    fn test_setup_rejects_curve_order() {
+        let result = KZG::new(&curve_order_bytes(), 4);
+        assert!(matches!(result, Err(KZGErrors::SecretMustBeLessThanTheOrderOfTheGroup)));
+    }
+
+    #[test]
+    fn test_setup_rejects_zero() {
+        let result = KZG::new(&[0u8; 32], 4);
+        assert!(matches!(result, Err(KZGErrors::SecretMustBeNonZero)));
+    }
+
+    #[test]
+    fn test_setup_accepts_order_minus_one() {
+        let mut tau = curve_order_bytes();
+        tau[31] -= 1;
+
+        let kzg = KZG::new(&tau, 4).unwrap();
+        assert_eq!(kzg.degree(), 4);
+    }
+
+    #[test]
+    fn test_sampled_secret_is_wrapped() {
+        // the rejection loop runs twice and the result still comes back in the wiped-on-drop wrapper
+        let mut rng = ScriptedRng { bad_byte: 0xff, bad_draws: 2, draws: 0 };
+        let secret: SecretBytes = sample_secret(&mut rng);
+
+        assert_eq!(rng.draws, 3);
+        assert_eq!(*secret, [0x11u8; 32]);
+    }
+
+    #[test]
+    fn test_new_rand_never_produces_degenerate_setup() {
+        // an all-zero draw must be rejected like an over-order one
+        let mut rng = ScriptedRng { bad_byte: 0x00, bad_draws: 2, draws: 0 };
+        let kzg = KZG::new_rand_with_rng(&mut rng, 3).unwrap();
+        assert_eq!(rng.draws, 3);
+
+        let identity = P1::default().compress();
+        for point in &kzg.public_parameter.points_in_g1 {
+            assert_ne!(point.compress(), identity);
+        }
+    }
+
+    #[test]
+    fn test_insecure_setup_from_seed() {
+        let first = KZG::insecure_setup_from_seed(b"fixture", 8).unwrap();
+        assert_eq!(first, KZG::insecure_setup_from_seed(b"fixture", 8).unwrap());
+        assert!(first.public_parameter.verify_structure().is_ok());
+        assert_ne!(first, KZG::insecure_setup_from_seed(b"fixture-2", 8).unwrap());
+        assert_ne!(first, KZG::insecure_setup_from_seed(b"", 8).unwrap());
+
+        // pinned, so a change to the derivation shows up here
+        assert_eq!(
+            hex::encode(first.public_parameter.digest()),
+            "51977dd608cb3f52e55c2d89d13e1cd55afcf0423a0ff2fd5f38ce1b1fbe1b57"
+        );
+
+        // the fixed-seed setup is the seeded one, cut to any degree
+        let test_setup = KZG::insecure_test_setup(6).unwrap();
+        assert_eq!(test_setup, KZG::insecure_setup_from_seed(INSECURE_TEST_SEED, 6).unwrap());
+        let longer = KZG::insecure_test_setup(12).unwrap().public_parameter;
+        for (a, b) in longer.points_in_g1.iter().zip(&test_setup.public_parameter.points_in_g1) {
+            assert_eq!(a.compress(), b.compress());
+        }
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_insecure_tiny_setup() {
+        let kzg = KZG::insecure_tiny_setup(4);
+        assert_eq!(kzg, KZG::insecure_tiny_setup(4));
+        assert_eq!(kzg.degree(), 4);
+        assert!(kzg.public_parameter.verify_structure().is_ok());
+        assert_eq!(
+            kzg.public_parameter.points_in_g1[2].compress(),
+            (Fr::from_u64(25) * P1::generator()).compress()
+        );
+
+        let polynomial = Polynomial::from(vec![3u64, 1, 4]);
+        let commitment = KZG::commit(&kzg.public_parameter, &polynomial).unwrap();
+        let opening = commitment.open_at(Fr::from_u64(9)).unwrap();
+        assert!(opening.verify(&Fr::from_u64(9), &commitment));
+    }
+
+    #[test]
+    fn test_opening_on_insecure_test_setup() {
+        // the polynomials and values of the reference vectors in `test_opening`, whose commitments
+        // and proofs are pinned to specific taus
+        let cases: [(&[u64], u64); 6] = [
+            (&[0], 0),
+            (&[11], 11),
+            (&[0, 1], 15),
+            (&[1, 12], 181),
+            (&[1, 2, 2], 481),
+            (&[1, 2, 3, 4, 7, 7, 7, 7, 13, 13, 13, 13, 13, 13, 13, 13], 6099236329206434206),
+        ];
+        let kzg = KZG::insecure_test_setup(16).unwrap();
+        let point = Fr::from_u64(15);
+
+        for (coefficients, value) in cases {
+            let polynomial = Polynomial::from(coefficients.to_vec());
+            let commitment = KZG::commit(&kzg.public_parameter, &polynomial).unwrap();
+            let opening = commitment.open_at(point).unwrap();
+
+            assert_eq!(opening.value.as_u64(), value);
+            assert!(opening.verify(&point, &commitment));
+            let wrong_value = Opening { value: opening.value + Fr::from_u64(1), proof: opening.proof };
+            assert!(!wrong_value.verify(&point, &commitment));
+        }
+    }
 
     #[test]
     fn test_setup() {
@@ -268,6 +4564,14 @@ mod tests {
 
 
 
+    /// the powers of tau = 0: the generator followed by identity points
+    fn degenerate_zero_setup(degree: usize) -> PP {
+        let mut points_in_g1 = vec![P1::default(); degree + 1];
+        points_in_g1[0] = P1::generator();
+
+        PP::from_parts(points_in_g1, vec![P2::generator(), P2::default()])
+    }
+
     #[test]
     fn test_opening() {
         // computed from python reference: https://github.com/ethereum/research/blob/master/kzg_data_availability/kzg_proofs.py
@@ -410,12 +4714,20 @@ mod tests {
 
             let degree = coefficients.len();
 
-            let secret = secret.as_slice().try_into().unwrap();
-            let setup = KZG::new(secret, degree).unwrap().public_parameter;
+            let secret: &[u8; 32] = secret.as_slice().try_into().unwrap();
+            let setup = if secret == &[0u8; 32] {
+                // the reference vectors include tau = 0, which `KZG::new` rightly refuses
+                assert!(matches!(KZG::new(secret, degree), Err(KZGErrors::SecretMustBeNonZero)));
+                degenerate_zero_setup(degree)
+            } else {
+                KZG::new(secret, degree).unwrap().public_parameter
+            };
 
             let polynomial = Polynomial::from_coefficients(coefficients);
 
             let commitment = KZG::commit(&setup, &polynomial).unwrap();
+            let key_commitment = CommitKey::precompute(&setup, 4).commit(&polynomial).unwrap();
+            assert_eq!(key_commitment.compress(), commitment.element.compress());
 
             let opening = commitment.open_at(point).unwrap();
 
@@ -438,4 +4750,158 @@ mod tests {
     }
 
 
+    #[test]
+    fn test_commit_matches_term_by_term() {
+        let pp = KZG::new(&[0x73u8; 32], 4096).unwrap().public_parameter;
+        let mut rng = StdRng::seed_from_u64(73);
+        // either side of the MSM threshold, and the full setup
+        for length in [1, msm::PIPPENGER_THRESHOLD - 1, msm::PIPPENGER_THRESHOLD, 500, 4097] {
+            let coefficients = (0..length)
+                .map(|_| {
+                    let mut bytes = [0u8; 64];
+                    rng.fill_bytes(&mut bytes);
+                    scalar::fr_from_bytes_be(&bytes)
+                })
+                .collect::<Vec<_>>();
+            let term_by_term = coefficients
+                .iter()
+                .zip(&pp.points_in_g1)
+                .fold(P1::default(), |sum, (coefficient, point)| sum + *coefficient * *point);
+
+            let commitment = KZG::commit(&pp, &Polynomial::from_coefficients(coefficients)).unwrap().element;
+            assert_eq!(commitment.compress(), term_by_term.compress());
+        }
+    }
+
+    #[test]
+    fn test_commit_degree_2_14() {
+        // large enough for the chunked MSM under the `parallel` feature, serial otherwise
+        let degree = 1 << 14;
+        let pp = KZG::new(&[0x57u8; 32], degree).unwrap().public_parameter;
+        let coefficients = (0..=degree as u64).map(|i| Fr::from_u64(i * 7 + 1) / Fr::from_u64(i + 3)).collect::<Vec<_>>();
+        let polynomial = Polynomial::from_coefficients(coefficients);
+        let commitment = KZG::commit(&pp, &polynomial).unwrap();
+
+        // the four quarters of the MSM, one at a time
+        let quarter = (degree + 1).div_ceil(4);
+        let quarters = polynomial
+            .coefficients
+            .chunks(quarter)
+            .zip(pp.points_in_g1.chunks(quarter))
+            .map(|(scalars, bases)| msm::pippenger(scalars, bases))
+            .fold(P1::default(), |sum, part| sum + part);
+        assert_eq!(commitment.element.compress(), quarters.compress());
+
+        let z = Fr::from_u64(0x75);
+        assert!(commitment.open_at(z).unwrap().verify(&z, &commitment));
+    }
+
+    #[test]
+    fn test_caching_commitment() {
+        let pp = KZG::new(&[0x53u8; 32], 8).unwrap().public_parameter;
+        let polynomial = Polynomial::from(vec![8u64, 3, 0, 5, 7]);
+        let mut cached = CachingCommitment::new(KZG::commit(&pp, &polynomial).unwrap());
+        let z = Fr::from_u64(83);
+
+        let first = cached.open_at_cached(z).unwrap();
+        assert_eq!(cached.computed_openings(), 1);
+        let second = cached.open_at_cached(z).unwrap();
+        assert_eq!(cached.computed_openings(), 1);
+        assert_eq!(second.value, first.value);
+        assert_eq!(second.proof.compress(), first.proof.compress());
+        assert!(second.verify(&z, cached.commitment()));
+
+        // another point is computed, and both are kept
+        let w = Fr::from_u64(84);
+        let other = cached.open_at_cached(w).unwrap();
+        assert_eq!(other.value, polynomial.evaluate(w));
+        assert_eq!(cached.computed_openings(), 2);
+        assert_eq!(cached.cached_openings(), 2);
+        cached.open_at_cached(w).unwrap();
+        assert_eq!(cached.computed_openings(), 2);
+
+        cached.clear();
+        assert_eq!(cached.cached_openings(), 0);
+        assert_eq!(cached.open_at_cached(z).unwrap().proof.compress(), first.proof.compress());
+        assert_eq!(cached.computed_openings(), 3);
+    }
+
+    #[test]
+    fn test_commit_batch() {
+        let pp = KZG::new(&[0x39u8; 32], 64).unwrap().public_parameter;
+        let mut rng = StdRng::seed_from_u64(79);
+        let mut polynomials = (0..100)
+            .map(|_| {
+                let length = rng.gen_range(0..=65);
+                Polynomial::from_coefficients((0..length).map(|_| Fr::from_u64(rng.next_u64())).collect())
+            })
+            .collect::<Vec<_>>();
+        polynomials[3] = Polynomial::zero();
+        // shorter than its coefficient vector
+        polynomials[4] = Polynomial::from(vec![1u64, 2, 3, 0, 0, 0]);
+
+        let batch = KZG::commit_batch(&pp, &polynomials).unwrap();
+        assert_eq!(batch.len(), 100);
+        for (commitment, polynomial) in batch.iter().zip(&polynomials) {
+            let single = KZG::commit(&pp, polynomial).unwrap();
+            assert_eq!(commitment.element.compress(), single.element.compress());
+            assert_eq!(commitment.polynomial, polynomial);
+        }
+        let z = Fr::from_u64(79);
+        assert!(batch[10].open_at(z).unwrap().verify(&z, &batch[10]));
+
+        // the first oversized polynomial is the one reported
+        polynomials[7] = Polynomial::from(vec![1u64; 66]);
+        polynomials[9] = Polynomial::from(vec![1u64; 70]);
+        assert!(matches!(
+            KZG::commit_batch(&pp, &polynomials),
+            Err(KZGErrors::BatchPolynomialDegreeTooLarge { index: 7, degree: 65, max_degree: 64 })
+        ));
+
+        assert!(KZG::commit_batch(&pp, &[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_degree_bound_proof() {
+        let pp = KZG::setup_with_g2_powers(&[0x67u8; 32], 8, 9).unwrap().public_parameter;
+        let degree_3 = Polynomial::from(vec![1u64, 2, 3, 4]);
+        let commitment = KZG::commit(&pp, &degree_3).unwrap().element;
+
+        let proof = prove_degree_bound(&pp, &degree_3, 5).unwrap();
+        assert!(verify_degree_bound(&pp, &commitment, &proof).unwrap());
+        // and any larger bound, up to the setup's degree
+        for bound in [3, 4, 8] {
+            assert!(verify_degree_bound(&pp, &commitment, &prove_degree_bound(&pp, &degree_3, bound).unwrap()).unwrap());
+        }
+
+        // a degree-6 polynomial can't be proven within 5
+        let degree_6 = Polynomial::from(vec![1u64, 2, 3, 4, 5, 6, 7]);
+        let commitment = KZG::commit(&pp, &degree_6).unwrap().element;
+        assert!(matches!(
+            prove_degree_bound(&pp, &degree_6, 5),
+            Err(KZGErrors::PolynomialDegreeTooLarge { degree: 6, max_degree: 5 })
+        ));
+        // nor can its proof for bound 6 pass as one for bound 5
+        let proof = prove_degree_bound(&pp, &degree_6, 6).unwrap();
+        assert!(verify_degree_bound(&pp, &commitment, &proof).unwrap());
+        let relabelled = DegreeBoundProof { bound: 5, ..proof };
+        assert!(!verify_degree_bound(&pp, &commitment, &relabelled).unwrap());
+
+        // the check needs [tau^(D - bound)]_2
+        let small = KZG::new(&[0x67u8; 32], 8).unwrap().public_parameter;
+        assert!(matches!(
+            prove_degree_bound(&small, &degree_3, 5),
+            Err(KZGErrors::G2PowerOutOfRange { index: 3, available: 2 })
+        ));
+        assert!(matches!(
+            verify_degree_bound(&small, &commitment, &relabelled),
+            Err(KZGErrors::G2PowerOutOfRange { index: 3, available: 2 })
+        ));
+        assert!(matches!(
+            prove_degree_bound(&pp, &degree_3, 9),
+            Err(KZGErrors::DegreeExceedsSetup { requested: 9, available: 8 })
+        ));
+    }
+
+
 }
\ No newline at end of file
@@ -0,0 +1,404 @@
+//! The setup in Lagrange form, for committing to data given as evaluations over a [`Domain`].
+//!
+//! For the domain `{1, w, ..., w^(n-1)}`, `L_i(X) = (1/n) * sum_j (w^-i X)^j`, so the points
+//! `[L_i(tau)]_1` are the inverse FFT of `[1]_1, [tau]_1, ..., [tau^(n-1)]_1`. The FFT runs over
+//! the group elements directly: tau itself is never needed.
+
+use std::ops::{Add, Mul, Neg};
+
+use blst::{blst_p1, blst_scalar};
+use oblast_demo::{Fr, P1, P2};
+use sha2::{Digest, Sha256};
+
+use crate::domain::{Domain, Evaluations};
+use crate::group::{self, G1_COMPRESSED_SIZE, G2_COMPRESSED_SIZE};
+use crate::kzg_commit::{self, KZGErrors, ValidationLevel, KZG, PP};
+use crate::msm;
+
+/// magic bytes at the start of a serialized `LagrangePP`
+const LAGRANGE_MAGIC: &[u8; 4] = b"KZGL";
+/// current version of the serialized `LagrangePP` format
+const LAGRANGE_VERSION: u8 = 1;
+/// magic + version + domain size + number of G2 powers + digest
+const LAGRANGE_HEADER_SIZE: usize = 4 + 1 + 8 + 8 + 32;
+
+/// `[L_0(tau)]_1, ..., [L_(n-1)(tau)]_1` for a domain of size `n`, with the G2 powers of the
+/// setup it was derived from (see [`PP::to_lagrange`])
+#[derive(Clone, Debug)]
+pub struct LagrangePP {
+    /// `[L_i(tau)]_1` for every point of the domain, in domain order
+    pub points_in_g1: Vec<P1>,
+    /// Powers of Tau for P2: `[1]_2, [tau]_2, ...` (at least two)
+    pub points_in_g2: Vec<P2>,
+    domain: Domain,
+}
+
+/// points are compared through their compressed encoding, like [`PP`]
+impl PartialEq for LagrangePP {
+    fn eq(&self, other: &Self) -> bool {
+        self.domain == other.domain
+            && self.points_in_g1.len() == other.points_in_g1.len()
+            && self.points_in_g2.len() == other.points_in_g2.len()
+            && self
+                .points_in_g1
+                .iter()
+                .zip(&other.points_in_g1)
+                .all(|(a, b)| a.compress() == b.compress())
+            && self
+                .points_in_g2
+                .iter()
+                .zip(&other.points_in_g2)
+                .all(|(a, b)| a.compress() == b.compress())
+    }
+}
+
+/// a G1 point for the FFT of [`PP::to_lagrange`], added with `blst`'s add-or-double: the
+/// butterflies add equal points whenever powers of tau repeat (tau = 1, or tau = -1), which the
+/// `+` of `oblast_demo` gets wrong
+#[derive(Clone, Copy)]
+struct FftPoint(blst_p1);
+
+impl From<&P1> for FftPoint {
+    fn from(point: &P1) -> Self {
+        let mut raw = blst_p1::default();
+        unsafe { blst::blst_p1_from_affine(&mut raw, &group::g1_to_affine(point)) };
+        FftPoint(raw)
+    }
+}
+
+impl Add for FftPoint {
+    type Output = FftPoint;
+
+    fn add(self, other: FftPoint) -> FftPoint {
+        let mut sum = blst_p1::default();
+        unsafe { blst::blst_p1_add_or_double(&mut sum, &self.0, &other.0) };
+        FftPoint(sum)
+    }
+}
+
+impl Neg for FftPoint {
+    type Output = FftPoint;
+
+    fn neg(mut self) -> FftPoint {
+        unsafe { blst::blst_p1_cneg(&mut self.0, true) };
+        self
+    }
+}
+
+impl Mul<FftPoint> for Fr {
+    type Output = FftPoint;
+
+    fn mul(self, point: FftPoint) -> FftPoint {
+        let mut scalar = blst_scalar::default();
+        let mut product = blst_p1::default();
+        unsafe {
+            blst::blst_scalar_from_uint64(&mut scalar, msm::limbs(&self).as_ptr());
+            blst::blst_p1_mult(&mut product, &point.0, scalar.b.as_ptr(), 255);
+        }
+        FftPoint(product)
+    }
+}
+
+impl PP {
+    /// the Lagrange-form setup for `domain`, computed by an inverse FFT over the first `n` G1
+    /// powers. A domain of size `n` needs a setup of degree at least `n - 1`.
+    pub fn to_lagrange(&self, domain: &Domain) -> Result<LagrangePP, KZGErrors> {
+        let view = self.as_slice(domain.size() - 1)?;
+        let mut points: Vec<FftPoint> = view.g1_basis().iter().map(FftPoint::from).collect();
+        domain.ifft_in_place(&mut points);
+        let points_in_g1 = points.into_iter().map(|point| P1::from_raw(point.0)).collect();
+
+        Ok(LagrangePP {
+            points_in_g1,
+            points_in_g2: self.points_in_g2.clone(),
+            domain: domain.clone(),
+        })
+    }
+}
+
+impl LagrangePP {
+    pub(crate) fn from_parts(points_in_g1: Vec<P1>, points_in_g2: Vec<P2>, domain: Domain) -> LagrangePP {
+        assert_eq!(points_in_g1.len(), domain.size(), "one G1 point per domain point");
+        assert!(points_in_g2.len() >= 2, "need at least [1]_2 and [tau]_2");
+        LagrangePP {
+            points_in_g1,
+            points_in_g2,
+            domain,
+        }
+    }
+
+    /// the domain the points are the Lagrange basis of
+    pub fn domain(&self) -> &Domain {
+        &self.domain
+    }
+
+    /// `[tau]_2`, for verifying openings of Lagrange-form commitments
+    pub fn point_in_g2(&self) -> P2 {
+        self.points_in_g2[1]
+    }
+
+    /// commits to the polynomial taking `evaluations[i]` at the `i`-th domain point (missing
+    /// evaluations are zero); the result is the same point [`kzg_commit::KZG::commit`] gives for
+    /// the polynomial's coefficients under the monomial setup
+    pub fn commit(&self, evaluations: &[Fr]) -> Result<P1, KZGErrors> {
+        if evaluations.len() > self.domain.size() {
+            return Err(KZGErrors::TooManyEvaluations {
+                evaluations: evaluations.len(),
+                domain_size: self.domain.size(),
+            });
+        }
+
+        Ok(msm::linear_combination(evaluations, &self.points_in_g1[..evaluations.len()]))
+    }
+
+    /// SHA-256 fingerprint over magic `KZGL`, the format version, the domain size and number of
+    /// G2 powers as big-endian u64s, and every compressed point in order
+    pub fn digest(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(LAGRANGE_MAGIC);
+        hasher.update([LAGRANGE_VERSION]);
+        hasher.update((self.domain.size() as u64).to_be_bytes());
+        hasher.update((self.points_in_g2.len() as u64).to_be_bytes());
+        for point in &self.points_in_g1 {
+            hasher.update(point.compress());
+        }
+        for point in &self.points_in_g2 {
+            hasher.update(point.compress());
+        }
+        hasher.finalize().into()
+    }
+
+    /// serializes like [`PP::to_bytes`], with magic `KZGL` and the domain size in place of the
+    /// degree: magic, version byte, domain size and number of G2 powers as big-endian u64s, the
+    /// 32-byte [`LagrangePP::digest`], then the compressed G1 points and the compressed G2 points
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(
+            LAGRANGE_HEADER_SIZE
+                + self.points_in_g1.len() * G1_COMPRESSED_SIZE
+                + self.points_in_g2.len() * G2_COMPRESSED_SIZE,
+        );
+
+        bytes.extend_from_slice(LAGRANGE_MAGIC);
+        bytes.push(LAGRANGE_VERSION);
+        bytes.extend_from_slice(&(self.domain.size() as u64).to_be_bytes());
+        bytes.extend_from_slice(&(self.points_in_g2.len() as u64).to_be_bytes());
+        bytes.extend_from_slice(&self.digest());
+        for point in &self.points_in_g1 {
+            bytes.extend_from_slice(&point.compress());
+        }
+        for point in &self.points_in_g2 {
+            bytes.extend_from_slice(&point.compress());
+        }
+
+        bytes
+    }
+
+    /// parses the output of [`LagrangePP::to_bytes`], decompressing every point and checking the
+    /// digest in the header
+    pub fn from_bytes(bytes: &[u8]) -> Result<LagrangePP, KZGErrors> {
+        if bytes.len() < LAGRANGE_HEADER_SIZE {
+            return Err(KZGErrors::TruncatedInput);
+        }
+        if &bytes[..4] != LAGRANGE_MAGIC {
+            return Err(KZGErrors::InvalidSerializationMagic);
+        }
+        if bytes[4] != LAGRANGE_VERSION {
+            return Err(KZGErrors::UnsupportedSerializationVersion(bytes[4]));
+        }
+
+        let size = u64::from_be_bytes(bytes[5..13].try_into().unwrap());
+        let g2_count = u64::from_be_bytes(bytes[13..21].try_into().unwrap());
+        let digest: [u8; 32] = bytes[21..53].try_into().unwrap();
+        let domain = usize::try_from(size)
+            .map_err(|_| KZGErrors::InvalidDomainSize(usize::MAX))
+            .and_then(Domain::new)?;
+        if g2_count < 2 {
+            return Err(KZGErrors::NotEnoughG2Powers { found: g2_count as usize });
+        }
+
+        let g2_size = usize::try_from(g2_count)
+            .ok()
+            .and_then(|count| count.checked_mul(G2_COMPRESSED_SIZE))
+            .ok_or(KZGErrors::TruncatedInput)?;
+        let expected_len = domain
+            .size()
+            .checked_mul(G1_COMPRESSED_SIZE)
+            .and_then(|size| size.checked_add(LAGRANGE_HEADER_SIZE))
+            .and_then(|size| size.checked_add(g2_size))
+            .ok_or(KZGErrors::TruncatedInput)?;
+        if bytes.len() < expected_len {
+            return Err(KZGErrors::TruncatedInput);
+        }
+        if bytes.len() > expected_len {
+            return Err(KZGErrors::TrailingBytes);
+        }
+
+        let body = &bytes[LAGRANGE_HEADER_SIZE..];
+        let (g1_bytes, g2_bytes) = body.split_at(body.len() - g2_size);
+        let (points_in_g1, points_in_g2) = kzg_commit::decode_points(g1_bytes, g2_bytes, ValidationLevel::Full)?;
+
+        let lagrange = LagrangePP::from_parts(points_in_g1, points_in_g2, domain);
+        if digest != lagrange.digest() {
+            return Err(KZGErrors::DigestMismatch);
+        }
+
+        Ok(lagrange)
+    }
+}
+
+impl KZG {
+    /// commits to the polynomial given by its evaluations, with the Lagrange-form setup over the
+    /// same domain and without an inverse FFT; the result is the point [`KZG::commit`] gives for
+    /// the coefficients under the monomial setup
+    pub fn commit_lagrange(lpp: &LagrangePP, evaluations: &Evaluations) -> Result<P1, KZGErrors> {
+        if evaluations.domain() != lpp.domain() {
+            return Err(KZGErrors::DomainMismatch {
+                expected: lpp.domain().size(),
+                found: evaluations.domain().size(),
+            });
+        }
+        lpp.commit(evaluations.values())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg_commit::KZG;
+    use crate::polynomial::Polynomial;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn random_polynomial(rng: &mut StdRng, len: usize) -> Polynomial {
+        Polynomial::from_coefficients((0..len).map(|_| Fr::from_u64(rng.gen())).collect())
+    }
+
+    #[test]
+    fn lagrange_commitments_match_monomial_ones() {
+        let mut rng = StdRng::seed_from_u64(35);
+        let pp = KZG::new(&[0x35u8; 32], 80).unwrap().public_parameter;
+        let domain = Domain::new(64).unwrap();
+        let lagrange = pp.to_lagrange(&domain).unwrap();
+
+        for len in [1, 17, 63, 64] {
+            let polynomial = random_polynomial(&mut rng, len);
+            let evaluations = domain.fft(&polynomial.coefficients);
+
+            let expected = KZG::commit(&pp, &polynomial).unwrap();
+            let commitment = lagrange.commit(&evaluations).unwrap();
+            assert_eq!(commitment.compress(), expected.element.compress());
+        }
+
+        // the i-th point commits to the i-th Lagrange polynomial, which is 1 at w^i and 0 elsewhere
+        let mut indicator = vec![Fr::default(); 64];
+        indicator[5] = Fr::from_u64(1);
+        let lagrange_5 = Polynomial::from_coefficients(domain.ifft(&indicator));
+        let expected = KZG::commit(&pp, &lagrange_5).unwrap();
+        assert_eq!(lagrange.points_in_g1[5].compress(), expected.element.compress());
+
+        assert!(matches!(
+            lagrange.commit(&[Fr::from_u64(1); 65]),
+            Err(KZGErrors::TooManyEvaluations { evaluations: 65, domain_size: 64 })
+        ));
+    }
+
+    #[test]
+    fn lagrange_form_of_repeating_powers() {
+        // tau = 1 makes every power [1]_1 and tau = r - 1 alternates [1]_1 and -[1]_1, so the FFT
+        // adds equal points and a point to its negation
+        let one = hex::decode("0000000000000000000000000000000000000000000000000000000000000001").unwrap();
+        let minus_one = hex::decode("73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000000").unwrap();
+        let mut rng = StdRng::seed_from_u64(36);
+        for tau in [one, minus_one] {
+            let pp = KZG::new(tau.as_slice().try_into().unwrap(), 7).unwrap().public_parameter;
+            let domain = Domain::new(8).unwrap();
+            let lagrange = pp.to_lagrange(&domain).unwrap();
+
+            for len in [1, 5, 8] {
+                let polynomial = random_polynomial(&mut rng, len);
+                let expected = KZG::commit(&pp, &polynomial).unwrap().element;
+                let commitment = lagrange.commit(&domain.fft(&polynomial.coefficients)).unwrap();
+                assert_eq!(commitment.compress(), expected.compress(), "{}", hex::encode(&tau));
+            }
+        }
+    }
+
+    #[test]
+    fn commit_lagrange_matches_commit() {
+        let mut rng = StdRng::seed_from_u64(82);
+        let pp = KZG::new(&[0x52u8; 32], 64).unwrap().public_parameter;
+        let domain = Domain::new(64).unwrap();
+        let lagrange = pp.to_lagrange(&domain).unwrap();
+
+        // full length, and lower degrees whose coefficients are zero-padded to the domain size
+        for len in [64, 40, 5, 1, 0] {
+            let polynomial = random_polynomial(&mut rng, len);
+            let evaluations = Evaluations::from_polynomial(&polynomial, &domain).unwrap();
+            assert_eq!(evaluations.to_polynomial(), polynomial);
+
+            let expected = KZG::commit(&pp, &polynomial).unwrap().element;
+            assert_eq!(KZG::commit_lagrange(&lagrange, &evaluations).unwrap().compress(), expected.compress());
+            let padded = Polynomial::from_coefficients(evaluations.to_polynomial().coefficients);
+            assert_eq!(padded.coefficients.len(), 64);
+            assert_eq!(KZG::commit(&pp, &padded).unwrap().element.compress(), expected.compress());
+        }
+
+        let values = (0..64).map(|_| Fr::from_u64(rng.gen())).collect::<Vec<_>>();
+        let evaluations = Evaluations::new(values.clone(), domain.clone()).unwrap();
+        let expected = KZG::commit(&pp, &evaluations.to_polynomial()).unwrap().element;
+        assert_eq!(KZG::commit_lagrange(&lagrange, &evaluations).unwrap().compress(), expected.compress());
+
+        // the evaluations must be over the setup's domain
+        let smaller = Domain::new(32).unwrap();
+        let evaluations = Evaluations::new(values[..32].to_vec(), smaller).unwrap();
+        assert!(matches!(
+            KZG::commit_lagrange(&lagrange, &evaluations),
+            Err(KZGErrors::DomainMismatch { expected: 64, found: 32 })
+        ));
+        assert!(matches!(
+            Evaluations::new(values[..63].to_vec(), domain.clone()),
+            Err(KZGErrors::EvaluationCountMismatch { evaluations: 63, domain_size: 64 })
+        ));
+        assert!(matches!(
+            Evaluations::from_polynomial(&random_polynomial(&mut rng, 65), &domain),
+            Err(KZGErrors::PolynomialDegreeTooLarge { degree: 64, max_degree: 63 })
+        ));
+    }
+
+    #[test]
+    fn domain_must_fit_the_setup() {
+        let pp = KZG::new(&[0x36u8; 32], 63).unwrap().public_parameter;
+        assert!(pp.to_lagrange(&Domain::new(64).unwrap()).is_ok());
+        assert!(matches!(
+            pp.to_lagrange(&Domain::new(128).unwrap()),
+            Err(KZGErrors::DegreeExceedsSetup { requested: 127, available: 63 })
+        ));
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let pp = KZG::new(&[0x37u8; 32], 16).unwrap().public_parameter;
+        let lagrange = pp.to_lagrange(&Domain::new(16).unwrap()).unwrap();
+
+        let bytes = lagrange.to_bytes();
+        assert_eq!(bytes.len(), LAGRANGE_HEADER_SIZE + 16 * 48 + 2 * 96);
+        assert_eq!(LagrangePP::from_bytes(&bytes).unwrap(), lagrange);
+
+        // a monomial setup isn't a Lagrange one
+        assert!(matches!(LagrangePP::from_bytes(&pp.to_bytes()), Err(KZGErrors::InvalidSerializationMagic)));
+
+        assert!(matches!(LagrangePP::from_bytes(&bytes[..bytes.len() - 1]), Err(KZGErrors::TruncatedInput)));
+
+        let mut swapped = bytes.clone();
+        let first = LAGRANGE_HEADER_SIZE;
+        let second = first + G1_COMPRESSED_SIZE;
+        let (a, b) = swapped[first..second + G1_COMPRESSED_SIZE].split_at_mut(G1_COMPRESSED_SIZE);
+        a.swap_with_slice(b);
+        assert!(matches!(LagrangePP::from_bytes(&swapped), Err(KZGErrors::DigestMismatch)));
+
+        let mut bad_size = bytes;
+        bad_size[12] = 12;
+        assert!(matches!(LagrangePP::from_bytes(&bad_size), Err(KZGErrors::InvalidDomainSize(12))));
+    }
+}
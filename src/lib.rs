@@ -1,10 +1,28 @@
+pub mod backend;
+pub mod builder;
 pub mod polynomial;
 pub mod kzg_commit;
+pub mod scalar;
+pub mod ceremony;
+pub mod domain;
+pub mod eip4844;
+pub mod fixed_base;
+pub mod keys;
+pub mod lagrange;
+pub mod msm;
+mod group;
+#[cfg(feature = "mmap")]
+pub mod mapped;
+mod secret;
+pub mod settings;
+pub mod setup_loaders;
+#[cfg(feature = "serde")]
+mod serde_impls;
+mod transcript;
+pub mod vector;
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     fn it_works() {}
 }
@@ -0,0 +1,307 @@
+//! Setups read straight from a memory-mapped file (behind the `mmap` feature).
+//!
+//! A [`MappedPP`] maps a file written by [`PP::to_bytes`] and only decompresses a G1 point when a
+//! commitment needs it, keeping a bounded LRU cache of decoded points. The G2 powers are few and
+//! are decoded up front.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::path::Path;
+use std::sync::Mutex;
+
+use memmap2::Mmap;
+use oblast_demo::{P1, P2};
+use sha2::{Digest, Sha256};
+
+use crate::group::{self, G1_COMPRESSED_SIZE, G2_COMPRESSED_SIZE};
+use crate::kzg_commit::{self, KZGErrors, Srs, SrsPoints, PP, PP_DIGEST_VERSION, PP_HEADER_SIZE, PP_MAGIC, PP_VERSION};
+use crate::setup_loaders::SetupLoadError;
+
+/// decoded G1 points by index, evicting the least recently used one when full
+struct PointCache {
+    capacity: usize,
+    clock: u64,
+    points: HashMap<usize, (P1, u64)>,
+    /// last use -> index, oldest first
+    recency: BTreeMap<u64, usize>,
+}
+
+impl PointCache {
+    fn new(capacity: usize) -> Self {
+        PointCache {
+            capacity,
+            clock: 0,
+            points: HashMap::new(),
+            recency: BTreeMap::new(),
+        }
+    }
+
+    fn get(&mut self, index: usize) -> Option<P1> {
+        self.clock += 1;
+        let (point, last_use) = self.points.get_mut(&index)?;
+        self.recency.remove(last_use);
+        self.recency.insert(self.clock, index);
+        *last_use = self.clock;
+        Some(*point)
+    }
+
+    fn insert(&mut self, index: usize, point: P1) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.points.len() >= self.capacity {
+            if let Some((_, oldest)) = self.recency.pop_first() {
+                self.points.remove(&oldest);
+            }
+        }
+        self.clock += 1;
+        self.points.insert(index, (point, self.clock));
+        self.recency.insert(self.clock, index);
+    }
+}
+
+/// A [`PP`] left on disk: G1 points are decompressed on demand, so only the cache is resident
+pub struct MappedPP {
+    map: Mmap,
+    max_degree: usize,
+    points_in_g2: Vec<P2>,
+    digest: [u8; 32],
+    cache: Mutex<PointCache>,
+}
+
+fn invalid(error: KZGErrors) -> SetupLoadError {
+    SetupLoadError::InvalidFormat(error)
+}
+
+impl MappedPP {
+    /// maps a setup written by [`PP::to_bytes`] (the current format version only), keeping at most
+    /// `cache_capacity` decoded G1 points in memory.
+    ///
+    /// The header and the digest are checked here, which reads the whole file once; G1 points are
+    /// only validated when they are first used. The file must not be modified while it is mapped.
+    pub fn open(path: impl AsRef<Path>, cache_capacity: usize) -> Result<MappedPP, SetupLoadError> {
+        let file = File::open(path)?;
+        // the file is only read, and callers must not change it while it is mapped
+        let map = unsafe { Mmap::map(&file)? };
+
+        if map.len() < PP_HEADER_SIZE {
+            return Err(invalid(KZGErrors::TruncatedInput));
+        }
+        if &map[..4] != PP_MAGIC {
+            return Err(invalid(KZGErrors::InvalidSerializationMagic));
+        }
+        if map[4] != PP_VERSION {
+            return Err(invalid(KZGErrors::UnsupportedSerializationVersion(map[4])));
+        }
+
+        let degree = u64::from_be_bytes(map[5..13].try_into().unwrap());
+        let g2_count = u64::from_be_bytes(map[13..21].try_into().unwrap());
+        let digest: [u8; 32] = map[21..53].try_into().unwrap();
+        if g2_count < 2 {
+            return Err(invalid(KZGErrors::NotEnoughG2Powers { found: g2_count as usize }));
+        }
+
+        let g1_count = usize::try_from(degree)
+            .ok()
+            .and_then(|degree| degree.checked_add(1))
+            .ok_or(invalid(KZGErrors::TruncatedInput))?;
+        let g2_size = usize::try_from(g2_count)
+            .ok()
+            .and_then(|count| count.checked_mul(G2_COMPRESSED_SIZE))
+            .ok_or(invalid(KZGErrors::TruncatedInput))?;
+        let expected_len = g1_count
+            .checked_mul(G1_COMPRESSED_SIZE)
+            .and_then(|size| size.checked_add(PP_HEADER_SIZE))
+            .and_then(|size| size.checked_add(g2_size))
+            .ok_or(invalid(KZGErrors::TruncatedInput))?;
+        if map.len() < expected_len {
+            return Err(invalid(KZGErrors::TruncatedInput));
+        }
+        if map.len() > expected_len {
+            return Err(invalid(KZGErrors::TrailingBytes));
+        }
+
+        // the file body is exactly the compressed points `PP::digest` hashes
+        let mut hasher = Sha256::new();
+        hasher.update(PP_MAGIC);
+        hasher.update([PP_DIGEST_VERSION]);
+        hasher.update(degree.to_be_bytes());
+        hasher.update(g2_count.to_be_bytes());
+        hasher.update(&map[PP_HEADER_SIZE..]);
+        if <[u8; 32]>::from(hasher.finalize()) != digest {
+            return Err(invalid(KZGErrors::DigestMismatch));
+        }
+
+        let points_in_g2 = map[expected_len - g2_size..]
+            .chunks_exact(G2_COMPRESSED_SIZE)
+            .enumerate()
+            .map(|(index, chunk)| {
+                group::decompress_g2(chunk.try_into().unwrap())
+                    .map_err(|error| invalid(kzg_commit::point_error(error, g1_count + index)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mapped = MappedPP {
+            map,
+            max_degree: g1_count - 1,
+            points_in_g2,
+            digest,
+            cache: Mutex::new(PointCache::new(cache_capacity)),
+        };
+        // decoded outside the cache, which starts empty
+        let generator = mapped.decode_g1(0).map_err(invalid)?;
+        kzg_commit::check_generator_slot(&[generator]).map_err(invalid)?;
+        Ok(mapped)
+    }
+
+    /// the largest degree that can be committed to
+    pub fn max_degree(&self) -> usize {
+        self.max_degree
+    }
+
+    /// `[tau]_2`
+    pub fn point_in_g2(&self) -> P2 {
+        self.points_in_g2[1]
+    }
+
+    /// the digest from the file header, equal to [`PP::digest`] of the in-memory setup
+    pub fn digest(&self) -> [u8; 32] {
+        self.digest
+    }
+
+    /// the number of decoded G1 points currently held in memory
+    pub fn cached_points(&self) -> usize {
+        self.cache.lock().unwrap().points.len()
+    }
+
+    fn decode_g1(&self, index: usize) -> Result<P1, KZGErrors> {
+        let offset = PP_HEADER_SIZE + index * G1_COMPRESSED_SIZE;
+        let bytes = self.map[offset..offset + G1_COMPRESSED_SIZE].try_into().unwrap();
+        group::decompress_g1(bytes).map_err(|error| kzg_commit::point_error(error, index))
+    }
+
+    /// decodes the whole setup into memory
+    pub fn to_pp(&self) -> Result<PP, KZGErrors> {
+        PP::from_bytes(&self.map)
+    }
+}
+
+impl SrsPoints for MappedPP {
+    fn g1_point(&self, index: usize) -> Result<P1, KZGErrors> {
+        if index > self.max_degree {
+            return Err(KZGErrors::DegreeExceedsSetup {
+                requested: index,
+                available: self.max_degree,
+            });
+        }
+
+        let mut cache = self.cache.lock().unwrap();
+        if let Some(point) = cache.get(index) {
+            return Ok(point);
+        }
+
+        let point = self.decode_g1(index)?;
+        cache.insert(index, point);
+        Ok(point)
+    }
+}
+
+impl Srs for MappedPP {
+    fn point_in_g2(&self) -> P2 {
+        MappedPP::point_in_g2(self)
+    }
+
+    fn max_degree(&self) -> usize {
+        self.max_degree
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg_commit::KZG;
+    use crate::polynomial::{Fr, Polynomial};
+
+    fn write_setup(name: &str, bytes: &[u8]) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("kzg-mapped-{}-{}.bin", name, std::process::id()));
+        std::fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    #[test]
+    fn commits_match_the_in_memory_setup() {
+        let pp = KZG::new(&[0x43u8; 32], 64).unwrap().public_parameter;
+        let path = write_setup("commit", &pp.to_bytes());
+        let mapped = MappedPP::open(&path, 16).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(mapped.max_degree(), 64);
+        assert_eq!(mapped.digest(), pp.digest());
+        assert_eq!(mapped.cached_points(), 0);
+
+        for length in [1u64, 10, 40, 65] {
+            let polynomial = Polynomial::from((0..length).map(|i| i * 7 + 1).collect::<Vec<_>>());
+            let in_memory = KZG::commit(&pp, &polynomial).unwrap();
+            let from_file = KZG::commit(&mapped, &polynomial).unwrap();
+            assert_eq!(from_file.element.compress(), in_memory.element.compress());
+
+            let z = Fr::from_u64(length);
+            let opening = from_file.open_at(z).unwrap();
+            assert!(opening.verify(&z, &from_file));
+            assert!(opening.verify(&z, &in_memory));
+
+            // the cache never grows past its capacity
+            assert!(mapped.cached_points() <= 16);
+        }
+        assert_eq!(mapped.cached_points(), 16);
+        assert_eq!(mapped.to_pp().unwrap(), pp);
+
+        assert!(matches!(
+            KZG::commit(&mapped, &Polynomial::from(vec![1u64; 66])),
+            Err(KZGErrors::PolynomialDegreeTooLarge { degree: 65, max_degree: 64 })
+        ));
+    }
+
+    #[test]
+    fn cache_evicts_the_least_recently_used_point() {
+        let mut cache = PointCache::new(2);
+        cache.insert(0, P1::generator());
+        cache.insert(1, P1::generator());
+        assert!(cache.get(0).is_some());
+        cache.insert(2, P1::generator());
+
+        assert!(cache.get(1).is_none());
+        assert!(cache.get(0).is_some());
+        assert!(cache.get(2).is_some());
+        assert_eq!(cache.points.len(), 2);
+
+        let mut disabled = PointCache::new(0);
+        disabled.insert(0, P1::generator());
+        assert!(disabled.get(0).is_none());
+    }
+
+    #[test]
+    fn rejects_corrupted_files() {
+        let pp = KZG::new(&[0x44u8; 32], 8).unwrap().public_parameter;
+        let bytes = pp.to_bytes();
+
+        let mut corrupted = bytes.clone();
+        corrupted[PP_HEADER_SIZE + 3 * G1_COMPRESSED_SIZE + 5] ^= 1;
+        let path = write_setup("corrupted", &corrupted);
+        assert!(matches!(
+            MappedPP::open(&path, 4),
+            Err(SetupLoadError::InvalidFormat(KZGErrors::DigestMismatch))
+        ));
+        std::fs::remove_file(&path).unwrap();
+
+        let path = write_setup("truncated", &bytes[..bytes.len() - 1]);
+        assert!(matches!(
+            MappedPP::open(&path, 4),
+            Err(SetupLoadError::InvalidFormat(KZGErrors::TruncatedInput))
+        ));
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(MappedPP::open(&path, 4), Err(SetupLoadError::Io(_))));
+    }
+}
@@ -0,0 +1,255 @@
+//! Multi-scalar multiplication `sum scalars[i] * bases[i]` with Pippenger's bucket method.
+//!
+//! Each scalar is split into `c`-bit windows. For every window the bases are sorted into `2^c - 1`
+//! buckets by their digit and each bucket is summed, so a base costs one addition per window
+//! instead of a full scalar multiplication; the buckets are then combined with a running sum.
+//! For a degree-4096 commitment this is more than twice as fast as multiplying term by term.
+//!
+//! The accumulation runs on `blst` points directly, since the point addition of `oblast_demo`
+//! does not handle doubling, which bucket sums run into whenever two inputs coincide.
+//!
+//! With the `parallel` feature, inputs of at least [`PARALLEL_THRESHOLD`] terms are split into one
+//! chunk per rayon thread and the partial sums are added up at the end. The result is the same
+//! point as the serial computation for any number of threads.
+
+use blst::{blst_p1, blst_p1_affine};
+use oblast_demo::{Fr, P1};
+
+use crate::group;
+use crate::scalar;
+
+/// scalars are below the curve order, which has 255 bits
+pub(crate) const SCALAR_BITS: usize = 255;
+
+/// below this many terms one scalar multiplication per term is faster than bucketing, which pays
+/// for a conversion of every base and a fixed number of bucket sums
+pub const PIPPENGER_THRESHOLD: usize = 24;
+
+/// from this many terms on the `parallel` feature splits the work across the rayon pool; below
+/// it a chunk per thread is too small to pay for the extra bucket sums
+pub const PARALLEL_THRESHOLD: usize = 1024;
+
+/// the window width for `n` terms: each of the `255 / c` windows costs `n` additions into buckets
+/// and about `2^(c + 1)` to combine them, so take the `c` minimizing the total
+fn window_bits(n: usize) -> usize {
+    (2..=16)
+        .min_by_key(|&c| SCALAR_BITS.div_ceil(c) * (n + (2 << c)))
+        .unwrap()
+}
+
+/// `c` bits of a little-endian 256-bit number starting at bit `start`
+pub(crate) fn digit(limbs: &[u64; 4], start: usize, c: usize) -> usize {
+    let limb = start / 64;
+    let offset = start % 64;
+    let mut bits = limbs[limb] >> offset;
+    if offset + c > 64 && limb + 1 < 4 {
+        bits |= limbs[limb + 1] << (64 - offset);
+    }
+    (bits & ((1u64 << c) - 1)) as usize
+}
+
+/// little-endian limbs of the canonical value of `scalar`
+pub(crate) fn limbs(scalar: &Fr) -> [u64; 4] {
+    let bytes = scalar::fr_to_bytes_be(scalar);
+    let mut limbs = [0u64; 4];
+    for (i, limb) in limbs.iter_mut().enumerate() {
+        *limb = u64::from_be_bytes(bytes[24 - 8 * i..32 - 8 * i].try_into().unwrap());
+    }
+    limbs
+}
+
+/// `sum scalars[i] * bases[i]` by bucket accumulation; the same point as multiplying and adding
+/// term by term. Panics if the slices have different lengths.
+pub fn pippenger(scalars: &[Fr], bases: &[P1]) -> P1 {
+    assert_eq!(scalars.len(), bases.len(), "one base per scalar");
+    pippenger_affine(scalars, &to_affine(bases))
+}
+
+/// [`pippenger`] with the bases already converted by [`to_affine`], for callers running many MSMs
+/// over the same bases
+pub(crate) fn pippenger_affine(scalars: &[Fr], bases: &[blst_p1_affine]) -> P1 {
+    assert_eq!(scalars.len(), bases.len(), "one base per scalar");
+    P1::from_raw(accumulate(scalars, bases))
+}
+
+/// the bases in the affine `blst` form the buckets are filled from; this costs a decompression
+/// per point, a good part of a commitment
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn to_affine(bases: &[P1]) -> Vec<blst_p1_affine> {
+    bases.iter().map(group::g1_to_affine).collect()
+}
+
+/// the bases in the affine `blst` form the buckets are filled from, converted across the pool
+#[cfg(feature = "parallel")]
+pub(crate) fn to_affine(bases: &[P1]) -> Vec<blst_p1_affine> {
+    use rayon::prelude::*;
+
+    bases.par_iter().map(group::g1_to_affine).collect()
+}
+
+#[cfg(not(feature = "parallel"))]
+fn accumulate(scalars: &[Fr], bases: &[blst_p1_affine]) -> blst_p1 {
+    accumulate_serial(scalars, bases)
+}
+
+/// one chunk of terms per thread, reduced in chunk order
+#[cfg(feature = "parallel")]
+fn accumulate(scalars: &[Fr], bases: &[blst_p1_affine]) -> blst_p1 {
+    use rayon::prelude::*;
+
+    let threads = rayon::current_num_threads();
+    if scalars.len() < PARALLEL_THRESHOLD || threads == 1 {
+        return accumulate_serial(scalars, bases);
+    }
+
+    let chunk = scalars.len().div_ceil(threads);
+    scalars
+        .par_chunks(chunk)
+        .zip(bases.par_chunks(chunk))
+        .map(|(scalars, bases)| accumulate_serial(scalars, bases))
+        .collect::<Vec<_>>()
+        .iter()
+        .fold(blst_p1::default(), |mut sum, partial| {
+            // two partial sums can be the same point
+            unsafe { blst::blst_p1_add_or_double(&mut sum, &sum, partial) };
+            sum
+        })
+}
+
+fn accumulate_serial(scalars: &[Fr], bases: &[blst_p1_affine]) -> blst_p1 {
+    if scalars.is_empty() {
+        return blst_p1::default();
+    }
+
+    let c = window_bits(scalars.len());
+    let limbs: Vec<[u64; 4]> = scalars.iter().map(limbs).collect();
+    let windows = SCALAR_BITS.div_ceil(c);
+
+    let mut result = blst_p1::default();
+    let mut buckets = vec![blst_p1::default(); (1 << c) - 1];
+    for window in (0..windows).rev() {
+        unsafe {
+            for _ in 0..c {
+                blst::blst_p1_double(&mut result, &result);
+            }
+        }
+
+        buckets.fill(blst_p1::default());
+        for (limbs, base) in limbs.iter().zip(bases) {
+            let digit = digit(limbs, window * c, c);
+            if digit != 0 {
+                let bucket = &mut buckets[digit - 1];
+                unsafe { blst::blst_p1_add_or_double_affine(bucket, bucket, base) };
+            }
+        }
+
+        // sum_d d * bucket_d, as the sum of the running sums from the top bucket down
+        let mut running = blst_p1::default();
+        let mut window_sum = blst_p1::default();
+        for bucket in buckets.iter().rev() {
+            unsafe {
+                blst::blst_p1_add_or_double(&mut running, &running, bucket);
+                blst::blst_p1_add_or_double(&mut window_sum, &window_sum, &running);
+            }
+        }
+        unsafe { blst::blst_p1_add_or_double(&mut result, &result, &window_sum) };
+    }
+
+    result
+}
+
+/// `sum scalars[i] * bases[i]`, term by term below [`PIPPENGER_THRESHOLD`] terms and with
+/// [`pippenger`] from there on
+pub(crate) fn linear_combination(scalars: &[Fr], bases: &[P1]) -> P1 {
+    if scalars.len() < PIPPENGER_THRESHOLD {
+        naive(scalars, bases)
+    } else {
+        pippenger(scalars, bases)
+    }
+}
+
+/// one scalar multiplication per term, for inputs below [`PIPPENGER_THRESHOLD`]. The terms are
+/// summed with `blst`, since a partial sum can equal the next term (e.g. a repeated point)
+pub(crate) fn naive(scalars: &[Fr], bases: &[P1]) -> P1 {
+    let mut sum = blst_p1::default();
+    for (scalar, base) in scalars.iter().zip(bases) {
+        let term = group::g1_to_affine(&(*scalar * *base));
+        unsafe { blst::blst_p1_add_or_double_affine(&mut sum, &sum, &term) };
+    }
+    P1::from_raw(sum)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{RngCore, SeedableRng};
+
+    fn random_scalars(rng: &mut StdRng, n: usize) -> Vec<Fr> {
+        (0..n)
+            .map(|_| {
+                let mut bytes = [0u8; 64];
+                rng.fill_bytes(&mut bytes);
+                scalar::fr_from_bytes_be(&bytes)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn agrees_with_naive_multiplication() {
+        let mut rng = StdRng::seed_from_u64(73);
+        let bases: Vec<P1> = random_scalars(&mut rng, 300).iter().map(|s| *s * P1::generator()).collect();
+
+        for n in [0, 1, 2, 15, 23, 24, 100, 300] {
+            let scalars = random_scalars(&mut rng, n);
+            assert_eq!(pippenger(&scalars, &bases[..n]).compress(), naive(&scalars, &bases[..n]).compress());
+        }
+
+        // zeros, ones, order - 1, a repeated base and the identity
+        let scalars = [Fr::default(), Fr::from_u64(1), -Fr::from_u64(1), Fr::from_u64(5), Fr::from_u64(5), Fr::from_u64(9)];
+        let bases = [bases[0], bases[1], bases[2], bases[3], bases[3], P1::default()];
+        let expected = (Fr::from_u64(1) * bases[1] + -bases[2]) + Fr::from_u64(10) * bases[3];
+        assert_eq!(pippenger(&scalars, &bases).compress(), expected.compress());
+        assert_eq!(naive(&scalars, &bases).compress(), expected.compress());
+
+        // the second term equals the first partial sum, which has to be doubled
+        let doubled = naive(&[Fr::from_u64(1), Fr::from_u64(1)], &[bases[0], bases[0]]);
+        assert_eq!(doubled.compress(), (Fr::from_u64(2) * bases[0]).compress());
+    }
+
+    #[test]
+    fn digits_cover_the_scalar() {
+        let number = [u64::MAX, 1, 0, 1 << 62];
+        assert_eq!(digit(&number, 0, 4), 0xf);
+        assert_eq!(digit(&number, 62, 4), 0b0111);
+        assert_eq!(digit(&number, 252, 4), 0b0100);
+        assert_eq!(limbs(&Fr::from_u64(0x1234))[0], 0x1234);
+        // order - 1 fits in 255 bits
+        assert_eq!(limbs(&-Fr::from_u64(1))[3] >> 63, 0);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn same_result_for_any_number_of_threads() {
+        let mut rng = StdRng::seed_from_u64(75);
+        let n = 3 * PARALLEL_THRESHOLD + 7;
+        let bases: Vec<P1> = random_scalars(&mut rng, 64).iter().map(|s| *s * P1::generator()).collect();
+        // repeated bases, so chunks can end up with equal partial sums
+        let bases: Vec<P1> = (0..n).map(|i| bases[i % 64]).collect();
+        let scalars = random_scalars(&mut rng, n);
+        let serial = P1::from_raw(accumulate_serial(&scalars, &to_affine(&bases))).compress();
+
+        for threads in [1, 2, 3, 4, 8] {
+            let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+            assert_eq!(pool.install(|| pippenger(&scalars, &bases)).compress(), serial, "{} threads", threads);
+        }
+
+        // identical chunks: the partial sums coincide and have to be doubled
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+        let scalars = [&scalars[..PARALLEL_THRESHOLD], &scalars[..PARALLEL_THRESHOLD]].concat();
+        let bases = [&bases[..PARALLEL_THRESHOLD], &bases[..PARALLEL_THRESHOLD]].concat();
+        let half = pippenger(&scalars[..PARALLEL_THRESHOLD], &bases[..PARALLEL_THRESHOLD]);
+        assert_eq!(pool.install(|| pippenger(&scalars, &bases)).compress(), (Fr::from_u64(2) * half).compress());
+    }
+}
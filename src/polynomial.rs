@@ -1,7 +1,20 @@
 use core::fmt;
+use std::ops::{Add, Mul, Sub};
 
+use num_bigint::BigUint;
 pub use oblast_demo::Fr;
 
+use crate::domain::Domain;
+use crate::kzg_commit::KZGErrors;
+use crate::scalar;
+
+/// below this many coefficients in the smaller operand schoolbook multiplication beats
+/// [`Polynomial::mul_fft`] (measured with `cargo bench -- multiplication`)
+pub const FFT_MULTIPLICATION_THRESHOLD: usize = 192;
+/// below this many coefficients in the divisor or the quotient schoolbook division beats
+/// [`Polynomial::div_rem_newton`] (measured with `cargo bench -- division`)
+pub const FAST_DIVISION_THRESHOLD: usize = 512;
+
 /// OBJECTIVEs
 /// 1. Implement a struct Polynomial that represents a polynomial. [Done]
 /// 2. Implement the Display trait for Polynomial so that we can print it out. [Done]
@@ -9,8 +22,10 @@ pub use oblast_demo::Fr;
 /// 4. Implement a method to create a polynomial from a list of coefficients.[Done]
 
 #[derive(Debug, Clone)]
-pub struct Polynomial {
-    pub coefficients: Vec<Fr>,
+pub struct Polynomial<F = Fr> {
+    /// lowest degree first, over `Fr` except when committed through another
+    /// [`PairingBackend`](crate::backend::PairingBackend)
+    pub coefficients: Vec<F>,
 }
 
 impl fmt::Display for Polynomial {
@@ -28,32 +43,422 @@ impl fmt::Display for Polynomial {
     }
 }
 
+/// polynomials are equal when they agree on every coefficient, so trailing zeros don't matter
+impl PartialEq for Polynomial {
+    fn eq(&self, other: &Self) -> bool {
+        let len = self.coefficients.len().max(other.coefficients.len());
+        (0..len).all(|i| {
+            let a = self.coefficients.get(i).copied().unwrap_or_default();
+            let b = other.coefficients.get(i).copied().unwrap_or_default();
+            a == b
+        })
+    }
+}
+
+impl Add<&Polynomial> for &Polynomial {
+    type Output = Polynomial;
+
+    fn add(self, other: &Polynomial) -> Polynomial {
+        let len = self.coefficients.len().max(other.coefficients.len());
+        let coefficients = (0..len)
+            .map(|i| {
+                let a = self.coefficients.get(i).copied().unwrap_or_default();
+                let b = other.coefficients.get(i).copied().unwrap_or_default();
+                a + b
+            })
+            .collect();
+        Polynomial { coefficients }
+    }
+}
+
+impl Sub<&Polynomial> for &Polynomial {
+    type Output = Polynomial;
+
+    fn sub(self, other: &Polynomial) -> Polynomial {
+        let len = self.coefficients.len().max(other.coefficients.len());
+        let coefficients = (0..len)
+            .map(|i| {
+                let a = self.coefficients.get(i).copied().unwrap_or_default();
+                let b = other.coefficients.get(i).copied().unwrap_or_default();
+                a - b
+            })
+            .collect();
+        Polynomial { coefficients }
+    }
+}
+
+impl Mul<&Polynomial> for &Polynomial {
+    type Output = Polynomial;
+
+    // schoolbook multiplication
+    fn mul(self, other: &Polynomial) -> Polynomial {
+        if self.coefficients.is_empty() || other.coefficients.is_empty() {
+            return Polynomial::zero();
+        }
+
+        let mut coefficients = vec![Fr::default(); self.coefficients.len() + other.coefficients.len() - 1];
+        for (i, a) in self.coefficients.iter().enumerate() {
+            for (j, b) in other.coefficients.iter().enumerate() {
+                coefficients[i + j] += *a * *b;
+            }
+        }
+
+        Polynomial { coefficients }
+    }
+}
+
+impl Mul for Polynomial {
+    type Output = Polynomial;
+
+    fn mul(self, other: Polynomial) -> Polynomial {
+        &self * &other
+    }
+}
+
+/// the sum of no polynomials is [`Polynomial::zero`]
+impl std::iter::Sum for Polynomial {
+    fn sum<I: Iterator<Item = Polynomial>>(iter: I) -> Polynomial {
+        iter.fold(Polynomial::zero(), |sum, polynomial| &sum + &polynomial)
+    }
+}
+
+/// the product of no polynomials is [`Polynomial::one`]; multiplying linear factors `(x - a_i)`
+/// this way builds the vanishing polynomial of the `a_i`
+impl std::iter::Product for Polynomial {
+    fn product<I: Iterator<Item = Polynomial>>(iter: I) -> Polynomial {
+        iter.fold(Polynomial::one(), |product, polynomial| product * polynomial)
+    }
+}
+
+impl From<Vec<Fr>> for Polynomial {
+    fn from(coefficients: Vec<Fr>) -> Self {
+        Self::from_coefficients(coefficients)
+    }
+}
+
+impl From<&[u64]> for Polynomial {
+    fn from(coefficients: &[u64]) -> Self {
+        Self::from_coefficients(coefficients.iter().copied().map(Fr::from_u64).collect())
+    }
+}
+
+impl From<Vec<u64>> for Polynomial {
+    fn from(coefficients: Vec<u64>) -> Self {
+        Self::from(coefficients.as_slice())
+    }
+}
+
 impl Polynomial {
+    /// the polynomial `sum coefficients[i] * x^i`: index 0 is the constant term and there are no
+    /// negative powers. An empty vector is the zero polynomial; use
+    /// [`Polynomial::from_coefficients_checked`] where an empty vector means a caller mistake.
     pub fn from_coefficients(coefficients: Vec<Fr>) -> Self {
         Self { coefficients }
     }
 
-    pub fn evalaute(&self, x: Fr) -> Fr {
-        let mut sum = self.coefficients[0].clone();
-        let mut variable = x.clone();
+    /// same as [`Polynomial::from_coefficients`], rejecting an empty vector with
+    /// [`KZGErrors::EmptyCoefficients`] (the zero polynomial can still be given as `[0]`)
+    pub fn from_coefficients_checked(coefficients: Vec<Fr>) -> Result<Self, KZGErrors> {
+        if coefficients.is_empty() {
+            return Err(KZGErrors::EmptyCoefficients);
+        }
+        Ok(Self::from_coefficients(coefficients))
+    }
+
+    /// builds a polynomial from arbitrary non-negative integers, each reduced mod the curve order.
+    /// Reduction cannot fail today; the `Result` leaves room for limits on untrusted input.
+    pub fn from_bigints(values: &[BigUint]) -> Result<Self, KZGErrors> {
+        Ok(Self::from_coefficients(values.iter().map(scalar::fr_from_biguint).collect()))
+    }
+
+    /// builds a polynomial from signed integers; a negative value `-v` becomes `r - v` for the
+    /// curve order `r`
+    pub fn from_i64(values: &[i64]) -> Self {
+        let coefficients = values
+            .iter()
+            .map(|value| {
+                let magnitude = Fr::from_u64(value.unsigned_abs());
+                if *value < 0 {
+                    -magnitude
+                } else {
+                    magnitude
+                }
+            })
+            .collect();
+        Self::from_coefficients(coefficients)
+    }
+
+    /// the zero polynomial (no coefficients)
+    pub fn zero() -> Self {
+        Self { coefficients: vec![] }
+    }
+
+    /// the constant polynomial `1`
+    pub fn one() -> Self {
+        Self::constant(Fr::from_u64(1))
+    }
+
+    /// the constant polynomial `c`
+    pub fn constant(c: Fr) -> Self {
+        Self { coefficients: vec![c] }
+    }
+
+    /// the polynomial `coeff * x^degree`
+    pub fn monomial(degree: usize, coeff: Fr) -> Self {
+        let mut coefficients = vec![Fr::default(); degree + 1];
+        coefficients[degree] = coeff;
+        Self { coefficients }
+    }
 
-        for i in 1..self.coefficients.len() {
-            sum += self.coefficients[i] * variable;
-            variable *= variable;
+    /// the degree of the polynomial, ignoring trailing zero coefficients (`None` for the zero polynomial)
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.iter().rposition(|c| *c != Fr::default())
+    }
+
+    /// true when every coefficient is zero, including for an empty coefficient vector
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.iter().all(|c| *c == Fr::default())
+    }
+
+    pub fn evaluate(&self, x: Fr) -> Fr {
+        // Horner's rule, starting from the highest coefficient
+        let mut sum = Fr::default();
+        for coefficient in self.coefficients.iter().rev() {
+            sum = sum * x + *coefficient;
         }
 
         sum
     }
 
+    /// evaluates at every point of `points`, in order. This is Horner's rule run for all the points
+    /// at once, so each coefficient is read a single time; a remainder tree would be asymptotically
+    /// faster for many points.
+    pub fn evaluate_many(&self, points: &[Fr]) -> Vec<Fr> {
+        let mut sums = vec![Fr::default(); points.len()];
+        for coefficient in self.coefficients.iter().rev() {
+            for (sum, x) in sums.iter_mut().zip(points) {
+                *sum = *sum * *x + *coefficient;
+            }
+        }
+
+        sums
+    }
+
+    /// `self^exp` by exponentiation by squaring; `pow(0)` is the constant `1`
+    pub fn pow(&self, exp: usize) -> Polynomial {
+        let mut result = Polynomial::one();
+        let mut base = self.clone();
+        let mut exp = exp;
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = &result * &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+
+        result
+    }
+
+    /// the composition `self(inner(x))`, by Horner's rule over the coefficients of `self`.
+    ///
+    /// The result has degree `deg(self) * deg(inner)`, so composing even moderate degrees quickly
+    /// produces polynomials too large for a setup.
+    pub fn compose(&self, inner: &Polynomial) -> Polynomial {
+        let mut result = Polynomial::zero();
+        for coefficient in self.coefficients.iter().rev() {
+            result = result.mul_auto(inner).sub_constant(-*coefficient);
+        }
+
+        result
+    }
+
+    /// `self - c`, i.e. `c` subtracted from the constant term (which is created if the polynomial is empty)
+    pub fn sub_constant(&self, c: Fr) -> Polynomial {
+        let mut coefficients = self.coefficients.clone();
+        match coefficients.first_mut() {
+            Some(constant) => *constant = *constant - c,
+            None => coefficients.push(-c),
+        }
+        Polynomial { coefficients }
+    }
+
+    /// `c * self`, every coefficient multiplied by `c`
+    pub fn scale(&self, c: Fr) -> Polynomial {
+        let coefficients = self.coefficients.iter().map(|coefficient| c * *coefficient).collect();
+        Polynomial { coefficients }
+    }
+
+    /// the reciprocal polynomial `x^n * f(1/x)` (`n` the degree): the coefficients in reverse
+    /// order, after trimming trailing zeros. Reversing twice gives back the trimmed polynomial as
+    /// long as its constant term is non-zero.
+    pub fn reverse(&self) -> Polynomial {
+        let len = self.degree().map_or(0, |degree| degree + 1);
+        let coefficients = self.coefficients[..len].iter().rev().copied().collect();
+        Polynomial { coefficients }
+    }
+
+    /// the coefficients `0..=max_degree`, i.e. `self mod x^(max_degree + 1)`; the whole polynomial
+    /// if it is shorter
+    pub fn truncate(&self, max_degree: usize) -> Polynomial {
+        let len = self.coefficients.len().min(max_degree.saturating_add(1));
+        Polynomial::from_coefficients(self.coefficients[..len].to_vec())
+    }
+
+    /// splits into the low `k` coefficients and the rest shifted down to start at `x^0`, so that
+    /// `self = low + x^k * high`. For `k` past the length the high part is zero.
+    pub fn split_at(&self, k: usize) -> (Polynomial, Polynomial) {
+        let (low, high) = self.coefficients.split_at(k.min(self.coefficients.len()));
+        (Polynomial::from_coefficients(low.to_vec()), Polynomial::from_coefficients(high.to_vec()))
+    }
+
+    /// the product with the vanishing polynomial `x^n - 1` of a domain of size `n`, in `O(len + n)`:
+    /// the coefficients shifted up by `n`, minus the coefficients themselves
+    pub fn mul_by_vanishing(&self, domain_size: usize) -> Polynomial {
+        let mut coefficients = vec![Fr::default(); self.coefficients.len() + domain_size];
+        for (i, coefficient) in self.coefficients.iter().enumerate() {
+            coefficients[i + domain_size] += *coefficient;
+            coefficients[i] = coefficients[i] - *coefficient;
+        }
+        Polynomial::from_coefficients(coefficients)
+    }
+
+    /// the product through an FFT over a large enough domain, `O(n log n)`
+    pub fn mul_fft(&self, other: &Polynomial) -> Polynomial {
+        if self.coefficients.is_empty() || other.coefficients.is_empty() {
+            return Polynomial::zero();
+        }
+
+        let len = self.coefficients.len() + other.coefficients.len() - 1;
+        let domain = Domain::new(len.next_power_of_two()).expect("product too large for an FFT domain");
+        let a = domain.fft(&self.coefficients);
+        let b = domain.fft(&other.coefficients);
+        let product: Vec<Fr> = a.iter().zip(&b).map(|(a, b)| *a * *b).collect();
+
+        let mut coefficients = domain.ifft(&product);
+        coefficients.truncate(len);
+        Polynomial { coefficients }
+    }
+
+    /// schoolbook or FFT multiplication, whichever is faster for these sizes
+    fn mul_auto(&self, other: &Polynomial) -> Polynomial {
+        if self.coefficients.len().min(other.coefficients.len()) < FFT_MULTIPLICATION_THRESHOLD {
+            self * other
+        } else {
+            self.mul_fft(other)
+        }
+    }
+
+    /// the first `len` coefficients, i.e. `self mod x^len`
+    fn truncated(&self, len: usize) -> Polynomial {
+        let len = len.min(self.coefficients.len());
+        Polynomial { coefficients: self.coefficients[..len].to_vec() }
+    }
+
+    /// `1 / self mod x^len` by Newton iteration (`g <- g (2 - self g)`, doubling the precision each
+    /// step); the constant term must be non-zero
+    fn inverse_mod_x_pow(&self, len: usize) -> Polynomial {
+        let mut inverse = Polynomial::constant(Fr::from_u64(1) / self.coefficients[0]);
+        let mut precision = 1;
+        while precision < len {
+            precision = (2 * precision).min(len);
+            let error = self.truncated(precision).mul_auto(&inverse).truncated(precision);
+            let correction = &Polynomial::constant(Fr::from_u64(2)) - &error;
+            inverse = inverse.mul_auto(&correction).truncated(precision);
+        }
+        inverse
+    }
+
+    /// quotient and remainder by schoolbook long division, `O(n m)`; the reference for
+    /// [`Polynomial::div_rem`]. Panics if `divisor` is the zero polynomial.
+    pub fn div_rem_naive(&self, divisor: &Polynomial) -> (Polynomial, Polynomial) {
+        let divisor_degree = divisor.degree().expect("division by the zero polynomial");
+        let leading_inverse = Fr::from_u64(1) / divisor.coefficients[divisor_degree];
+
+        let mut remainder = self.coefficients.clone();
+        let dividend_len = self.degree().map_or(0, |degree| degree + 1);
+        remainder.truncate(dividend_len);
+        if dividend_len <= divisor_degree {
+            return (Polynomial::zero(), Polynomial { coefficients: remainder });
+        }
+
+        let mut quotient = vec![Fr::default(); dividend_len - divisor_degree];
+        for i in (0..quotient.len()).rev() {
+            let term = remainder[i + divisor_degree] * leading_inverse;
+            quotient[i] = term;
+            for (j, coefficient) in divisor.coefficients[..=divisor_degree].iter().enumerate() {
+                remainder[i + j] = remainder[i + j] - term * *coefficient;
+            }
+        }
+        remainder.truncate(divisor_degree);
+
+        (Polynomial { coefficients: quotient }, Polynomial { coefficients: remainder })
+    }
+
+    /// quotient and remainder of `self / divisor`: [`Polynomial::div_rem_newton`] for large
+    /// operands and [`Polynomial::div_rem_naive`] below [`FAST_DIVISION_THRESHOLD`].
+    /// Panics if `divisor` is the zero polynomial.
+    pub fn div_rem(&self, divisor: &Polynomial) -> (Polynomial, Polynomial) {
+        let divisor_degree = divisor.degree().expect("division by the zero polynomial");
+        match self.degree() {
+            Some(degree) if degree >= divisor_degree => {
+                let quotient_len = degree - divisor_degree + 1;
+                if quotient_len.min(divisor_degree + 1) < FAST_DIVISION_THRESHOLD {
+                    self.div_rem_naive(divisor)
+                } else {
+                    self.div_rem_newton(divisor)
+                }
+            }
+            _ => self.div_rem_naive(divisor),
+        }
+    }
+
+    /// quotient and remainder by Newton iteration on the reversed polynomials
+    /// (`rev(q) = rev(self) / rev(divisor) mod x^(n-m+1)`) with FFT multiplication, `O(n log n)`.
+    /// Panics if `divisor` is the zero polynomial.
+    pub fn div_rem_newton(&self, divisor: &Polynomial) -> (Polynomial, Polynomial) {
+        let divisor_degree = divisor.degree().expect("division by the zero polynomial");
+        let dividend_degree = match self.degree() {
+            Some(degree) if degree >= divisor_degree => degree,
+            _ => return self.div_rem_naive(divisor),
+        };
+        let quotient_len = dividend_degree - divisor_degree + 1;
+
+        let reversed_divisor = divisor.reverse();
+        let reversed_quotient = self
+            .reverse()
+            .truncated(quotient_len)
+            .mul_auto(&reversed_divisor.inverse_mod_x_pow(quotient_len))
+            .truncated(quotient_len);
+
+        // reverse back as a polynomial with exactly `quotient_len` coefficients
+        let mut coefficients = reversed_quotient.coefficients;
+        coefficients.resize(quotient_len, Fr::default());
+        coefficients.reverse();
+        let quotient = Polynomial { coefficients };
+
+        let mut remainder = self - &divisor.mul_auto(&quotient);
+        remainder.coefficients.truncate(divisor_degree);
+
+        (quotient, remainder)
+    }
+
+    /// original (misspelled) name of [`Polynomial::evaluate`], kept so existing callers keep compiling
+    pub fn evalaute(&self, x: Fr) -> Fr {
+        self.evaluate(x)
+    }
+
 
 }
 
 
 #[cfg(test)]
 mod tests {
-    use std::fmt::Debug;
-
     use super::*;
+    use rand::SeedableRng;
 
     #[test]
     fn evaluate_test() {
@@ -62,4 +467,327 @@ mod tests {
 
         assert_eq!(eval, Fr::from_u64(15));
     }
+
+    #[test]
+    fn evaluate_higher_degree_test() {
+        // 1 + 2x + 3x^2 + 4x^3 at x = 2 -> 1 + 4 + 12 + 32
+        let polynomial = Polynomial::from_coefficients(vec![Fr::from_u64(1), Fr::from_u64(2), Fr::from_u64(3), Fr::from_u64(4)]);
+        let eval = polynomial.evalaute(Fr::from_u64(2));
+
+        assert_eq!(eval, Fr::from_u64(49));
+    }
+
+    #[test]
+    fn evaluate_many_test() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(331);
+        let polynomial = random_polynomial(&mut rng, 20);
+        let points: Vec<Fr> = (0..7u64).map(|i| Fr::from_u64(i * i + 3)).chain([-Fr::from_u64(1)]).collect();
+
+        let evaluations = polynomial.evaluate_many(&points);
+        assert_eq!(evaluations.len(), points.len());
+        for (point, evaluation) in points.iter().zip(&evaluations) {
+            assert_eq!(*evaluation, polynomial.evalaute(*point));
+        }
+
+        assert!(polynomial.evaluate_many(&[]).is_empty());
+        assert_eq!(Polynomial::zero().evaluate_many(&points), vec![Fr::default(); points.len()]);
+    }
+
+    #[test]
+    fn from_u64_test() {
+        let manual = Polynomial::from_coefficients(vec![1, 2, 3].into_iter().map(Fr::from_u64).collect());
+        let converted = Polynomial::from(vec![1u64, 2, 3]);
+        let from_slice: Polynomial = [1u64, 2, 3][..].into();
+
+        let x = Fr::from_u64(9);
+        assert_eq!(converted.evalaute(x), manual.evalaute(x));
+        assert_eq!(from_slice.coefficients, manual.coefficients);
+        assert_eq!(Polynomial::from(manual.coefficients.clone()).coefficients, manual.coefficients);
+    }
+
+    #[test]
+    fn from_integers_test() {
+        use oblast_demo::curve_order;
+
+        // -3 + 2x evaluates to 1 at x = 2 and to -5 at x = -1
+        let signed = Polynomial::from_i64(&[-3, 2]);
+        assert_eq!(signed.evaluate(Fr::from_u64(2)), Fr::from_u64(1));
+        assert_eq!(signed.evaluate(-Fr::from_u64(1)), -Fr::from_u64(5));
+        assert_eq!(Polynomial::from_i64(&[i64::MIN]).coefficients, vec![-Fr::from_u64(1 << 63)]);
+
+        // r + 5 and 2r reduce to 5 and 0
+        let order = curve_order();
+        let big = Polynomial::from_bigints(&[&order + 5u32, BigUint::from(7u32), &order * 2u32]).unwrap();
+        assert_eq!(big, Polynomial::from(vec![5u64, 7]));
+
+        // both agree with the Fr-based constructor on small positive values
+        let small = Polynomial::from(vec![1u64, 2, 3]);
+        assert_eq!(Polynomial::from_i64(&[1, 2, 3]), small);
+        let as_bigints = [1u32, 2, 3].map(BigUint::from);
+        assert_eq!(Polynomial::from_bigints(&as_bigints).unwrap(), small);
+    }
+
+    #[test]
+    fn checked_constructor_test() {
+        assert!(matches!(Polynomial::from_coefficients_checked(vec![]), Err(KZGErrors::EmptyCoefficients)));
+        let p = Polynomial::from_coefficients_checked(vec![Fr::from_u64(4), Fr::from_u64(2)]).unwrap();
+        assert_eq!(p, Polynomial::from(vec![4u64, 2]));
+        assert!(Polynomial::from_coefficients_checked(vec![Fr::default()]).unwrap().is_zero());
+    }
+
+    #[test]
+    fn constructors_test() {
+        let x = Fr::from_u64(12345);
+        assert_eq!(Polynomial::one().evaluate(x), Fr::from_u64(1));
+        assert_eq!(Polynomial::constant(Fr::from_u64(7)).evaluate(x), Fr::from_u64(7));
+        assert_eq!(Polynomial::zero().evaluate(x), Fr::default());
+
+        let monomial = Polynomial::monomial(3, Fr::from_u64(2));
+        assert_eq!(monomial.evaluate(Fr::from_u64(2)), Fr::from_u64(16));
+        assert_eq!(monomial.degree(), Some(3));
+    }
+
+    #[test]
+    fn degree_test() {
+        assert_eq!(Polynomial::zero().degree(), None);
+        assert_eq!(Polynomial::from(vec![0u64, 0]).degree(), None);
+        assert_eq!(Polynomial::one().degree(), Some(0));
+        assert_eq!(Polynomial::from(vec![1u64, 2, 0, 0]).degree(), Some(1));
+    }
+
+    #[test]
+    fn is_zero_test() {
+        assert!(Polynomial::zero().is_zero());
+        assert!(Polynomial::from_coefficients(vec![]).is_zero());
+        assert!(Polynomial::from(vec![0u64, 0, 0]).is_zero());
+        assert!(!Polynomial::from(vec![0u64, 0, 1]).is_zero());
+        assert!(!Polynomial::one().is_zero());
+    }
+
+    #[test]
+    fn mul_test() {
+        // (1 + x)(2 + 3x) = 2 + 5x + 3x^2
+        let product = Polynomial::from(vec![1u64, 1]) * Polynomial::from(vec![2u64, 3]);
+        assert_eq!(product, Polynomial::from(vec![2u64, 5, 3]));
+        assert_eq!(&product * &Polynomial::zero(), Polynomial::zero());
+    }
+
+    #[test]
+    fn compose_test() {
+        // (x + 1)^2 = x^2 + 2x + 1
+        let square = Polynomial::from(vec![0u64, 0, 1]);
+        let shift = Polynomial::from(vec![1u64, 1]);
+        assert_eq!(square.compose(&shift), Polynomial::from(vec![1u64, 2, 1]));
+
+        let f = Polynomial::from(vec![3u64, 0, 2, 7]);
+        let g = Polynomial::from(vec![5u64, 4, 1]);
+        let composed = f.compose(&g);
+        assert_eq!(composed.degree(), Some(6));
+        let x = Fr::from_u64(11);
+        assert_eq!(composed.evaluate(x), f.evaluate(g.evaluate(x)));
+
+        assert!(Polynomial::zero().compose(&g).is_zero());
+        assert_eq!(f.compose(&Polynomial::constant(Fr::from_u64(2))), Polynomial::constant(f.evaluate(Fr::from_u64(2))));
+    }
+
+    #[test]
+    fn sum_and_product_test() {
+        let p1 = Polynomial::from(vec![1u64, 1]);
+        let p2 = Polynomial::from(vec![2u64, 3]);
+        let p3 = Polynomial::from(vec![4u64, 0, 5]);
+
+        let product: Polynomial = vec![p1.clone(), p2.clone(), p3.clone()].into_iter().product();
+        assert_eq!(product, p1.clone() * p2.clone() * p3.clone());
+        let sum: Polynomial = vec![p1.clone(), p2.clone(), p3.clone()].into_iter().sum();
+        assert_eq!(sum, &(&p1 + &p2) + &p3);
+
+        assert_eq!(std::iter::empty::<Polynomial>().sum::<Polynomial>(), Polynomial::zero());
+        assert_eq!(std::iter::empty::<Polynomial>().product::<Polynomial>(), Polynomial::one());
+
+        // the vanishing polynomial of 1, 2, 3
+        let roots = [1u64, 2, 3].map(Fr::from_u64);
+        let vanishing: Polynomial = roots
+            .iter()
+            .map(|root| Polynomial::from_coefficients(vec![-*root, Fr::from_u64(1)]))
+            .product();
+        assert_eq!(vanishing.degree(), Some(3));
+        assert!(roots.iter().all(|root| vanishing.evaluate(*root) == Fr::default()));
+    }
+
+    #[test]
+    fn eq_ignores_trailing_zeros() {
+        assert_eq!(Polynomial::from(vec![1u64, 2, 0, 0]), Polynomial::from(vec![1u64, 2]));
+        assert_eq!(Polynomial::zero(), Polynomial::from(vec![0u64]));
+        assert_ne!(Polynomial::from(vec![1u64, 2]), Polynomial::from(vec![1u64, 2, 3]));
+    }
+
+    #[test]
+    fn sub_constant_test() {
+        let p = Polynomial::from(vec![5u64, 0, 3, 1]);
+        let z = Fr::from_u64(11);
+        assert_eq!(p.sub_constant(p.evaluate(z)).evaluate(z), Fr::default());
+        assert_eq!(p.sub_constant(Fr::from_u64(2)), Polynomial::from(vec![3u64, 0, 3, 1]));
+        assert_eq!(Polynomial::zero().sub_constant(Fr::from_u64(4)), Polynomial::constant(-Fr::from_u64(4)));
+    }
+
+    #[test]
+    fn reverse_test() {
+        let p = Polynomial::from(vec![1u64, 2, 3]);
+        assert_eq!(p.reverse().coefficients, Polynomial::from(vec![3u64, 2, 1]).coefficients);
+
+        let padded = Polynomial::from(vec![1u64, 4, 5, 0, 0]);
+        assert_eq!(padded.reverse().coefficients, Polynomial::from(vec![5u64, 4, 1]).coefficients);
+        assert_eq!(padded.reverse().reverse().coefficients, Polynomial::from(vec![1u64, 4, 5]).coefficients);
+
+        // a zero constant term becomes a trailing zero, so it's gone after a second reverse
+        let divisible_by_x = Polynomial::from(vec![0u64, 4, 5]);
+        assert_eq!(divisible_by_x.reverse().coefficients, Polynomial::from(vec![5u64, 4, 0]).coefficients);
+        assert_eq!(divisible_by_x.reverse().reverse().coefficients, Polynomial::from(vec![4u64, 5]).coefficients);
+        assert_eq!(p.reverse().reverse().coefficients, p.coefficients);
+        assert!(Polynomial::zero().reverse().coefficients.is_empty());
+
+        // x^n * f(1/x)
+        let x = Fr::from_u64(3);
+        let x_inverse = Fr::from_u64(1) / x;
+        assert_eq!(p.reverse().evaluate(x), x * x * p.evaluate(x_inverse));
+    }
+
+    #[test]
+    fn scale_test() {
+        let p = Polynomial::from(vec![1u64, 2, 3]);
+        assert_eq!(p.scale(Fr::from_u64(3)), Polynomial::from(vec![3u64, 6, 9]));
+        assert!(p.scale(Fr::default()).is_zero());
+        assert_eq!(p.scale(Fr::from_u64(1)), p);
+        let x = Fr::from_u64(19);
+        assert_eq!(p.scale(-Fr::from_u64(5)).evaluate(x), -Fr::from_u64(5) * p.evaluate(x));
+    }
+
+    #[test]
+    fn truncate_test() {
+        let p = Polynomial::from(vec![1u64, 2, 3, 4]);
+        assert_eq!(p.truncate(0), Polynomial::constant(Fr::from_u64(1)));
+        assert_eq!(p.truncate(1), Polynomial::from(vec![1u64, 2]));
+        assert_eq!(p.truncate(3), p);
+        assert_eq!(p.truncate(10).coefficients, p.coefficients);
+        assert_eq!(p.truncate(usize::MAX), p);
+        assert!(Polynomial::zero().truncate(5).is_zero());
+    }
+
+    #[test]
+    fn mul_by_vanishing_test() {
+        let vanishing = |n: usize| {
+            let mut coefficients = vec![Fr::default(); n + 1];
+            coefficients[0] = -Fr::from_u64(1);
+            coefficients[n] += Fr::from_u64(1);
+            Polynomial::from_coefficients(coefficients)
+        };
+
+        let p = Polynomial::from(vec![3u64, 1, 4, 1, 5, 9]);
+        // n below, at and above the length of p, where the shifted part overlaps or doesn't
+        for n in [1, 2, 4, 6, 8] {
+            assert_eq!(p.mul_by_vanishing(n), &p * &vanishing(n), "{}", n);
+        }
+
+        // x^0 - 1 is zero, as is the product of zero with anything
+        assert_eq!(p.mul_by_vanishing(0), Polynomial::zero());
+        assert_eq!(Polynomial::zero().mul_by_vanishing(4), Polynomial::zero());
+    }
+
+    #[test]
+    fn split_at_test() {
+        let p = Polynomial::from(vec![1u64, 2, 3, 4, 5]);
+        let (low, high) = p.split_at(2);
+        assert_eq!(low.coefficients, Polynomial::from(vec![1u64, 2]).coefficients);
+        assert_eq!(high.coefficients, Polynomial::from(vec![3u64, 4, 5]).coefficients);
+
+        // low + x^k * high
+        let shifted = &Polynomial::monomial(2, Fr::from_u64(1)) * &high;
+        assert_eq!(&low + &shifted, p);
+
+        // at the boundaries and beyond the length
+        let (low, high) = p.split_at(0);
+        assert!(low.coefficients.is_empty());
+        assert_eq!(high, p);
+        let (low, high) = p.split_at(5);
+        assert_eq!(low, p);
+        assert!(high.coefficients.is_empty());
+        let (low, high) = p.split_at(9);
+        assert_eq!(low, p);
+        assert!(high.is_zero());
+    }
+
+    /// pseudo-random field elements from a seeded generator
+    fn random_polynomial(rng: &mut impl rand::RngCore, len: usize) -> Polynomial {
+        let coefficients = (0..len)
+            .map(|_| crate::scalar::fr_from_u64_limbs(&[rng.next_u64(), rng.next_u64(), rng.next_u64(), rng.next_u64()]))
+            .collect();
+        Polynomial { coefficients }
+    }
+
+    #[test]
+    fn add_sub_test() {
+        let a = Polynomial::from(vec![1u64, 2, 3]);
+        let b = Polynomial::from(vec![5u64, 7]);
+        assert_eq!(&a + &b, Polynomial::from(vec![6u64, 9, 3]));
+        assert_eq!(&(&a + &b) - &b, a);
+        assert_eq!(&a - &a, Polynomial::zero());
+    }
+
+    #[test]
+    fn mul_fft_matches_schoolbook() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(32);
+        for (len_a, len_b) in [(1, 1), (3, 5), (64, 64), (100, 37)] {
+            let a = random_polynomial(&mut rng, len_a);
+            let b = random_polynomial(&mut rng, len_b);
+            assert_eq!(a.mul_fft(&b), &a * &b);
+        }
+        assert_eq!(Polynomial::zero().mul_fft(&Polynomial::one()), Polynomial::zero());
+    }
+
+    #[test]
+    fn div_rem_test() {
+        // x^3 + 2x + 5 = (x - 1)(x^2 + x + 3) + 8
+        let dividend = Polynomial::from(vec![5u64, 2, 0, 1]);
+        let divisor = Polynomial::from_coefficients(vec![-Fr::from_u64(1), Fr::from_u64(1)]);
+        let (quotient, remainder) = dividend.div_rem_naive(&divisor);
+        assert_eq!(quotient, Polynomial::from(vec![3u64, 1, 1]));
+        assert_eq!(remainder, Polynomial::constant(Fr::from_u64(8)));
+
+        // a divisor of higher degree leaves the dividend as the remainder
+        let (quotient, remainder) = divisor.div_rem(&dividend);
+        assert_eq!(quotient, Polynomial::zero());
+        assert_eq!(remainder, divisor);
+    }
+
+    #[test]
+    fn fast_division_matches_naive() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(256);
+        for divisor_len in [2, 17, 64, 100, 190, 257] {
+            // degree 256, the divisor from linear up to the same degree
+            let dividend = random_polynomial(&mut rng, 257);
+            let divisor = random_polynomial(&mut rng, divisor_len);
+
+            let (expected_quotient, expected_remainder) = dividend.div_rem_naive(&divisor);
+            for (quotient, remainder) in [dividend.div_rem(&divisor), dividend.div_rem_newton(&divisor)] {
+                assert_eq!(quotient, expected_quotient);
+                assert_eq!(remainder, expected_remainder);
+            }
+            assert_eq!(&(&divisor * &expected_quotient) + &expected_remainder, dividend);
+        }
+    }
+
+    #[test]
+    fn pow_test() {
+        let x_plus_one = Polynomial::from(vec![1u64, 1]);
+        assert_eq!(x_plus_one.pow(2), Polynomial::from(vec![1u64, 2, 1]));
+        assert_eq!(x_plus_one.pow(0), Polynomial::one());
+        assert_eq!(Polynomial::from(vec![3u64, 0, 7]).pow(0), Polynomial::one());
+
+        let p = Polynomial::from(vec![2u64, 0, 5]);
+        let mut expected = Polynomial::one();
+        for _ in 0..5 {
+            expected = &expected * &p;
+        }
+        assert_eq!(p.pow(5), expected);
+    }
 }
@@ -0,0 +1,238 @@
+//! Conversions between `Fr` and bytes / big integers.
+//!
+//! `oblast_demo` only exposes `Fr::from_u64` and `Fr::as_u64`, so wider values are assembled from
+//! (and split into) 64-bit limbs with field arithmetic.
+
+use num_bigint::BigUint;
+use oblast_demo::{curve_order, Fr};
+
+use crate::kzg_commit::KZGErrors;
+
+/// Byte conversions for `Fr`, for turning external input into field elements.
+///
+/// ```
+/// use kzg_impl::polynomial::Fr;
+/// use kzg_impl::scalar::FrBytes;
+///
+/// let mut bytes = [0u8; 32];
+/// bytes[31] = 42;
+/// assert_eq!(Fr::from_bytes_checked(&bytes).unwrap(), Fr::from_u64(42));
+/// assert!(Fr::from_bytes_checked(&[0xff; 32]).is_err());
+/// ```
+pub trait FrBytes: Sized {
+    /// parses a 32-byte big-endian encoding, rejecting values that are not below the curve order
+    /// (rather than silently reducing them)
+    fn from_bytes_checked(bytes: &[u8; 32]) -> Result<Self, KZGErrors>;
+
+    /// the canonical 32-byte big-endian encoding
+    fn to_bytes_be(&self) -> [u8; 32];
+}
+
+impl FrBytes for Fr {
+    fn from_bytes_checked(bytes: &[u8; 32]) -> Result<Self, KZGErrors> {
+        let value = BigUint::from_bytes_be(bytes);
+        if value >= curve_order() {
+            return Err(KZGErrors::NonCanonicalScalar);
+        }
+
+        Ok(fr_from_biguint(&value))
+    }
+
+    fn to_bytes_be(&self) -> [u8; 32] {
+        fr_to_bytes_be(self)
+    }
+}
+
+/// `2^64` as a field element
+fn two_pow_64() -> Fr {
+    Fr::from_u64(u64::MAX) + Fr::from_u64(1)
+}
+
+/// builds `sum(limbs[i] * 2^(64 * i))` reduced mod the curve order (limbs are little-endian)
+pub(crate) fn fr_from_u64_limbs(limbs: &[u64]) -> Fr {
+    let shift = two_pow_64();
+    let mut result = Fr::default();
+    for limb in limbs.iter().rev() {
+        result = result * shift + Fr::from_u64(*limb);
+    }
+    result
+}
+
+/// the 512-bit big-endian integer `bytes` mod the curve order, for turning hash output into a
+/// field element. Reducing 512 bits biases the result by less than `2^-256`, where reducing a
+/// 256-bit hash makes part of the field one and a half times as likely as the rest.
+///
+/// Unlike [`Fr::from_bytes_checked`] or a rejection loop, this never branches on the input: the
+/// eight limbs always go through the same field multiplications and additions, which `blst` runs
+/// in constant time.
+///
+/// ```
+/// use kzg_impl::polynomial::Fr;
+/// use kzg_impl::scalar::reduce_mod_order;
+///
+/// let mut bytes = [0u8; 64];
+/// bytes[63] = 42;
+/// assert_eq!(reduce_mod_order(&bytes), Fr::from_u64(42));
+/// ```
+pub fn reduce_mod_order(bytes: &[u8; 64]) -> Fr {
+    let mut limbs = [0u64; 8];
+    for (i, limb) in limbs.iter_mut().enumerate() {
+        *limb = u64::from_be_bytes(bytes[56 - 8 * i..64 - 8 * i].try_into().unwrap());
+    }
+    fr_from_u64_limbs(&limbs)
+}
+
+/// interprets big-endian bytes as an integer and reduces it mod the curve order
+pub(crate) fn fr_from_bytes_be(bytes: &[u8]) -> Fr {
+    fr_from_biguint(&BigUint::from_bytes_be(bytes))
+}
+
+/// reduces `value` mod the curve order
+pub(crate) fn fr_from_biguint(value: &BigUint) -> Fr {
+    let limbs = value.to_u64_digits();
+    fr_from_u64_limbs(&limbs)
+}
+
+/// `base^exponent` by square-and-multiply over the bits of `exponent`
+pub(crate) fn fr_pow(base: Fr, exponent: &BigUint) -> Fr {
+    let mut result = Fr::from_u64(1);
+    for byte in exponent.to_bytes_be() {
+        for bit in (0..8).rev() {
+            result *= result;
+            if (byte >> bit) & 1 == 1 {
+                result *= base;
+            }
+        }
+    }
+    result
+}
+
+/// the canonical 32-byte big-endian encoding of `value`
+pub(crate) fn fr_to_bytes_be(value: &Fr) -> [u8; 32] {
+    // peel off the low limb, then divide the (exact) remainder by 2^64
+    let inverse_shift = Fr::from_u64(1) / two_pow_64();
+    let mut rest = *value;
+    let mut bytes = [0u8; 32];
+
+    for i in 0..4 {
+        let limb = rest.as_u64();
+        bytes[24 - 8 * i..32 - 8 * i].copy_from_slice(&limb.to_be_bytes());
+        rest = (rest - Fr::from_u64(limb)) * inverse_shift;
+    }
+
+    bytes
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_small_values() {
+        for value in [0u64, 1, 2, 255, u64::MAX] {
+            let fr = Fr::from_u64(value);
+            let bytes = fr_to_bytes_be(&fr);
+
+            assert_eq!(bytes[24..], value.to_be_bytes());
+            assert_eq!(fr_from_bytes_be(&bytes), fr);
+        }
+    }
+
+    #[test]
+    fn round_trip_order_minus_one() {
+        let order_minus_one = curve_order() - BigUint::from(1u32);
+        let fr = fr_from_biguint(&order_minus_one);
+
+        assert_eq!(fr, -Fr::from_u64(1));
+        assert_eq!(BigUint::from_bytes_be(&fr_to_bytes_be(&fr)), order_minus_one);
+    }
+
+    #[test]
+    fn reduces_mod_order() {
+        let order_plus_five = curve_order() + BigUint::from(5u32);
+        assert_eq!(fr_from_biguint(&order_plus_five), Fr::from_u64(5));
+    }
+
+    #[test]
+    fn pow_matches_repeated_multiplication() {
+        let base = Fr::from_u64(7);
+        let mut expected = Fr::from_u64(1);
+        for exponent in 0u32..40 {
+            assert_eq!(fr_pow(base, &BigUint::from(exponent)), expected);
+            expected *= base;
+        }
+
+        // Fermat: a^(r-1) = 1
+        let order_minus_one = curve_order() - BigUint::from(1u32);
+        assert_eq!(fr_pow(Fr::from_u64(123456), &order_minus_one), Fr::from_u64(1));
+    }
+
+    #[test]
+    fn wide_reduction_matches_biguint() {
+        let order = curve_order();
+        let wide = |value: &BigUint| -> [u8; 64] {
+            let bytes = value.to_bytes_be();
+            let mut wide = [0u8; 64];
+            wide[64 - bytes.len()..].copy_from_slice(&bytes);
+            wide
+        };
+        let inputs = [
+            BigUint::default(),
+            BigUint::from(1u32),
+            order.clone() - BigUint::from(1u32),
+            order.clone(),
+            order.clone() * BigUint::from(12345u32) + BigUint::from(6789u32),
+            &order * &order,
+            (BigUint::from(1u32) << 512) - BigUint::from(1u32),
+            BigUint::from(0x0123456789abcdefu64) << 400,
+        ];
+        for input in inputs {
+            assert_eq!(reduce_mod_order(&wide(&input)), fr_from_biguint(&(&input % &order)), "{:x}", input);
+        }
+    }
+
+    #[test]
+    fn wide_reduction_is_uniform() {
+        use rand::rngs::StdRng;
+        use rand::{RngCore, SeedableRng};
+
+        // the low 4 bits of reduced random inputs fill 16 buckets evenly: 256 expected per bucket,
+        // with a standard deviation of 15
+        let mut rng = StdRng::seed_from_u64(355);
+        let mut buckets = [0u32; 16];
+        for _ in 0..4096 {
+            let mut bytes = [0u8; 64];
+            rng.fill_bytes(&mut bytes);
+            buckets[(fr_to_bytes_be(&reduce_mod_order(&bytes))[31] & 0xf) as usize] += 1;
+        }
+        assert!(buckets.iter().all(|count| (176..=336).contains(count)), "{:?}", buckets);
+
+        // and the top bit is set for about as many as its share of [0, order)
+        let mut high = 0;
+        for _ in 0..4096 {
+            let mut bytes = [0u8; 64];
+            rng.fill_bytes(&mut bytes);
+            high += (fr_to_bytes_be(&reduce_mod_order(&bytes))[0] >= 0x40) as u32;
+        }
+        // values from 2^254 to the order are (order - 2^254) / order ~ 0.45 of the field
+        assert!((1650..=2050).contains(&high), "{}", high);
+    }
+
+    #[test]
+    fn checked_accepts_canonical() {
+        let value = -Fr::from_u64(7);
+        let bytes = value.to_bytes_be();
+        assert_eq!(Fr::from_bytes_checked(&bytes).unwrap(), value);
+    }
+
+    #[test]
+    fn checked_rejects_over_order() {
+        let mut bytes = [0u8; 32];
+        let order = curve_order().to_bytes_be();
+        bytes[32 - order.len()..].copy_from_slice(&order);
+
+        assert!(matches!(Fr::from_bytes_checked(&bytes), Err(KZGErrors::NonCanonicalScalar)));
+        assert!(matches!(Fr::from_bytes_checked(&[0xff; 32]), Err(KZGErrors::NonCanonicalScalar)));
+    }
+}
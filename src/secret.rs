@@ -0,0 +1,75 @@
+//! Handling of setup secrets ("toxic waste").
+//!
+//! With the default `zeroize` feature the buffers holding tau are wiped when they are dropped.
+//! Some copies cannot be cleared because the backend types don't expose their memory:
+//! - `BigUint` values (`num-bigint` has no zeroize support and its digits are private),
+//! - `Scalar`/`Fr` values from `oblast_demo` (their `blst` limbs are private).
+//!
+//! Those live only for the duration of the setup call.
+
+#[cfg(feature = "zeroize")]
+pub(crate) use zeroize::Zeroizing;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// stand-in for `zeroize::Zeroizing` when the `zeroize` feature is disabled: same API, no wiping
+#[cfg(not(feature = "zeroize"))]
+pub(crate) struct Zeroizing<T>(T);
+
+#[cfg(not(feature = "zeroize"))]
+impl<T> Zeroizing<T> {
+    pub(crate) fn new(value: T) -> Self {
+        Zeroizing(value)
+    }
+}
+
+#[cfg(not(feature = "zeroize"))]
+impl<T> std::ops::Deref for Zeroizing<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+#[cfg(not(feature = "zeroize"))]
+impl<T> std::ops::DerefMut for Zeroizing<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+/// 32 secret bytes that are wiped on drop
+pub(crate) type SecretBytes = Zeroizing<[u8; 32]>;
+
+/// clear a buffer that held secret-derived limbs (no-op without the `zeroize` feature)
+pub(crate) fn wipe(limbs: &mut [u64]) {
+    #[cfg(feature = "zeroize")]
+    limbs.zeroize();
+
+    #[cfg(not(feature = "zeroize"))]
+    let _ = limbs;
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secret_bytes_deref() {
+        let mut secret = SecretBytes::new([1u8; 32]);
+        secret[0] = 9;
+        assert_eq!(secret[0], 9);
+        assert_eq!(secret[1..], [1u8; 31]);
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn wipe_clears_buffer() {
+        let mut buffer = vec![0xabu64; 4];
+        wipe(&mut buffer);
+        assert!(buffer.iter().all(|b| *b == 0));
+    }
+}
@@ -0,0 +1,474 @@
+//! `serde` support (behind the `serde` feature).
+//!
+//! Points are written in their compressed form: as a hex string for human-readable formats
+//! (JSON, TOML, ...) and as raw bytes otherwise (bincode, ...). Deserializing decompresses every
+//! point, so invalid or out-of-subgroup points are rejected. Scalars are written the same way as
+//! their 32 big-endian bytes, which must be below the curve order.
+
+use std::fmt;
+
+use oblast_demo::{Fr, P1, P2};
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::domain::Domain;
+use crate::group::{self, G1_COMPRESSED_SIZE, G2_COMPRESSED_SIZE};
+use crate::kzg_commit::{self, CommitmentPoint, Opening, KZG, PP};
+use crate::lagrange::LagrangePP;
+use crate::scalar::FrBytes;
+
+fn serialize_compressed<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&hex::encode(bytes))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+/// accepts a hex string, a byte string or a sequence of bytes
+struct CompressedVisitor;
+
+impl<'de> Visitor<'de> for CompressedVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a compressed curve point as hex or bytes")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Vec<u8>, E> {
+        let value = value.strip_prefix("0x").unwrap_or(value);
+        hex::decode(value).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Vec<u8>, E> {
+        Ok(value.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, value: Vec<u8>) -> Result<Vec<u8>, E> {
+        Ok(value)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        let mut bytes = Vec::new();
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(bytes)
+    }
+}
+
+fn deserialize_compressed<'de, D: Deserializer<'de>, const N: usize>(deserializer: D) -> Result<[u8; N], D::Error> {
+    let bytes = if deserializer.is_human_readable() {
+        deserializer.deserialize_str(CompressedVisitor)?
+    } else {
+        deserializer.deserialize_bytes(CompressedVisitor)?
+    };
+
+    let length = bytes.len();
+    bytes
+        .try_into()
+        .map_err(|_| de::Error::invalid_length(length, &"a compressed curve point"))
+}
+
+pub(crate) struct G1Point(pub(crate) P1);
+
+impl Serialize for G1Point {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_compressed(&self.0.compress(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for G1Point {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = deserialize_compressed::<D, G1_COMPRESSED_SIZE>(deserializer)?;
+        group::decompress_g1(&bytes)
+            .map(G1Point)
+            .map_err(|error| de::Error::custom(format!("invalid G1 point: {:?}", error)))
+    }
+}
+
+pub(crate) struct G2Point(pub(crate) P2);
+
+impl Serialize for G2Point {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_compressed(&self.0.compress(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for G2Point {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = deserialize_compressed::<D, G2_COMPRESSED_SIZE>(deserializer)?;
+        group::decompress_g2(&bytes)
+            .map(G2Point)
+            .map_err(|error| de::Error::custom(format!("invalid G2 point: {:?}", error)))
+    }
+}
+
+/// serializes borrowed G1 points as a sequence of [`G1Point`]s, without copying them into one
+struct G1Points<'a>(&'a [P1]);
+
+impl Serialize for G1Points<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().copied().map(G1Point))
+    }
+}
+
+/// same as [`G1Points`] for G2
+struct G2Points<'a>(&'a [P2]);
+
+impl Serialize for G2Points<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().copied().map(G2Point))
+    }
+}
+
+struct Scalar(Fr);
+
+impl Serialize for Scalar {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_compressed(&self.0.to_bytes_be(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Scalar {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = deserialize_compressed::<D, 32>(deserializer)?;
+        Fr::from_bytes_checked(&bytes)
+            .map(Scalar)
+            .map_err(|_| de::Error::custom("scalar is not below the curve order"))
+    }
+}
+
+/// the serialized shape of `PP`
+#[derive(Deserialize)]
+#[serde(rename = "PP")]
+struct PPRepr {
+    max_degree: usize,
+    points_in_g1: Vec<G1Point>,
+    points_in_g2: Vec<G2Point>,
+}
+
+/// [`PPRepr`] borrowing the points, for serializing
+#[derive(Serialize)]
+#[serde(rename = "PP")]
+struct PPReprRef<'a> {
+    max_degree: usize,
+    points_in_g1: G1Points<'a>,
+    points_in_g2: G2Points<'a>,
+}
+
+impl Serialize for PP {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PPReprRef {
+            max_degree: self.max_degree(),
+            points_in_g1: G1Points(&self.points_in_g1),
+            points_in_g2: G2Points(&self.points_in_g2),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PP {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = PPRepr::deserialize(deserializer)?;
+        if repr.points_in_g1.is_empty() {
+            return Err(de::Error::invalid_length(0, &"at least one G1 point"));
+        }
+        if repr.points_in_g2.len() < 2 {
+            return Err(de::Error::invalid_length(repr.points_in_g2.len(), &"at least two G2 points"));
+        }
+        if repr.max_degree != repr.points_in_g1.len() - 1 {
+            return Err(de::Error::custom(format!(
+                "max_degree {} doesn't match {} G1 points",
+                repr.max_degree,
+                repr.points_in_g1.len()
+            )));
+        }
+
+        let points_in_g1: Vec<P1> = repr.points_in_g1.into_iter().map(|point| point.0).collect();
+        kzg_commit::check_generator_slot(&points_in_g1).map_err(|_| de::Error::custom("the first G1 point is the identity"))?;
+
+        Ok(PP::from_parts(points_in_g1, repr.points_in_g2.into_iter().map(|point| point.0).collect()))
+    }
+}
+
+/// the serialized shape of `LagrangePP`
+#[derive(Deserialize)]
+#[serde(rename = "LagrangePP")]
+struct LagrangePPRepr {
+    domain_size: usize,
+    points_in_g1: Vec<G1Point>,
+    points_in_g2: Vec<G2Point>,
+}
+
+/// [`LagrangePPRepr`] borrowing the points, for serializing
+#[derive(Serialize)]
+#[serde(rename = "LagrangePP")]
+struct LagrangePPReprRef<'a> {
+    domain_size: usize,
+    points_in_g1: G1Points<'a>,
+    points_in_g2: G2Points<'a>,
+}
+
+impl Serialize for LagrangePP {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        LagrangePPReprRef {
+            domain_size: self.domain().size(),
+            points_in_g1: G1Points(&self.points_in_g1),
+            points_in_g2: G2Points(&self.points_in_g2),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for LagrangePP {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = LagrangePPRepr::deserialize(deserializer)?;
+        let domain = Domain::new(repr.domain_size)
+            .map_err(|_| de::Error::custom(format!("invalid domain size {}", repr.domain_size)))?;
+        if repr.points_in_g1.len() != domain.size() {
+            return Err(de::Error::custom(format!(
+                "domain of size {} doesn't match {} G1 points",
+                domain.size(),
+                repr.points_in_g1.len()
+            )));
+        }
+        if repr.points_in_g2.len() < 2 {
+            return Err(de::Error::invalid_length(repr.points_in_g2.len(), &"at least two G2 points"));
+        }
+
+        Ok(LagrangePP::from_parts(
+            repr.points_in_g1.into_iter().map(|point| point.0).collect(),
+            repr.points_in_g2.into_iter().map(|point| point.0).collect(),
+            domain,
+        ))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "KZG")]
+struct KZGRepr {
+    public_parameter: PP,
+}
+
+/// [`KZGRepr`] borrowing the setup, for serializing
+#[derive(Serialize)]
+#[serde(rename = "KZG")]
+struct KZGReprRef<'a> {
+    public_parameter: &'a PP,
+}
+
+impl Serialize for KZG {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        KZGReprRef {
+            public_parameter: &self.public_parameter,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for KZG {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = KZGRepr::deserialize(deserializer)?;
+        Ok(KZG {
+            public_parameter: repr.public_parameter,
+        })
+    }
+}
+
+/// the serialized shape of `Opening`
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Opening")]
+struct OpeningRepr {
+    value: Scalar,
+    proof: G1Point,
+}
+
+impl Serialize for Opening {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        OpeningRepr {
+            value: Scalar(self.value),
+            proof: G1Point(self.proof),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Opening {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = OpeningRepr::deserialize(deserializer)?;
+        Ok(Opening {
+            value: repr.value.0,
+            proof: repr.proof.0,
+        })
+    }
+}
+
+/// a [`kzg_commit::Commitment`] borrows its polynomial and setup, so only its point is serialized,
+/// as a bare compressed point
+impl Serialize for CommitmentPoint {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        G1Point(self.0).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CommitmentPoint {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        G1Point::deserialize(deserializer).map(|point| CommitmentPoint(point.0))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::Domain;
+    use crate::kzg_commit::{CommitmentPoint, Opening, KZG, PP};
+    use crate::lagrange::LagrangePP;
+    use crate::polynomial::{Fr, Polynomial};
+
+    fn setup() -> KZG {
+        KZG::new(&[0x42u8; 32], 64).unwrap()
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let kzg = setup();
+        let json = serde_json::to_string(&kzg).unwrap();
+        let restored: KZG = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored, kzg);
+        assert!(json.contains(&hex::encode(kzg.public_parameter.points_in_g1[1].compress())));
+    }
+
+    #[test]
+    fn serialized_shape() {
+        let kzg = setup();
+        let pp = &kzg.public_parameter;
+        let json: serde_json::Value = serde_json::from_str(&serde_json::to_string(pp).unwrap()).unwrap();
+        assert_eq!(json["max_degree"], 64);
+        let g1 = json["points_in_g1"].as_array().unwrap();
+        assert_eq!(g1.len(), 65);
+        assert_eq!(g1[2], hex::encode(pp.points_in_g1[2].compress()));
+        assert_eq!(json["points_in_g2"].as_array().unwrap().len(), pp.points_in_g2.len());
+
+        let kzg_json: serde_json::Value = serde_json::from_str(&serde_json::to_string(&kzg).unwrap()).unwrap();
+        assert_eq!(kzg_json, serde_json::json!({ "public_parameter": json }));
+
+        // bincode: the degree, then each sequence as its length and length-prefixed point bytes
+        let bytes = bincode::serialize(pp).unwrap();
+        assert_eq!(bytes.len(), 8 + 8 + 65 * (8 + 48) + 8 + pp.points_in_g2.len() * (8 + 96));
+    }
+
+    #[test]
+    fn bincode_round_trip() {
+        let kzg = setup();
+        let bytes = bincode::serialize(&kzg.public_parameter).unwrap();
+        let restored: PP = bincode::deserialize(&bytes).unwrap();
+
+        assert_eq!(restored, kzg.public_parameter);
+    }
+
+    #[test]
+    fn proof_verifies_after_round_trip() {
+        let kzg = setup();
+        let polynomial = Polynomial::from(vec![3u64, 1, 4, 1, 5, 9, 2, 6]);
+        let commitment = KZG::commit(&kzg.public_parameter, &polynomial).unwrap();
+        let z = Fr::from_u64(2718);
+        let opening = commitment.open_at(z).unwrap();
+
+        let json = serde_json::to_string(&kzg.public_parameter).unwrap();
+        let restored: PP = serde_json::from_str(&json).unwrap();
+        let restored_commitment = KZG::commit(&restored, &polynomial).unwrap();
+
+        assert!(opening.verify(&z, &restored_commitment));
+    }
+
+    #[test]
+    fn inconsistent_max_degree_is_rejected() {
+        let json = serde_json::to_string(&setup().public_parameter).unwrap();
+        assert!(json.contains("\"max_degree\":64"));
+
+        let tampered = json.replacen("\"max_degree\":64", "\"max_degree\":65", 1);
+        assert!(serde_json::from_str::<PP>(&tampered).is_err());
+
+        // dropping a point without updating the degree
+        let mut pp = setup().public_parameter;
+        pp.points_in_g1.pop();
+        let json = serde_json::to_string(&pp).unwrap();
+        assert!(serde_json::from_str::<PP>(&json).is_err());
+    }
+
+    #[test]
+    fn lagrange_round_trip() {
+        let lagrange = setup().public_parameter.to_lagrange(&Domain::new(32).unwrap()).unwrap();
+
+        let json = serde_json::to_string(&lagrange).unwrap();
+        assert_eq!(serde_json::from_str::<LagrangePP>(&json).unwrap(), lagrange);
+        let bytes = bincode::serialize(&lagrange).unwrap();
+        assert_eq!(bincode::deserialize::<LagrangePP>(&bytes).unwrap(), lagrange);
+
+        let tampered = json.replacen("\"domain_size\":32", "\"domain_size\":16", 1);
+        assert!(serde_json::from_str::<LagrangePP>(&tampered).is_err());
+    }
+
+    #[test]
+    fn opening_and_commitment_round_trip() {
+        // the reference vector of `test_hex_api_with_reference_vector`
+        let secret = hex::decode("0b598c0727a94e556b8c1dcb64af40daea6971901b5dcb8b49da2fe2b533a52e").unwrap();
+        let setup = KZG::new(secret.as_slice().try_into().unwrap(), 3).unwrap().public_parameter;
+        let polynomial = Polynomial::from(vec![1u64, 2, 2]);
+        let commitment = KZG::commit(&setup, &polynomial).unwrap();
+        let z = Fr::from_u64(15);
+        let opening = commitment.open_at(z).unwrap();
+
+        let json = serde_json::to_string(&opening).unwrap();
+        let proof_hex = "b8cea544c0d68bf429533df6126a3f9a3ce9027595df4e7fc1e00a368f8b92690251434e51a9b53b35e8e9677960e0b1";
+        assert_eq!(json, format!("{{\"value\":\"{:064x}\",\"proof\":\"{}\"}}", 481, proof_hex));
+        let restored: Opening = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.value, opening.value);
+        assert_eq!(restored.proof.compress(), opening.proof.compress());
+
+        let point = CommitmentPoint::from(&commitment);
+        let json = serde_json::to_string(&point).unwrap();
+        assert_eq!(
+            json,
+            "\"b3e43da9f207cb9d717f85d40b967a28254b22bb6269b551aed50444eb1aed7f93a2b519acd7076e56451dc084389323\""
+        );
+        let restored_point: CommitmentPoint = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored_point, point);
+
+        // the restored opening still verifies against the restored point
+        assert!(restored.verify_against(z, restored_point, setup.point_in_g2()));
+
+        let bytes = bincode::serialize(&opening).unwrap();
+        assert_eq!(bincode::deserialize::<Opening>(&bytes).unwrap().proof.compress(), opening.proof.compress());
+        let bytes = bincode::serialize(&point).unwrap();
+        assert_eq!(bincode::deserialize::<CommitmentPoint>(&bytes).unwrap(), point);
+
+        // a value of the curve order itself, and a value or proof of the wrong length
+        let order = "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001";
+        let json = format!("{{\"value\":\"{}\",\"proof\":\"{}\"}}", order, proof_hex);
+        assert!(serde_json::from_str::<Opening>(&json).is_err());
+        let json = format!("{{\"value\":\"{:062x}\",\"proof\":\"{}\"}}", 481, proof_hex);
+        assert!(serde_json::from_str::<Opening>(&json).is_err());
+        let json = format!("{{\"value\":\"{:064x}\",\"proof\":\"{}\"}}", 481, &proof_hex[2..]);
+        assert!(serde_json::from_str::<Opening>(&json).is_err());
+    }
+
+    #[test]
+    fn corrupted_hex_is_rejected() {
+        let json = serde_json::to_string(&setup().public_parameter).unwrap();
+        let target = hex::encode(setup().public_parameter.points_in_g1[3].compress());
+        let position = json.find(&target).unwrap() + 10;
+
+        // a non-hex character
+        let mut not_hex = json.clone().into_bytes();
+        not_hex[position] = b'z';
+        assert!(serde_json::from_slice::<PP>(&not_hex).is_err());
+
+        // a different hex digit: no longer a valid point
+        let mut wrong_digit = json.into_bytes();
+        wrong_digit[position] = if wrong_digit[position] == b'0' { b'1' } else { b'0' };
+        assert!(serde_json::from_slice::<PP>(&wrong_digit).is_err());
+    }
+}
@@ -0,0 +1,367 @@
+//! Everything an application needs in one object: the monomial setup, its Lagrange form and the
+//! evaluation domain, with the blob-oriented commit / open / verify calls built on them.
+//!
+//! A blob is the vector of evaluations of a polynomial of degree `< n` over the domain of size `n`.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use oblast_demo::{Fr, P1};
+
+use crate::builder::KZGBuilder;
+use crate::domain::Domain;
+use crate::kzg_commit::{self, KZGErrors, Opening, KZG, PP};
+use crate::lagrange::LagrangePP;
+use crate::polynomial::Polynomial;
+use crate::transcript::Transcript;
+
+/// magic bytes at the start of a saved `KZGSettings`
+const SETTINGS_MAGIC: &[u8; 4] = b"KZGS";
+/// current version of the saved `KZGSettings` format
+const SETTINGS_VERSION: u8 = 1;
+/// magic + version + length of the monomial setup
+const SETTINGS_HEADER_SIZE: usize = 4 + 1 + 8;
+
+#[derive(Debug)]
+pub enum SettingsError {
+    /// reading or writing the file failed
+    Io(io::Error),
+    /// one of the stored setups is malformed
+    Setup(KZGErrors),
+    /// the stored Lagrange setup is not the Lagrange form of the stored monomial setup
+    InconsistentLagrange,
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingsError::Io(error) => write!(f, "reading or writing the settings failed: {}", error),
+            SettingsError::Setup(error) => write!(f, "invalid stored setup: {}", error),
+            SettingsError::InconsistentLagrange => {
+                f.write_str("the stored Lagrange setup does not match the monomial setup")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SettingsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SettingsError::Io(error) => Some(error),
+            SettingsError::Setup(error) => Some(error),
+            SettingsError::InconsistentLagrange => None,
+        }
+    }
+}
+
+impl From<io::Error> for SettingsError {
+    fn from(error: io::Error) -> Self {
+        SettingsError::Io(error)
+    }
+}
+
+impl From<KZGErrors> for SettingsError {
+    fn from(error: KZGErrors) -> Self {
+        SettingsError::Setup(error)
+    }
+}
+
+/// The monomial setup, its Lagrange form over `domain`, and the domain itself
+#[derive(Clone, Debug, PartialEq)]
+pub struct KZGSettings {
+    monomial: PP,
+    lagrange: LagrangePP,
+    domain: Domain,
+}
+
+impl KZGSettings {
+    /// derives the domain of size `domain_size` and the Lagrange setup over it from `pp`, which
+    /// needs a degree of at least `domain_size - 1`
+    pub fn new(pp: PP, domain_size: usize) -> Result<KZGSettings, KZGErrors> {
+        let domain = Domain::new(domain_size)?;
+        let lagrange = pp.to_lagrange(&domain)?;
+        Ok(KZGSettings {
+            monomial: pp,
+            lagrange,
+            domain,
+        })
+    }
+
+    /// the setup in monomial (coefficient) form
+    pub fn monomial(&self) -> &PP {
+        &self.monomial
+    }
+
+    /// the setup in Lagrange form over [`KZGSettings::domain`]
+    pub fn lagrange(&self) -> &LagrangePP {
+        &self.lagrange
+    }
+
+    /// the evaluation domain blobs are defined over
+    pub fn domain(&self) -> &Domain {
+        &self.domain
+    }
+
+    /// the number of field elements in a blob
+    pub fn blob_size(&self) -> usize {
+        self.domain.size()
+    }
+
+    fn check_blob(&self, blob: &[Fr]) -> Result<(), KZGErrors> {
+        if blob.len() != self.blob_size() {
+            return Err(KZGErrors::InvalidBlobLength {
+                expected: self.blob_size(),
+                found: blob.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// the polynomial (in coefficient form) whose evaluations over the domain are `blob`
+    pub fn blob_to_polynomial(&self, blob: &[Fr]) -> Result<Polynomial, KZGErrors> {
+        self.check_blob(blob)?;
+        Ok(Polynomial::from_coefficients(self.domain.ifft(blob)))
+    }
+
+    /// commits to a blob directly from its evaluations with the Lagrange setup
+    pub fn commit_blob(&self, blob: &[Fr]) -> Result<P1, KZGErrors> {
+        self.check_blob(blob)?;
+        self.lagrange.commit(blob)
+    }
+
+    /// opens the blob's polynomial at an arbitrary point `z`
+    pub fn open_blob_at(&self, blob: &[Fr], z: Fr) -> Result<Opening, KZGErrors> {
+        let polynomial = self.blob_to_polynomial(blob)?;
+        KZG::commit(&self.monomial, &polynomial)?.open_at(z)
+    }
+
+    /// checks an opening of a blob commitment at `z` (see [`Opening::verify_against`])
+    pub fn verify(&self, commitment: &P1, z: Fr, opening: &Opening) -> bool {
+        opening.verify_against(z, *commitment, self.monomial.point_in_g2())
+    }
+
+    /// writes the settings as: magic `KZGS`, a version byte, the length of the monomial setup as a
+    /// big-endian u64, then [`PP::to_bytes`] and [`LagrangePP::to_bytes`]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let monomial = self.monomial.to_bytes();
+        let lagrange = self.lagrange.to_bytes();
+
+        let mut bytes = Vec::with_capacity(SETTINGS_HEADER_SIZE + monomial.len() + lagrange.len());
+        bytes.extend_from_slice(SETTINGS_MAGIC);
+        bytes.push(SETTINGS_VERSION);
+        bytes.extend_from_slice(&(monomial.len() as u64).to_be_bytes());
+        bytes.extend_from_slice(&monomial);
+        bytes.extend_from_slice(&lagrange);
+        bytes
+    }
+
+    /// parses the output of [`KZGSettings::to_bytes`]. Besides the checks of the two setup
+    /// parsers, this checks (with one random linear combination) that the Lagrange points are the
+    /// Lagrange form of the monomial ones.
+    pub fn from_bytes(bytes: &[u8]) -> Result<KZGSettings, SettingsError> {
+        if bytes.len() < SETTINGS_HEADER_SIZE {
+            return Err(KZGErrors::TruncatedInput.into());
+        }
+        if &bytes[..4] != SETTINGS_MAGIC {
+            return Err(KZGErrors::InvalidSerializationMagic.into());
+        }
+        if bytes[4] != SETTINGS_VERSION {
+            return Err(KZGErrors::UnsupportedSerializationVersion(bytes[4]).into());
+        }
+
+        let monomial_len = u64::from_be_bytes(bytes[5..13].try_into().unwrap());
+        let body = &bytes[SETTINGS_HEADER_SIZE..];
+        let monomial_len = usize::try_from(monomial_len)
+            .ok()
+            .filter(|len| *len <= body.len())
+            .ok_or(KZGErrors::TruncatedInput)?;
+        let (monomial, lagrange) = body.split_at(monomial_len);
+
+        let monomial = PP::from_bytes(monomial)?;
+        let lagrange = LagrangePP::from_bytes(lagrange)?;
+        let domain = lagrange.domain().clone();
+        let same_g2 = lagrange.points_in_g2.len() == monomial.points_in_g2.len()
+            && lagrange
+                .points_in_g2
+                .iter()
+                .zip(&monomial.points_in_g2)
+                .all(|(a, b)| a.compress() == b.compress());
+        if domain.size() > monomial.max_degree() + 1 || !same_g2 {
+            return Err(SettingsError::InconsistentLagrange);
+        }
+
+        let settings = KZGSettings {
+            monomial,
+            lagrange,
+            domain,
+        };
+        if !settings.lagrange_matches_monomial() {
+            return Err(SettingsError::InconsistentLagrange);
+        }
+        Ok(settings)
+    }
+
+    /// saves [`KZGSettings::to_bytes`] to `path`
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_bytes())
+    }
+
+    /// loads settings written by [`KZGSettings::save`]
+    pub fn load(path: impl AsRef<Path>) -> Result<KZGSettings, SettingsError> {
+        KZGSettings::from_bytes(&fs::read(path)?)
+    }
+
+    /// commits to `1 + rX + ... + r^(n-1) X^(n-1)` for a challenge `r` bound to both setups, once
+    /// from the coefficients and once from the evaluations; the two agree for every `r` only if
+    /// the Lagrange points are right
+    fn lagrange_matches_monomial(&self) -> bool {
+        let mut transcript = Transcript::new(b"kzg-settings");
+        transcript.append_bytes(&self.monomial.digest());
+        transcript.append_bytes(&self.lagrange.digest());
+        let r = transcript.challenge();
+
+        let n = self.domain.size();
+        let polynomial = Polynomial::from_coefficients(kzg_commit::powers_of_tau(r, n - 1));
+        let Ok(from_coefficients) = KZG::commit(&self.monomial, &polynomial) else {
+            return false;
+        };
+        let Ok(from_evaluations) = self.lagrange.commit(&self.domain.fft(&polynomial.coefficients)) else {
+            return false;
+        };
+
+        from_coefficients.element.compress() == from_evaluations.compress()
+    }
+}
+
+impl KZG {
+    /// a setup sized to `domain`, bundled with its Lagrange form: degree `domain.size() - 1`
+    /// plus `extra_powers` (e.g. headroom for quotients).
+    ///
+    /// The tau source and the other options come from `setup` (see [`KZGBuilder::tau_bytes`] and
+    /// [`KZGBuilder::random`]); any degree set on it is replaced. A [`Domain`] can only be built
+    /// with a size the field's two-adicity supports, so only the degree needs checking here.
+    pub fn setup_for_domain(setup: KZGBuilder<'_>, domain: &Domain, extra_powers: usize) -> Result<KZGSettings, KZGErrors> {
+        let degree = (domain.size() - 1)
+            .checked_add(extra_powers)
+            .ok_or(KZGErrors::DegreeOverflow)?;
+        let pp = setup.degree(degree).build()?.public_parameter;
+        let lagrange = pp.to_lagrange(domain)?;
+
+        Ok(KZGSettings {
+            monomial: pp,
+            lagrange,
+            domain: domain.clone(),
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn settings() -> KZGSettings {
+        KZGSettings::new(KZG::new(&[0x37u8; 32], 40).unwrap().public_parameter, 32).unwrap()
+    }
+
+    fn random_blob(rng: &mut StdRng, size: usize) -> Vec<Fr> {
+        (0..size).map(|_| Fr::from_u64(rng.gen())).collect()
+    }
+
+    #[test]
+    fn blob_commit_open_verify() {
+        let mut rng = StdRng::seed_from_u64(37);
+        let settings = settings();
+        let blob = random_blob(&mut rng, settings.blob_size());
+
+        let commitment = settings.commit_blob(&blob).unwrap();
+        let polynomial = settings.blob_to_polynomial(&blob).unwrap();
+        let expected = KZG::commit(settings.monomial(), &polynomial).unwrap();
+        assert_eq!(commitment.compress(), expected.element.compress());
+
+        let z = Fr::from_u64(123_456_789);
+        let opening = settings.open_blob_at(&blob, z).unwrap();
+        assert_eq!(opening.value, polynomial.evaluate(z));
+        assert!(settings.verify(&commitment, z, &opening));
+
+        // on the domain the opening reveals the blob itself
+        let w3 = settings.domain().elements()[3];
+        let opening = settings.open_blob_at(&blob, w3).unwrap();
+        assert_eq!(opening.value, blob[3]);
+        assert!(settings.verify(&commitment, w3, &opening));
+        assert!(!settings.verify(&commitment, z, &opening));
+
+        assert!(matches!(
+            settings.commit_blob(&blob[1..]),
+            Err(KZGErrors::InvalidBlobLength { expected: 32, found: 31 })
+        ));
+    }
+
+    #[test]
+    fn domain_must_fit_the_setup() {
+        let pp = KZG::new(&[0x38u8; 32], 30).unwrap().public_parameter;
+        assert!(matches!(KZGSettings::new(pp.clone(), 32), Err(KZGErrors::DegreeExceedsSetup { .. })));
+        assert!(matches!(KZGSettings::new(pp, 24), Err(KZGErrors::InvalidDomainSize(24))));
+    }
+
+    #[test]
+    fn setup_for_domain() {
+        let domain = Domain::new(16).unwrap();
+        let settings = KZG::setup_for_domain(KZG::builder().tau_bytes([0x65u8; 32]), &domain, 2).unwrap();
+        assert_eq!(settings.monomial().max_degree(), 17);
+        assert_eq!(settings.domain(), &domain);
+        assert_eq!(settings, KZGSettings::new(KZG::new(&[0x65u8; 32], 17).unwrap().public_parameter, 16).unwrap());
+
+        // full width: degree 15 = domain size - 1
+        let mut rng = StdRng::seed_from_u64(65);
+        let blob = random_blob(&mut rng, 16);
+        let commitment = settings.commit_blob(&blob).unwrap();
+        let z = Fr::from_u64(6565);
+        assert!(settings.verify(&commitment, z, &settings.open_blob_at(&blob, z).unwrap()));
+
+        // degree 17 uses the extra powers
+        let polynomial = Polynomial::from_coefficients(random_blob(&mut rng, 18));
+        let commitment = KZG::commit(settings.monomial(), &polynomial).unwrap();
+        let opening = commitment.open_at(z).unwrap();
+        assert!(settings.verify(&commitment.element, z, &opening));
+
+        let exact = KZG::setup_for_domain(KZG::builder().random(&mut rng), &domain, 0).unwrap();
+        assert_eq!(exact.monomial().max_degree(), 15);
+        assert!(matches!(
+            KZG::setup_for_domain(KZG::builder(), &domain, usize::MAX),
+            Err(KZGErrors::DegreeOverflow)
+        ));
+    }
+
+    #[test]
+    fn save_and_load() {
+        let settings = settings();
+        let path = std::env::temp_dir().join(format!("kzg-settings-{}.bin", std::process::id()));
+        settings.save(&path).unwrap();
+        let loaded = KZGSettings::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, settings);
+
+        // the Lagrange form of another setup
+        let other = KZG::new(&[0x39u8; 32], 40).unwrap().public_parameter;
+        let mut mixed = settings.clone();
+        mixed.lagrange = other.to_lagrange(settings.domain()).unwrap();
+        assert!(matches!(KZGSettings::from_bytes(&mixed.to_bytes()), Err(SettingsError::InconsistentLagrange)));
+
+        // right setup, wrong G2 power
+        mixed.lagrange = settings.lagrange.clone();
+        mixed.lagrange.points_in_g2 = other.points_in_g2;
+        assert!(matches!(KZGSettings::from_bytes(&mixed.to_bytes()), Err(SettingsError::InconsistentLagrange)));
+
+        let bytes = settings.to_bytes();
+        assert!(matches!(
+            KZGSettings::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(SettingsError::Setup(KZGErrors::TruncatedInput))
+        ));
+        assert!(matches!(KZGSettings::load(std::env::temp_dir().join("kzg-settings-missing")), Err(SettingsError::Io(_))));
+    }
+}
@@ -0,0 +1,1134 @@
+//! Loading setups produced by other tools.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use num_bigint::BigUint;
+use oblast_demo::{P1, P2};
+use sha2::{Digest, Sha256};
+
+#[cfg(feature = "serde")]
+use crate::domain::Domain;
+use crate::group::{self, G1_COMPRESSED_SIZE, G1_UNCOMPRESSED_SIZE, G2_COMPRESSED_SIZE, G2_UNCOMPRESSED_SIZE};
+use crate::kzg_commit::{self, KZGErrors, PP, PP_HEADER_SIZE, PP_MAGIC, PP_VERSION};
+
+#[derive(Debug)]
+pub enum SetupLoadError {
+    /// reading the underlying source failed
+    Io(io::Error),
+    /// a point count at the top of the file is missing or not a number (1-based line)
+    InvalidCount { line: usize },
+    /// the number of points present doesn't match the announced count
+    CountMismatch { group: &'static str, expected: usize, found: usize },
+    /// a line is not valid hex of the right length (1-based line)
+    InvalidHex { line: usize },
+    /// a line decodes but is not a point in the prime-order subgroup (1-based line)
+    InvalidPoint { line: usize },
+    /// the setup needs at least `[1]_2` and `[tau]_2`
+    NotEnoughG2Points { found: usize },
+    /// the setup contains no G1 points
+    NoG1Points,
+    /// the input is not valid JSON of the expected shape
+    InvalidJson(String),
+    /// a required section is absent
+    MissingSection(&'static str),
+    /// an entry of a section is not a valid hex-encoded point in the prime-order subgroup
+    InvalidSectionPoint { section: &'static str, index: usize },
+    /// more powers were requested than the setup provides
+    DegreeExceedsSetup { requested: usize, available: usize },
+    /// the input doesn't start with a valid `.ptau` header
+    InvalidPtauHeader,
+    /// the `.ptau` file is for a curve other than BLS12-381
+    UnsupportedCurve,
+    /// the input ends inside the named section
+    TruncatedSection(&'static str),
+    /// the input is not a valid serialized setup (see [`PP::to_bytes`])
+    InvalidFormat(KZGErrors),
+    /// the first G1 point, where `[1]_1` belongs, is the point at infinity
+    IdentityInGeneratorSlot,
+    /// the file header announces a format version this crate doesn't understand
+    UnsupportedVersion(u8),
+}
+
+impl fmt::Display for SetupLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SetupLoadError::Io(error) => write!(f, "reading the setup failed: {}", error),
+            SetupLoadError::InvalidCount { line } => write!(f, "line {}: missing or invalid point count", line),
+            SetupLoadError::CountMismatch { group, expected, found } => {
+                write!(f, "expected {} {} points, found {}", expected, group, found)
+            }
+            SetupLoadError::InvalidHex { line } => write!(f, "line {}: not a hex point of the right length", line),
+            SetupLoadError::InvalidPoint { line } => {
+                write!(f, "line {}: not a point in the prime-order subgroup", line)
+            }
+            SetupLoadError::NotEnoughG2Points { found } => write!(f, "need at least 2 G2 points, found {}", found),
+            SetupLoadError::NoG1Points => f.write_str("the setup contains no G1 points"),
+            SetupLoadError::InvalidJson(error) => write!(f, "invalid setup JSON: {}", error),
+            SetupLoadError::MissingSection(section) => write!(f, "missing section {}", section),
+            SetupLoadError::InvalidSectionPoint { section, index } => {
+                write!(f, "{}[{}] is not a valid point in the prime-order subgroup", section, index)
+            }
+            SetupLoadError::DegreeExceedsSetup { requested, available } => {
+                write!(f, "degree {} requested, the setup supports up to {}", requested, available)
+            }
+            SetupLoadError::InvalidPtauHeader => f.write_str("invalid .ptau header"),
+            SetupLoadError::UnsupportedCurve => f.write_str("the .ptau file is not for BLS12-381"),
+            SetupLoadError::TruncatedSection(section) => write!(f, "input ends inside section {}", section),
+            SetupLoadError::InvalidFormat(error) => write!(f, "invalid serialized setup: {}", error),
+            SetupLoadError::IdentityInGeneratorSlot => f.write_str("the first G1 point is the point at infinity"),
+            SetupLoadError::UnsupportedVersion(version) => write!(f, "unsupported setup file version {}", version),
+        }
+    }
+}
+
+impl std::error::Error for SetupLoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SetupLoadError::Io(error) => Some(error),
+            SetupLoadError::InvalidFormat(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SetupLoadError {
+    fn from(error: io::Error) -> Self {
+        SetupLoadError::Io(error)
+    }
+}
+
+/// decodes a hex line (optional `0x`) into exactly `N` bytes
+fn decode_line<const N: usize>(line: &str, line_number: usize) -> Result<[u8; N], SetupLoadError> {
+    let line = line.strip_prefix("0x").unwrap_or(line);
+    hex::decode(line)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(SetupLoadError::InvalidHex { line: line_number })
+}
+
+/// the setup from the decoded points, which every loader below ends with
+fn checked_setup(points_in_g1: Vec<P1>, points_in_g2: Vec<P2>) -> Result<PP, SetupLoadError> {
+    kzg_commit::check_generator_slot(&points_in_g1).map_err(|_| SetupLoadError::IdentityInGeneratorSlot)?;
+    Ok(PP::from_parts(points_in_g1, points_in_g2))
+}
+
+fn parse_count(line: Option<(usize, String)>, group: &'static str) -> Result<usize, SetupLoadError> {
+    match line {
+        Some((line_number, line)) => line
+            .trim()
+            .parse()
+            .map_err(|_| SetupLoadError::InvalidCount { line: line_number }),
+        None => Err(SetupLoadError::CountMismatch { group, expected: 1, found: 0 }),
+    }
+}
+
+impl PP {
+    /// reads the c-kzg `trusted_setup.txt` format: the G1 count and the G2 count on the first two
+    /// lines, then one hex-encoded compressed point per line (all G1 points, then all G2 points).
+    ///
+    /// The G1 points are used as-is for `points_in_g1` and the G2 points for `points_in_g2`. Note that the mainnet file stores its G1 points in Lagrange form, so
+    /// coefficient-form commitments need a file carrying monomial points.
+    pub fn from_trusted_setup_reader(reader: impl BufRead) -> Result<PP, SetupLoadError> {
+        let mut lines = reader
+            .lines()
+            .enumerate()
+            .map(|(index, line)| line.map(|line| (index + 1, line)))
+            .filter(|line| !matches!(line, Ok((_, text)) if text.trim().is_empty()));
+
+        let g1_count = parse_count(lines.next().transpose()?, "G1")?;
+        let g2_count = parse_count(lines.next().transpose()?, "G2")?;
+
+        if g1_count == 0 {
+            return Err(SetupLoadError::NoG1Points);
+        }
+        if g2_count < 2 {
+            return Err(SetupLoadError::NotEnoughG2Points { found: g2_count });
+        }
+
+        let mut points_in_g1: Vec<P1> = Vec::new();
+        for found in 0..g1_count {
+            let (line_number, line) = lines
+                .next()
+                .transpose()?
+                .ok_or(SetupLoadError::CountMismatch { group: "G1", expected: g1_count, found })?;
+            let bytes = decode_line::<G1_COMPRESSED_SIZE>(line.trim(), line_number)?;
+            let point = group::decompress_g1(&bytes).map_err(|_| SetupLoadError::InvalidPoint { line: line_number })?;
+            points_in_g1.push(point);
+        }
+
+        let mut points_in_g2: Vec<P2> = Vec::new();
+        for found in 0..g2_count {
+            let (line_number, line) = lines
+                .next()
+                .transpose()?
+                .ok_or(SetupLoadError::CountMismatch { group: "G2", expected: g2_count, found })?;
+            let bytes = decode_line::<G2_COMPRESSED_SIZE>(line.trim(), line_number)?;
+            let point = group::decompress_g2(&bytes).map_err(|_| SetupLoadError::InvalidPoint { line: line_number })?;
+            points_in_g2.push(point);
+        }
+
+        let extra = lines.count();
+        if extra > 0 {
+            return Err(SetupLoadError::CountMismatch {
+                group: "G2",
+                expected: g2_count,
+                found: g2_count + extra,
+            });
+        }
+
+        checked_setup(points_in_g1, points_in_g2)
+    }
+
+    /// [`PP::from_trusted_setup_reader`] on a file
+    pub fn from_trusted_setup_file(path: impl AsRef<Path>) -> Result<PP, SetupLoadError> {
+        let file = File::open(path)?;
+        PP::from_trusted_setup_reader(BufReader::new(file))
+    }
+}
+
+
+// ===================================
+// SNARKJS .ptau FILES
+// ===================================
+const PTAU_MAGIC: &[u8; 4] = b"ptau";
+const PTAU_HEADER_SECTION: u32 = 1;
+const PTAU_TAU_G1_SECTION: u32 = 2;
+const PTAU_TAU_G2_SECTION: u32 = 3;
+/// size of a BLS12-381 base field element
+const FQ_SIZE: usize = 48;
+
+/// the BLS12-381 base field modulus
+fn fq_modulus() -> BigUint {
+    BigUint::parse_bytes(
+        b"1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab",
+        16,
+    )
+    .unwrap()
+}
+
+/// undoes the Montgomery form `x * 2^384 mod q` used by `.ptau` files
+struct Montgomery {
+    modulus: BigUint,
+    r_inverse: BigUint,
+}
+
+impl Montgomery {
+    fn new() -> Self {
+        let modulus = fq_modulus();
+        let r = (BigUint::from(1u32) << (8 * FQ_SIZE)) % &modulus;
+        let r_inverse = r.modpow(&(&modulus - 2u32), &modulus);
+        Montgomery { modulus, r_inverse }
+    }
+
+    /// little-endian Montgomery form -> canonical big-endian, rejecting values that aren't reduced
+    fn to_canonical_be(&self, le: &[u8]) -> Option<[u8; FQ_SIZE]> {
+        let value = BigUint::from_bytes_le(le);
+        if value >= self.modulus {
+            return None;
+        }
+
+        let canonical = (value * &self.r_inverse % &self.modulus).to_bytes_be();
+        let mut bytes = [0u8; FQ_SIZE];
+        bytes[FQ_SIZE - canonical.len()..].copy_from_slice(&canonical);
+        Some(bytes)
+    }
+
+    /// `.ptau` stores `x || y` (each little-endian Montgomery) and all zeros for the identity
+    fn g1(&self, raw: &[u8]) -> Option<P1> {
+        let mut bytes = [0u8; G1_UNCOMPRESSED_SIZE];
+        if raw.iter().all(|byte| *byte == 0) {
+            bytes[0] = 0x40;
+        } else {
+            for (i, coordinate) in raw.chunks_exact(FQ_SIZE).enumerate() {
+                bytes[i * FQ_SIZE..(i + 1) * FQ_SIZE].copy_from_slice(&self.to_canonical_be(coordinate)?);
+            }
+        }
+        group::deserialize_g1(&bytes).ok()
+    }
+
+    /// `.ptau` stores `x.c0 || x.c1 || y.c0 || y.c1`, `blst` expects `x.c1 || x.c0 || y.c1 || y.c0`
+    fn g2(&self, raw: &[u8]) -> Option<P2> {
+        let mut bytes = [0u8; G2_UNCOMPRESSED_SIZE];
+        if raw.iter().all(|byte| *byte == 0) {
+            bytes[0] = 0x40;
+        } else {
+            for (i, coordinate) in raw.chunks_exact(FQ_SIZE).enumerate() {
+                let position = i ^ 1;
+                bytes[position * FQ_SIZE..(position + 1) * FQ_SIZE]
+                    .copy_from_slice(&self.to_canonical_be(coordinate)?);
+            }
+        }
+        group::deserialize_g2(&bytes).ok()
+    }
+}
+
+/// `read_exact` that reports running out of input as a truncated `section`
+fn read_section_bytes(reader: &mut impl Read, buffer: &mut [u8], section: &'static str) -> Result<(), SetupLoadError> {
+    reader.read_exact(buffer).map_err(|error| match error.kind() {
+        io::ErrorKind::UnexpectedEof => SetupLoadError::TruncatedSection(section),
+        _ => SetupLoadError::Io(error),
+    })
+}
+
+fn read_u32(reader: &mut impl Read, section: &'static str) -> Result<u32, SetupLoadError> {
+    let mut bytes = [0u8; 4];
+    read_section_bytes(reader, &mut bytes, section)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut impl Read, section: &'static str) -> Result<u64, SetupLoadError> {
+    let mut bytes = [0u8; 8];
+    read_section_bytes(reader, &mut bytes, section)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// discards `length` bytes without buffering them
+fn skip_section_bytes(reader: &mut impl Read, length: u64, section: &'static str) -> Result<(), SetupLoadError> {
+    let skipped = io::copy(&mut reader.take(length), &mut io::sink())?;
+    if skipped < length {
+        return Err(SetupLoadError::TruncatedSection(section));
+    }
+    Ok(())
+}
+
+/// the section's entry count, checking its size is a whole number of entries
+fn section_entries(size: u64, entry_size: usize, section: &'static str) -> Result<usize, SetupLoadError> {
+    if !size.is_multiple_of(entry_size as u64) {
+        return Err(SetupLoadError::TruncatedSection(section));
+    }
+    usize::try_from(size / entry_size as u64).map_err(|_| SetupLoadError::TruncatedSection(section))
+}
+
+impl PP {
+    /// reads the first `degree + 1` powers of tau from a snarkjs / perpetual-powers-of-tau `.ptau`
+    /// file for BLS12-381.
+    ///
+    /// The file is a `ptau` magic, a version and a section count, followed by sections of
+    /// `(type: u32, size: u64, data)`, all little-endian. Only the header (1), `tauG1` (2) and
+    /// `tauG2` (3) sections are used; reading stops as soon as the needed points have been decoded,
+    /// so only the start of a multi-gigabyte ceremony file is ever read.
+    pub fn from_ptau_reader(mut reader: impl Read, degree: usize) -> Result<PP, SetupLoadError> {
+        let mut magic = [0u8; 4];
+        read_section_bytes(&mut reader, &mut magic, "file header").map_err(|_| SetupLoadError::InvalidPtauHeader)?;
+        if &magic != PTAU_MAGIC {
+            return Err(SetupLoadError::InvalidPtauHeader);
+        }
+        let _version = read_u32(&mut reader, "file header")?;
+        let section_count = read_u32(&mut reader, "file header")?;
+
+        let field = Montgomery::new();
+        let mut seen_header = false;
+        let mut points_in_g1: Option<Vec<P1>> = None;
+        let mut points_in_g2: Option<Vec<P2>> = None;
+
+        for _ in 0..section_count {
+            let kind = read_u32(&mut reader, "section header")?;
+            let size = read_u64(&mut reader, "section header")?;
+
+            match kind {
+                PTAU_HEADER_SECTION => {
+                    // n8 (bytes per field element), q, power, ceremony power
+                    let n8 = read_u32(&mut reader, "header")?;
+                    if n8 as usize != FQ_SIZE {
+                        return Err(SetupLoadError::UnsupportedCurve);
+                    }
+                    let mut modulus = [0u8; FQ_SIZE];
+                    read_section_bytes(&mut reader, &mut modulus, "header")?;
+                    if BigUint::from_bytes_le(&modulus) != field.modulus {
+                        return Err(SetupLoadError::UnsupportedCurve);
+                    }
+                    let consumed = (4 + FQ_SIZE) as u64;
+                    let rest = size.checked_sub(consumed).ok_or(SetupLoadError::InvalidPtauHeader)?;
+                    skip_section_bytes(&mut reader, rest, "header")?;
+                    seen_header = true;
+                }
+                PTAU_TAU_G1_SECTION => {
+                    if !seen_header {
+                        return Err(SetupLoadError::MissingSection("header"));
+                    }
+                    let available = section_entries(size, 2 * FQ_SIZE, "tauG1")?;
+                    if available == 0 {
+                        return Err(SetupLoadError::NoG1Points);
+                    }
+                    if degree >= available {
+                        return Err(SetupLoadError::DegreeExceedsSetup {
+                            requested: degree,
+                            available: available - 1,
+                        });
+                    }
+
+                    let mut raw = [0u8; 2 * FQ_SIZE];
+                    let mut points = Vec::with_capacity(degree + 1);
+                    for index in 0..=degree {
+                        read_section_bytes(&mut reader, &mut raw, "tauG1")?;
+                        let point = field
+                            .g1(&raw)
+                            .ok_or(SetupLoadError::InvalidSectionPoint { section: "tauG1", index })?;
+                        points.push(point);
+                    }
+                    points_in_g1 = Some(points);
+
+                    if points_in_g2.is_none() {
+                        let rest = size - ((degree + 1) * 2 * FQ_SIZE) as u64;
+                        skip_section_bytes(&mut reader, rest, "tauG1")?;
+                    }
+                }
+                PTAU_TAU_G2_SECTION => {
+                    if !seen_header {
+                        return Err(SetupLoadError::MissingSection("header"));
+                    }
+                    let available = section_entries(size, 4 * FQ_SIZE, "tauG2")?;
+                    if available < 2 {
+                        return Err(SetupLoadError::NotEnoughG2Points { found: available });
+                    }
+
+                    // [1]_2 and [tau]_2
+                    let mut raw = [0u8; 4 * FQ_SIZE];
+                    let mut points = Vec::with_capacity(2);
+                    for index in 0..2 {
+                        read_section_bytes(&mut reader, &mut raw, "tauG2")?;
+                        let point = field
+                            .g2(&raw)
+                            .ok_or(SetupLoadError::InvalidSectionPoint { section: "tauG2", index })?;
+                        points.push(point);
+                    }
+                    points_in_g2 = Some(points);
+
+                    if points_in_g1.is_none() {
+                        skip_section_bytes(&mut reader, size - (2 * 4 * FQ_SIZE) as u64, "tauG2")?;
+                    }
+                }
+                _ => skip_section_bytes(&mut reader, size, "unused section")?,
+            }
+
+            if points_in_g1.is_some() && points_in_g2.is_some() {
+                break;
+            }
+        }
+
+        if !seen_header {
+            return Err(SetupLoadError::MissingSection("header"));
+        }
+
+        checked_setup(
+            points_in_g1.ok_or(SetupLoadError::MissingSection("tauG1"))?,
+            points_in_g2.ok_or(SetupLoadError::MissingSection("tauG2"))?,
+        )
+    }
+}
+
+
+/// Which G1 section of a ceremony file to load
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum G1Basis {
+    /// `g1_monomial`: `[tau^i]_1`, for committing to coefficient-form polynomials
+    Monomial,
+    /// `g1_lagrange`: `[L_i(tau)]_1`, for committing to evaluations over the ceremony's domain
+    Lagrange,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct CeremonyJson {
+    g1_monomial: Option<Vec<String>>,
+    g1_lagrange: Option<Vec<String>>,
+    g2_monomial: Option<Vec<String>>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct CeremonyJsonOut {
+    g1_monomial: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    g1_lagrange: Option<Vec<String>>,
+    g2_monomial: Vec<String>,
+}
+
+/// 0x-prefixed lowercase hex of a compressed point
+#[cfg(feature = "serde")]
+fn ceremony_hex(compressed: &[u8]) -> String {
+    format!("0x{}", hex::encode(compressed))
+}
+
+#[cfg(feature = "serde")]
+impl PP {
+    /// reads the Ethereum KZG ceremony JSON (`trusted_setup.json`/`transcript.json` layout):
+    /// `g1_monomial`, `g1_lagrange` and `g2_monomial` arrays of 0x-prefixed hex points.
+    ///
+    /// Only the first `degree + 1` points of the chosen G1 section are decoded, so a small setup
+    /// can be cut from the full 4096/65-point arrays. Truncating a Lagrange section only makes
+    /// sense if the caller works over a matching sub-domain.
+    pub fn from_ceremony_json(json: &str, basis: G1Basis, degree: usize) -> Result<PP, SetupLoadError> {
+        let ceremony: CeremonyJson =
+            serde_json::from_str(json).map_err(|error| SetupLoadError::InvalidJson(error.to_string()))?;
+
+        let (section, g1_points) = match basis {
+            G1Basis::Monomial => ("g1_monomial", ceremony.g1_monomial),
+            G1Basis::Lagrange => ("g1_lagrange", ceremony.g1_lagrange),
+        };
+        let g1_points = g1_points.ok_or(SetupLoadError::MissingSection(section))?;
+        let g2_points = ceremony.g2_monomial.ok_or(SetupLoadError::MissingSection("g2_monomial"))?;
+
+        if g1_points.is_empty() {
+            return Err(SetupLoadError::NoG1Points);
+        }
+        if degree >= g1_points.len() {
+            return Err(SetupLoadError::DegreeExceedsSetup {
+                requested: degree,
+                available: g1_points.len() - 1,
+            });
+        }
+        if g2_points.len() < 2 {
+            return Err(SetupLoadError::NotEnoughG2Points { found: g2_points.len() });
+        }
+
+        let points_in_g1 = g1_points[..=degree]
+            .iter()
+            .enumerate()
+            .map(|(index, point)| {
+                decode_line::<G1_COMPRESSED_SIZE>(point, index)
+                    .ok()
+                    .and_then(|bytes| group::decompress_g1(&bytes).ok())
+                    .ok_or(SetupLoadError::InvalidSectionPoint { section, index })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let points_in_g2 = g2_points
+            .iter()
+            .enumerate()
+            .map(|(index, point)| {
+                decode_line::<G2_COMPRESSED_SIZE>(point, index)
+                    .ok()
+                    .and_then(|bytes| group::decompress_g2(&bytes).ok())
+                    .ok_or(SetupLoadError::InvalidSectionPoint { section: "g2_monomial", index })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        checked_setup(points_in_g1, points_in_g2)
+    }
+
+    /// writes the setup in the layout [`PP::from_ceremony_json`] reads: the compressed G1 powers
+    /// as `g1_monomial`, the G2 powers as `g2_monomial`, and with a `domain` also the Lagrange
+    /// points over it (see [`PP::to_lagrange`]) as `g1_lagrange`
+    pub fn to_ceremony_json(&self, domain: Option<&Domain>) -> Result<String, SetupLoadError> {
+        let g1_lagrange = domain
+            .map(|domain| self.to_lagrange(domain))
+            .transpose()
+            .map_err(SetupLoadError::InvalidFormat)?
+            .map(|lagrange| lagrange.points_in_g1.iter().map(|point| ceremony_hex(&point.compress())).collect());
+
+        let ceremony = CeremonyJsonOut {
+            g1_monomial: self.points_in_g1.iter().map(|point| ceremony_hex(&point.compress())).collect(),
+            g1_lagrange,
+            g2_monomial: self.points_in_g2.iter().map(|point| ceremony_hex(&point.compress())).collect(),
+        };
+        serde_json::to_string_pretty(&ceremony).map_err(|error| SetupLoadError::InvalidJson(error.to_string()))
+    }
+}
+
+
+// ===================================
+// PP FILES
+// ===================================
+/// magic bytes at the start of a file written by [`PP::save_to_file`]
+const PP_FILE_MAGIC: &[u8; 4] = b"KZGF";
+/// current version of the file header
+const PP_FILE_VERSION: u8 = 1;
+/// curve identifier recorded in the file header
+const CURVE_BLS12_381: u8 = 1;
+/// magic + version + curve + degree + body length + SHA-256 of the body
+const PP_FILE_HEADER_SIZE: usize = 4 + 1 + 1 + 8 + 8 + 32;
+
+impl PP {
+    /// writes the setup to `path` as a file header followed by [`PP::to_bytes`]. The header holds
+    /// magic `KZGF`, a version byte, a curve identifier byte (1 for BLS12-381), the degree and the
+    /// body length as big-endian u64s, then the SHA-256 of the body.
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let body = self.to_bytes();
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(PP_FILE_MAGIC)?;
+        file.write_all(&[PP_FILE_VERSION, CURVE_BLS12_381])?;
+        file.write_all(&(self.max_degree() as u64).to_be_bytes())?;
+        file.write_all(&(body.len() as u64).to_be_bytes())?;
+        file.write_all(&Sha256::digest(&body))?;
+        file.write_all(&body)?;
+        file.flush()
+    }
+
+    /// reads a file written by [`PP::save_to_file`]. The body is hashed in a first streaming pass
+    /// and only parsed, point by point, if it matches the digest in the header; the file is never
+    /// held in memory as a whole.
+    pub fn load_from_file(path: impl AsRef<Path>) -> Result<PP, SetupLoadError> {
+        let mut reader = BufReader::new(File::open(path)?);
+
+        let mut header = [0u8; PP_FILE_HEADER_SIZE];
+        read_section_bytes(&mut reader, &mut header, "file header")?;
+        if &header[..4] != PP_FILE_MAGIC {
+            return Err(SetupLoadError::InvalidFormat(KZGErrors::InvalidSerializationMagic));
+        }
+        if header[4] != PP_FILE_VERSION {
+            return Err(SetupLoadError::UnsupportedVersion(header[4]));
+        }
+        if header[5] != CURVE_BLS12_381 {
+            return Err(SetupLoadError::UnsupportedCurve);
+        }
+        let degree = u64::from_be_bytes(header[6..14].try_into().unwrap());
+        let body_len = u64::from_be_bytes(header[14..22].try_into().unwrap());
+        let digest: [u8; 32] = header[22..].try_into().unwrap();
+
+        let mut hasher = Sha256::new();
+        let hashed = io::copy(&mut reader.by_ref().take(body_len), &mut hasher)?;
+        if hashed < body_len {
+            return Err(SetupLoadError::TruncatedSection("PP body"));
+        }
+        if !reader.fill_buf()?.is_empty() {
+            return Err(SetupLoadError::InvalidFormat(KZGErrors::TrailingBytes));
+        }
+        if <[u8; 32]>::from(hasher.finalize()) != digest {
+            return Err(SetupLoadError::InvalidFormat(KZGErrors::DigestMismatch));
+        }
+
+        reader.seek(SeekFrom::Start(PP_FILE_HEADER_SIZE as u64))?;
+        read_pp_body(&mut reader, degree, body_len)
+    }
+}
+
+/// parses [`PP::to_bytes`] from `reader` one point at a time; `body_len` bytes are known to follow
+fn read_pp_body(reader: &mut impl Read, degree: u64, body_len: u64) -> Result<PP, SetupLoadError> {
+    let invalid = SetupLoadError::InvalidFormat;
+
+    let mut header = [0u8; PP_HEADER_SIZE];
+    read_section_bytes(reader, &mut header, "PP body")?;
+    if &header[..4] != PP_MAGIC {
+        return Err(invalid(KZGErrors::InvalidSerializationMagic));
+    }
+    if header[4] != PP_VERSION {
+        return Err(invalid(KZGErrors::UnsupportedSerializationVersion(header[4])));
+    }
+    let body_degree = u64::from_be_bytes(header[5..13].try_into().unwrap());
+    let g2_count = u64::from_be_bytes(header[13..21].try_into().unwrap());
+    let body_digest: [u8; 32] = header[21..53].try_into().unwrap();
+    if body_degree != degree {
+        return Err(invalid(KZGErrors::InconsistentMaxDegree {
+            max_degree: degree as usize,
+            points: body_degree.saturating_add(1) as usize,
+        }));
+    }
+    if g2_count < 2 {
+        return Err(SetupLoadError::NotEnoughG2Points { found: g2_count as usize });
+    }
+
+    // the counts are bounded by the body actually read, so nothing huge is allocated
+    let expected_len = degree
+        .checked_add(1)
+        .and_then(|count| count.checked_mul(G1_COMPRESSED_SIZE as u64))
+        .and_then(|size| size.checked_add(g2_count.checked_mul(G2_COMPRESSED_SIZE as u64)?))
+        .and_then(|size| size.checked_add(PP_HEADER_SIZE as u64));
+    match expected_len {
+        Some(expected) if expected == body_len => {}
+        Some(expected) if expected < body_len => return Err(invalid(KZGErrors::TrailingBytes)),
+        _ => return Err(invalid(KZGErrors::TruncatedInput)),
+    }
+
+    let g1_count = degree as usize + 1;
+    let mut points_in_g1 = Vec::with_capacity(g1_count);
+    let mut bytes = [0u8; G1_COMPRESSED_SIZE];
+    for index in 0..g1_count {
+        read_section_bytes(reader, &mut bytes, "PP body")?;
+        let point = group::decompress_g1(&bytes).map_err(|error| invalid(kzg_commit::point_error(error, index)))?;
+        points_in_g1.push(point);
+    }
+
+    let mut points_in_g2 = Vec::with_capacity(g2_count as usize);
+    let mut bytes = [0u8; G2_COMPRESSED_SIZE];
+    for index in g1_count..g1_count + g2_count as usize {
+        read_section_bytes(reader, &mut bytes, "PP body")?;
+        let point = group::decompress_g2(&bytes).map_err(|error| invalid(kzg_commit::point_error(error, index)))?;
+        points_in_g2.push(point);
+    }
+
+    let public_parameter = checked_setup(points_in_g1, points_in_g2)?;
+    if public_parameter.digest() != body_digest {
+        return Err(invalid(KZGErrors::DigestMismatch));
+    }
+    Ok(public_parameter)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg_commit::KZG;
+    use crate::polynomial::{Fr, Polynomial};
+    use std::io::Cursor;
+
+    /// a trusted_setup.txt for the given setup
+    fn trusted_setup_text(pp: &PP) -> String {
+        let mut text = format!("{}\n2\n", pp.points_in_g1.len());
+        for point in &pp.points_in_g1 {
+            text.push_str(&hex::encode(point.compress()));
+            text.push('\n');
+        }
+        text.push_str(&hex::encode(P2::generator().compress()));
+        text.push('\n');
+        text.push_str(&hex::encode(pp.point_in_g2().compress()));
+        text.push('\n');
+        text
+    }
+
+    #[test]
+    fn loads_synthetic_setup() {
+        let kzg = KZG::new(&[0x31u8; 32], 7).unwrap();
+        let text = trusted_setup_text(&kzg.public_parameter);
+
+        let loaded = PP::from_trusted_setup_reader(Cursor::new(text)).unwrap();
+        assert_eq!(loaded, kzg.public_parameter);
+
+        let polynomial = Polynomial::from(vec![1u64, 2, 3, 4]);
+        let commitment = KZG::commit(&loaded, &polynomial).unwrap();
+        let z = Fr::from_u64(99);
+        assert!(commitment.open_at(z).unwrap().verify(&z, &commitment));
+    }
+
+    #[test]
+    fn loads_from_file() {
+        let kzg = KZG::new(&[0x32u8; 32], 3).unwrap();
+        let path = std::env::temp_dir().join(format!("kzg-trusted-setup-{}.txt", std::process::id()));
+        std::fs::write(&path, trusted_setup_text(&kzg.public_parameter)).unwrap();
+
+        let loaded = PP::from_trusted_setup_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap(), kzg.public_parameter);
+
+        assert!(matches!(
+            PP::from_trusted_setup_file("/definitely/not/a/setup.txt"),
+            Err(SetupLoadError::Io(_))
+        ));
+    }
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("kzg-pp-file-{}-{}.bin", name, std::process::id()))
+    }
+
+    #[test]
+    fn pp_file_round_trip() {
+        let pp = KZG::new(&[0x38u8; 32], 20).unwrap().public_parameter;
+        let path = temp_path("round-trip");
+        pp.save_to_file(&path).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        let loaded = PP::load_from_file(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.unwrap(), pp);
+        assert_eq!(bytes.len(), PP_FILE_HEADER_SIZE + pp.to_bytes().len());
+        assert_eq!(&bytes[PP_FILE_HEADER_SIZE..], &pp.to_bytes()[..]);
+    }
+
+    #[test]
+    fn pp_file_rejects_corruption() {
+        let pp = KZG::new(&[0x39u8; 32], 8).unwrap().public_parameter;
+        let path = temp_path("corrupted");
+        pp.save_to_file(&path).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        let load = |bytes: &[u8]| {
+            std::fs::write(&path, bytes).unwrap();
+            PP::load_from_file(&path)
+        };
+
+        // clearing a compression flag would fail point parsing, but the digest check comes first
+        let mut flipped = bytes.clone();
+        flipped[PP_FILE_HEADER_SIZE + PP_HEADER_SIZE + 3 * G1_COMPRESSED_SIZE] &= 0x7f;
+        assert!(matches!(load(&flipped), Err(SetupLoadError::InvalidFormat(KZGErrors::DigestMismatch))));
+
+        // cut in the middle of the fifth G1 point
+        let cut = PP_FILE_HEADER_SIZE + PP_HEADER_SIZE + 4 * G1_COMPRESSED_SIZE + 20;
+        assert!(matches!(load(&bytes[..cut]), Err(SetupLoadError::TruncatedSection("PP body"))));
+        assert!(matches!(load(&bytes[..10]), Err(SetupLoadError::TruncatedSection("file header"))));
+
+        let mut next_version = bytes.clone();
+        next_version[4] = 2;
+        assert!(matches!(load(&next_version), Err(SetupLoadError::UnsupportedVersion(2))));
+
+        let mut other_curve = bytes.clone();
+        other_curve[5] = 2;
+        assert!(matches!(load(&other_curve), Err(SetupLoadError::UnsupportedCurve)));
+
+        let trailing = [&bytes[..], &[0]].concat();
+        assert!(matches!(load(&trailing), Err(SetupLoadError::InvalidFormat(KZGErrors::TrailingBytes))));
+
+        // a plain PP::to_bytes isn't a file
+        assert!(matches!(
+            load(&pp.to_bytes()),
+            Err(SetupLoadError::InvalidFormat(KZGErrors::InvalidSerializationMagic))
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn error_messages() {
+        use std::error::Error;
+
+        let error = SetupLoadError::InvalidFormat(KZGErrors::DigestMismatch);
+        assert_eq!(
+            error.to_string(),
+            "invalid serialized setup: serialized parameters do not match the digest in their header"
+        );
+        assert!(error.source().unwrap().downcast_ref::<KZGErrors>().is_some());
+        assert_eq!(SetupLoadError::InvalidHex { line: 4 }.to_string(), "line 4: not a hex point of the right length");
+
+        let boxed: Box<dyn Error> = PP::load_from_file(std::env::temp_dir().join("kzg-missing-pp")).unwrap_err().into();
+        assert!(boxed.to_string().starts_with("reading the setup failed: "));
+    }
+
+    #[test]
+    fn rejects_corrupted_point() {
+        let kzg = KZG::new(&[0x31u8; 32], 3).unwrap();
+        let text = trusted_setup_text(&kzg.public_parameter);
+        let mut lines: Vec<String> = text.lines().map(String::from).collect();
+
+        // clear the compression flag of the second G1 point (line 4)
+        let mut bytes = hex::decode(&lines[3]).unwrap();
+        bytes[0] &= 0x7f;
+        lines[3] = hex::encode(bytes);
+        let result = PP::from_trusted_setup_reader(Cursor::new(lines.join("\n")));
+        assert!(matches!(result, Err(SetupLoadError::InvalidPoint { line: 4 })));
+
+        lines[3] = "not hex".to_string();
+        let result = PP::from_trusted_setup_reader(Cursor::new(lines.join("\n")));
+        assert!(matches!(result, Err(SetupLoadError::InvalidHex { line: 4 })));
+
+        // on the curve but outside the subgroup
+        lines[3] = hex::encode(group::non_subgroup_g1());
+        let result = PP::from_trusted_setup_reader(Cursor::new(lines.join("\n")));
+        assert!(matches!(result, Err(SetupLoadError::InvalidPoint { line: 4 })));
+
+        // the identity where [1]_1 belongs (line 3)
+        lines[3] = hex::encode(kzg.public_parameter.points_in_g1[1].compress());
+        lines[2] = hex::encode(P1::default().compress());
+        let result = PP::from_trusted_setup_reader(Cursor::new(lines.join("\n")));
+        assert!(matches!(result, Err(SetupLoadError::IdentityInGeneratorSlot)));
+    }
+
+    #[test]
+    fn rejects_mismatched_counts() {
+        let kzg = KZG::new(&[0x31u8; 32], 3).unwrap();
+        let text = trusted_setup_text(&kzg.public_parameter);
+
+        // the file ends before all announced G1 points
+        let truncated = text.lines().take(5).collect::<Vec<_>>().join("\n");
+        let result = PP::from_trusted_setup_reader(Cursor::new(truncated));
+        assert!(matches!(
+            result,
+            Err(SetupLoadError::CountMismatch { group: "G1", expected: 4, found: 3 })
+        ));
+
+        let more_g2 = text.replacen("\n2\n", "\n3\n", 1);
+        let result = PP::from_trusted_setup_reader(Cursor::new(more_g2));
+        assert!(matches!(
+            result,
+            Err(SetupLoadError::CountMismatch { group: "G2", expected: 3, found: 2 })
+        ));
+
+        let mut extra_line = text.clone();
+        extra_line.push_str(&hex::encode(P2::generator().compress()));
+        let result = PP::from_trusted_setup_reader(Cursor::new(extra_line));
+        assert!(matches!(result, Err(SetupLoadError::CountMismatch { found: 3, .. })));
+
+        let bad_count = text.replacen("4\n", "four\n", 1);
+        let result = PP::from_trusted_setup_reader(Cursor::new(bad_count));
+        assert!(matches!(result, Err(SetupLoadError::InvalidCount { line: 1 })));
+
+        let one_g2 = text.replacen("\n2\n", "\n1\n", 1);
+        let result = PP::from_trusted_setup_reader(Cursor::new(one_g2));
+        assert!(matches!(result, Err(SetupLoadError::NotEnoughG2Points { found: 1 })));
+    }
+
+    /// the curve point as `.ptau` stores it: coordinates in little-endian Montgomery form
+    fn ptau_coordinates(uncompressed: &[u8], swap_c0_c1: bool) -> Vec<u8> {
+        let modulus = fq_modulus();
+        let r = (BigUint::from(1u32) << (8 * FQ_SIZE)) % &modulus;
+        let coordinates: Vec<&[u8]> = uncompressed.chunks_exact(FQ_SIZE).collect();
+
+        let mut out = Vec::new();
+        for i in 0..coordinates.len() {
+            let source = if swap_c0_c1 { coordinates[i ^ 1] } else { coordinates[i] };
+            let mut le = (BigUint::from_bytes_be(source) * &r % &modulus).to_bytes_le();
+            le.resize(FQ_SIZE, 0);
+            out.extend_from_slice(&le);
+        }
+        out
+    }
+
+    fn ptau_g1(point: &P1) -> Vec<u8> {
+        let mut affine = blst::blst_p1_affine::default();
+        let mut bytes = [0u8; G1_UNCOMPRESSED_SIZE];
+        unsafe {
+            blst::blst_p1_uncompress(&mut affine, point.compress().as_ptr());
+            blst::blst_p1_affine_serialize(bytes.as_mut_ptr(), &affine);
+        }
+        ptau_coordinates(&bytes, false)
+    }
+
+    fn ptau_g2(point: &P2) -> Vec<u8> {
+        let mut affine = blst::blst_p2_affine::default();
+        let mut bytes = [0u8; G2_UNCOMPRESSED_SIZE];
+        unsafe {
+            blst::blst_p2_uncompress(&mut affine, point.compress().as_ptr());
+            blst::blst_p2_affine_serialize(bytes.as_mut_ptr(), &affine);
+        }
+        ptau_coordinates(&bytes, true)
+    }
+
+    fn ptau_section(kind: u32, data: &[u8]) -> Vec<u8> {
+        let mut section = kind.to_le_bytes().to_vec();
+        section.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        section.extend_from_slice(data);
+        section
+    }
+
+    /// a `.ptau` file of power `power` for the given tau, with `modulus` in its header
+    fn ptau_file(tau: &[u8; 32], power: u32, n8: u32, modulus: &[u8]) -> Vec<u8> {
+        let g1_count = (1usize << (power + 1)) - 1;
+        let g2_count = 1usize << power;
+        let g1 = KZG::new(tau, g1_count - 1).unwrap().public_parameter;
+        let tau = crate::scalar::fr_from_bytes_be(tau);
+
+        let mut header = n8.to_le_bytes().to_vec();
+        header.extend_from_slice(modulus);
+        header.extend_from_slice(&power.to_le_bytes());
+        header.extend_from_slice(&power.to_le_bytes());
+
+        let tau_g1: Vec<u8> = g1.points_in_g1.iter().flat_map(ptau_g1).collect();
+        let mut tau_g2 = Vec::new();
+        let mut power_of_tau = Fr::from_u64(1);
+        for _ in 0..g2_count {
+            tau_g2.extend(ptau_g2(&(power_of_tau * P2::generator())));
+            power_of_tau *= tau;
+        }
+
+        let mut file = b"ptau".to_vec();
+        file.extend_from_slice(&1u32.to_le_bytes());
+        file.extend_from_slice(&4u32.to_le_bytes());
+        file.extend(ptau_section(1, &header));
+        file.extend(ptau_section(2, &tau_g1));
+        file.extend(ptau_section(3, &tau_g2));
+        // alphaTauG1, which the loader never needs to read
+        file.extend(ptau_section(4, &[0u8; 96]));
+        file
+    }
+
+    fn bls12_381_ptau(power: u32) -> Vec<u8> {
+        let mut modulus = fq_modulus().to_bytes_le();
+        modulus.resize(FQ_SIZE, 0);
+        ptau_file(&[0x61u8; 32], power, FQ_SIZE as u32, &modulus)
+    }
+
+    #[test]
+    fn loads_ptau() {
+        let file = bls12_381_ptau(2);
+        let expected = KZG::new(&[0x61u8; 32], 4).unwrap().public_parameter;
+
+        let loaded = PP::from_ptau_reader(Cursor::new(&file), 4).unwrap();
+        assert_eq!(loaded, expected);
+
+        let polynomial = Polynomial::from(vec![2u64, 7, 1, 8, 2]);
+        let commitment = KZG::commit(&loaded, &polynomial).unwrap();
+        let z = Fr::from_u64(1729);
+        assert!(commitment.open_at(z).unwrap().verify(&z, &commitment));
+
+        // a smaller degree only reads the start of the file
+        let mut reader = Cursor::new(&file);
+        let small = PP::from_ptau_reader(&mut reader, 1).unwrap();
+        assert_eq!(small.points_in_g1.len(), 2);
+        assert!((reader.position() as usize) < file.len());
+
+        assert!(matches!(
+            PP::from_ptau_reader(Cursor::new(&file), 7),
+            Err(SetupLoadError::DegreeExceedsSetup { requested: 7, available: 6 })
+        ));
+    }
+
+    #[test]
+    fn rejects_wrong_curve_ptau() {
+        // BN254's base field, as written by snarkjs for bn128 ceremonies
+        let bn254 = BigUint::parse_bytes(
+            b"30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47",
+            16,
+        )
+        .unwrap()
+        .to_bytes_le();
+        let file = ptau_file(&[0x61u8; 32], 1, 32, &bn254);
+        assert!(matches!(
+            PP::from_ptau_reader(Cursor::new(file), 1),
+            Err(SetupLoadError::UnsupportedCurve)
+        ));
+
+        // right size, wrong modulus
+        let mut modulus = fq_modulus().to_bytes_le();
+        modulus.resize(FQ_SIZE, 0);
+        modulus[0] ^= 2;
+        let file = ptau_file(&[0x61u8; 32], 1, FQ_SIZE as u32, &modulus);
+        assert!(matches!(
+            PP::from_ptau_reader(Cursor::new(file), 1),
+            Err(SetupLoadError::UnsupportedCurve)
+        ));
+    }
+
+    #[test]
+    fn rejects_malformed_ptau() {
+        let file = bls12_381_ptau(1);
+
+        assert!(matches!(
+            PP::from_ptau_reader(Cursor::new(b"zkey\x01\x00\x00\x00".to_vec()), 1),
+            Err(SetupLoadError::InvalidPtauHeader)
+        ));
+
+        // cut in the middle of the tauG1 section
+        let header_section = 12 + 4 + FQ_SIZE + 8;
+        let truncated = &file[..12 + header_section + 12 + 100];
+        assert!(matches!(
+            PP::from_ptau_reader(Cursor::new(truncated), 2),
+            Err(SetupLoadError::TruncatedSection("tauG1"))
+        ));
+
+        // corrupt the y coordinate of [tau]_1
+        let mut corrupted = file.clone();
+        corrupted[12 + header_section + 12 + 96 + 50] ^= 1;
+        assert!(matches!(
+            PP::from_ptau_reader(Cursor::new(corrupted), 2),
+            Err(SetupLoadError::InvalidSectionPoint { section: "tauG1", index: 1 })
+        ));
+    }
+
+    /// a trimmed ceremony file for tau = 0x5151..51 over a size-4 domain
+    #[cfg(feature = "serde")]
+    const CEREMONY_FIXTURE: &str = r#"{
+    "g1_monomial": [
+        "0x97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb",
+        "0x97f574cb7d9d95ce0d3e170a9b04b4be68a34d4b0c9ef70e113aba075f071baf84c77bbfedd55bfa3587f24ed9c0f3d2",
+        "0x982fc6d6c5a1f6a1a2d8fec069c65b4692f2b0f0662143070c9712a57289290883477ef2d8a9f4eaaa43665ada4bf82e",
+        "0xaa247d2abe6c396c4ded0faf3b9c35b1cfbfc1d99d5461638f7208d43509f1c53497fb6937650ccf689b1d0a505f2ae5"
+    ],
+    "g1_lagrange": [
+        "0xb8a59c1f3d0dd9a6e175a3d35ee15a0bf6ef52ab1605871c24f7b4376ba9431388f9fb6eec5b46e939dfe5321fc6f032",
+        "0x8711cadf1be6cc61af0c79f5f96c3b2f52311b67428c029b2a8c4bd5e78e4c07003571084ba8e6cd8779bd8556c11857",
+        "0xb7b2e387fcc3c186301e9ed9835ce6eebb9b50253f772cd4fb92462ddfd189a37a3c1f2c583b6e11da615e2ae1076c43",
+        "0xa00692a7494fb8077aa23d5505b518f6e60b09825dce54499d94e981759c4bb79971a53f028c5c0166658e540aecf2f6"
+    ],
+    "g2_monomial": [
+        "0x93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8",
+        "0x978ce73a950d1770f5a7d892c8552a754488afd7c1a5f88fc5a9be6a658aeebc51c0adf48f6c9d11cf802461354a03340b883543b7e7cbc93ce8a89d3b9d71521ac890eb64ccbb2424bfe5e744e1a9c829b40cae39dfe03e7bb2e1a4b45c73e1"
+    ]
+}"#;
+
+    /// `to_ceremony_json(None)` of the degree-4 setup for tau = 0x5151..51
+    #[cfg(feature = "serde")]
+    const CEREMONY_GOLDEN: &str = r#"{
+  "g1_monomial": [
+    "0x97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb",
+    "0x97f574cb7d9d95ce0d3e170a9b04b4be68a34d4b0c9ef70e113aba075f071baf84c77bbfedd55bfa3587f24ed9c0f3d2",
+    "0x982fc6d6c5a1f6a1a2d8fec069c65b4692f2b0f0662143070c9712a57289290883477ef2d8a9f4eaaa43665ada4bf82e",
+    "0xaa247d2abe6c396c4ded0faf3b9c35b1cfbfc1d99d5461638f7208d43509f1c53497fb6937650ccf689b1d0a505f2ae5",
+    "0x9076e5d9bc8c01425e7b2ef351abd33d0965cab0659c8e081401e5b0d849d2e8e30d7aafe915b0dc34819d92859dc826"
+  ],
+  "g2_monomial": [
+    "0x93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8",
+    "0x978ce73a950d1770f5a7d892c8552a754488afd7c1a5f88fc5a9be6a658aeebc51c0adf48f6c9d11cf802461354a03340b883543b7e7cbc93ce8a89d3b9d71521ac890eb64ccbb2424bfe5e744e1a9c829b40cae39dfe03e7bb2e1a4b45c73e1"
+  ]
+}"#;
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn loads_ceremony_json_monomial() {
+        let expected = KZG::new(&[0x51u8; 32], 3).unwrap().public_parameter;
+        let loaded = PP::from_ceremony_json(CEREMONY_FIXTURE, G1Basis::Monomial, 3).unwrap();
+        assert_eq!(loaded, expected);
+
+        let polynomial = Polynomial::from(vec![6u64, 0, 2, 8]);
+        let commitment = KZG::commit(&loaded, &polynomial).unwrap();
+        let z = Fr::from_u64(31337);
+        let opening = commitment.open_at(z).unwrap();
+        assert!(opening.verify(&z, &commitment));
+
+        // the same proof verifies against the in-crate setup
+        let reference = KZG::commit(&expected, &polynomial).unwrap();
+        assert!(opening.verify(&z, &reference));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn truncates_ceremony_json() {
+        let loaded = PP::from_ceremony_json(CEREMONY_FIXTURE, G1Basis::Monomial, 1).unwrap();
+        let full = KZG::new(&[0x51u8; 32], 3).unwrap().public_parameter;
+
+        assert_eq!(loaded.points_in_g1.len(), 2);
+        assert_eq!(loaded.points_in_g1[1].compress(), full.points_in_g1[1].compress());
+
+        assert!(matches!(
+            PP::from_ceremony_json(CEREMONY_FIXTURE, G1Basis::Monomial, 4),
+            Err(SetupLoadError::DegreeExceedsSetup { requested: 4, available: 3 })
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn loads_ceremony_json_lagrange() {
+        let loaded = PP::from_ceremony_json(CEREMONY_FIXTURE, G1Basis::Lagrange, 3).unwrap();
+
+        // the Lagrange basis sums to 1, so its points sum to the generator
+        let sum = loaded.points_in_g1.iter().fold(P1::default(), |acc, point| acc + *point);
+        assert_eq!(sum.compress(), P1::generator().compress());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn writes_ceremony_json() {
+        let pp = KZG::new(&[0x51u8; 32], 4).unwrap().public_parameter;
+        let json = pp.to_ceremony_json(None).unwrap();
+        assert_eq!(json, CEREMONY_GOLDEN);
+        assert_eq!(PP::from_ceremony_json(&json, G1Basis::Monomial, 4).unwrap(), pp);
+        assert!(matches!(
+            PP::from_ceremony_json(&json, G1Basis::Lagrange, 3),
+            Err(SetupLoadError::MissingSection("g1_lagrange"))
+        ));
+
+        let domain = Domain::new(4).unwrap();
+        let json = pp.to_ceremony_json(Some(&domain)).unwrap();
+        assert_eq!(PP::from_ceremony_json(&json, G1Basis::Monomial, 4).unwrap(), pp);
+        let lagrange = PP::from_ceremony_json(&json, G1Basis::Lagrange, 3).unwrap();
+        let expected = pp.to_lagrange(&domain).unwrap();
+        assert!(lagrange.points_in_g1.iter().zip(&expected.points_in_g1).all(|(a, b)| a.compress() == b.compress()));
+
+        // the same tau as the fixture, so the first four points match it
+        let fixture = PP::from_ceremony_json(CEREMONY_FIXTURE, G1Basis::Lagrange, 3).unwrap();
+        assert_eq!(lagrange, fixture);
+
+        assert!(matches!(
+            pp.to_ceremony_json(Some(&Domain::new(8).unwrap())),
+            Err(SetupLoadError::InvalidFormat(KZGErrors::DegreeExceedsSetup { requested: 7, available: 4 }))
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn rejects_malformed_ceremony_json() {
+        assert!(matches!(
+            PP::from_ceremony_json("{", G1Basis::Monomial, 0),
+            Err(SetupLoadError::InvalidJson(_))
+        ));
+        assert!(matches!(
+            PP::from_ceremony_json(r#"{"g2_monomial": []}"#, G1Basis::Lagrange, 0),
+            Err(SetupLoadError::MissingSection("g1_lagrange"))
+        ));
+
+        let corrupted = CEREMONY_FIXTURE.replacen("0x97f1", "0x17f1", 1);
+        assert!(matches!(
+            PP::from_ceremony_json(&corrupted, G1Basis::Monomial, 3),
+            Err(SetupLoadError::InvalidSectionPoint { section: "g1_monomial", index: 0 })
+        ));
+    }
+}
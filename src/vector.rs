@@ -0,0 +1,149 @@
+//! KZG as a vector commitment: a vector `v` of length `n` is committed as the polynomial taking
+//! `v[i]` at the `i`-th point `w^i` of a domain of size `n`, so opening at `w^i` proves the `i`-th
+//! entry.
+//!
+//! Everything stays in evaluation form over a [`LagrangePP`]: the commitment is `sum v[i] *
+//! [L_i(tau)]_1`, and the opening quotient `(f(X) - v[i]) / (X - w^i)` is computed from its values
+//! on the domain, `(v[j] - v[i]) / (w^j - w^i)` for `j != i` and `f'(w^i)` at `w^i` itself.
+
+use std::fmt;
+
+use oblast_demo::{Fr, P1};
+
+use crate::domain;
+use crate::kzg_commit::{KZGErrors, Opening};
+use crate::lagrange::LagrangePP;
+
+/// A commitment to a vector of at most as many entries as the domain of its setup has points;
+/// missing entries are zero
+pub struct VectorCommitment<'a> {
+    /// The commitment point
+    pub element: P1,
+    /// The vector committed to
+    pub vector: &'a [Fr],
+    /// Lagrange-form setup used during the commitment process
+    pub public_parameter: &'a LagrangePP,
+}
+
+impl<'a> VectorCommitment<'a> {
+    /// commits to `vector`, which can't be longer than the domain of `lpp`
+    pub fn commit(lpp: &'a LagrangePP, vector: &'a [Fr]) -> Result<VectorCommitment<'a>, KZGErrors> {
+        Ok(VectorCommitment {
+            element: lpp.commit(vector)?,
+            vector,
+            public_parameter: lpp,
+        })
+    }
+
+    /// the entry at `index` with a proof that it is the value at `w^index`
+    pub fn open_index(&self, index: usize) -> Result<Opening, KZGErrors> {
+        let domain = self.public_parameter.domain();
+        if index >= domain.size() {
+            return Err(KZGErrors::IndexOutOfRange { index, size: domain.size() });
+        }
+
+        let mut values = self.vector.to_vec();
+        values.resize(domain.size(), Fr::default());
+        let value = values[index];
+        let points = domain.elements();
+        let z = points[index];
+
+        // 1 / (w^j - w^i), with a placeholder at i, which isn't divided by
+        let mut denominators: Vec<Fr> = points
+            .iter()
+            .enumerate()
+            .map(|(j, point)| if j == index { Fr::from_u64(1) } else { *point - z })
+            .collect();
+        domain::batch_inverse(&mut denominators);
+
+        let mut quotient: Vec<Fr> = values
+            .iter()
+            .zip(&denominators)
+            .map(|(entry, inverse)| (*entry - value) * *inverse)
+            .collect();
+        // f'(w^i) = sum_(j != i) (v[j] - v[i]) * w^(j - i) / (w^i - w^j), that is minus the sum of
+        // the other quotient values times w^j / w^i
+        let derivative = quotient
+            .iter()
+            .zip(&points)
+            .enumerate()
+            .filter(|(j, _)| *j != index)
+            .fold(Fr::default(), |sum, (_, (quotient, point))| sum + *quotient * *point);
+        quotient[index] = -(derivative / z);
+
+        Ok(Opening {
+            value,
+            proof: self.public_parameter.commit(&quotient)?,
+        })
+    }
+
+    /// checks `opening` proves its value is the entry at `index` of the vector committed to by
+    /// `commitment`; false for an index outside the domain of `lpp`
+    pub fn verify_index(lpp: &LagrangePP, commitment: &P1, index: usize, opening: &Opening) -> bool {
+        if index >= lpp.domain().size() {
+            return false;
+        }
+        opening.verify_against(lpp.domain().element(index), *commitment, lpp.point_in_g2())
+    }
+}
+
+/// prints the compressed commitment point and the length of the vector, leaving out the borrowed
+/// vector and setup
+impl fmt::Debug for VectorCommitment<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VectorCommitment")
+            .field("element", &hex::encode(self.element.compress()))
+            .field("len", &self.vector.len())
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Domain;
+    use crate::kzg_commit::KZG;
+
+    fn setup(size: usize) -> LagrangePP {
+        let pp = KZG::new(&[0x56u8; 32], size - 1).unwrap().public_parameter;
+        pp.to_lagrange(&Domain::new(size).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn proves_an_entry() {
+        let lpp = setup(4);
+        let vector: Vec<Fr> = [10u64, 20, 30, 40].iter().map(|x| Fr::from_u64(*x)).collect();
+        let commitment = VectorCommitment::commit(&lpp, &vector).unwrap();
+
+        let opening = commitment.open_index(2).unwrap();
+        assert_eq!(opening.value, Fr::from_u64(30));
+        assert!(VectorCommitment::verify_index(&lpp, &commitment.element, 2, &opening));
+
+        // not the entry at another index, and not another value at this one
+        assert!(!VectorCommitment::verify_index(&lpp, &commitment.element, 1, &opening));
+        let forged = Opening { value: Fr::from_u64(31), proof: opening.proof };
+        assert!(!VectorCommitment::verify_index(&lpp, &commitment.element, 2, &forged));
+        assert!(!VectorCommitment::verify_index(&lpp, &commitment.element, 4, &opening));
+        assert!(matches!(commitment.open_index(4), Err(KZGErrors::IndexOutOfRange { index: 4, size: 4 })));
+    }
+
+    #[test]
+    fn every_entry_of_a_padded_vector() {
+        let lpp = setup(16);
+        let vector: Vec<Fr> = (0..11u64).map(|i| Fr::from_u64(i * i * 7 + 1)).collect();
+        let commitment = VectorCommitment::commit(&lpp, &vector).unwrap();
+
+        for index in 0..16 {
+            let opening = commitment.open_index(index).unwrap();
+            assert_eq!(opening.value, vector.get(index).copied().unwrap_or_default());
+            assert!(VectorCommitment::verify_index(&lpp, &commitment.element, index, &opening), "{}", index);
+        }
+
+        let too_long = vec![Fr::default(); 17];
+        assert!(matches!(
+            VectorCommitment::commit(&lpp, &too_long),
+            Err(KZGErrors::TooManyEvaluations { evaluations: 17, domain_size: 16 })
+        ));
+    }
+}
@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use kzg_impl::kzg_commit::{CommitmentPoint, KZG};
+use kzg_impl::polynomial::{Fr, Polynomial};
+
+fn polynomial(coefficients: &[u64]) -> Polynomial {
+    Polynomial::from(coefficients)
+}
+
+#[test]
+fn commit_open_verify_with_known_tau() {
+    let kzg = KZG::new(&[11u8; 32], 16).unwrap();
+    assert_eq!(kzg.degree(), 16);
+
+    let polynomial = polynomial(&[1, 2, 3, 4, 7, 7, 7, 7, 13, 13, 13, 13, 13, 13, 13, 13]);
+    let commitment = KZG::commit(&kzg.public_parameter, &polynomial).unwrap();
+
+    for z in [0u64, 1, 15, 1_000_003] {
+        let point = Fr::from_u64(z);
+        let opening = commitment.open_at(point).unwrap();
+
+        assert_eq!(opening.value, polynomial.evalaute(point));
+        assert!(opening.verify(&point, &commitment));
+    }
+}
+
+#[test]
+fn setup_matches_new() {
+    let tau = [3u8; 32];
+
+    let from_new = KZG::new(&tau, 8).unwrap();
+    let from_setup = KZG::setup(&tau, 8).unwrap();
+
+    assert_eq!(from_new, from_setup);
+}
+
+#[test]
+fn commit_open_verify_with_random_tau() {
+    let kzg = KZG::new_rand(5).unwrap();
+    assert_eq!(kzg.degree(), 5);
+    assert_eq!(kzg.public_parameter.points_in_g1.len(), 6);
+
+    let polynomial = polynomial(&[9, 8, 7, 6, 5, 4]);
+    let commitment = KZG::commit(&kzg.public_parameter, &polynomial).unwrap();
+
+    let point = Fr::from_u64(42);
+    let opening = commitment.open_at(point).unwrap();
+    assert!(opening.verify(&point, &commitment));
+
+    // a proof for one point must not verify at another
+    assert!(!opening.verify(&Fr::from_u64(43), &commitment));
+}
+
+#[test]
+fn fold_two_commitments_with_a_random_challenge() {
+    let pp = Arc::new(KZG::new(&[21u8; 32], 8).unwrap().public_parameter);
+    let f = KZG::commit_arc(Arc::clone(&pp), polynomial(&[1, 2, 3, 4, 5])).unwrap();
+    let g = KZG::commit_arc(Arc::clone(&pp), polynomial(&[8, 0, 0, 6])).unwrap();
+
+    let mut bytes = [0u8; 64];
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut bytes);
+    let r = kzg_impl::scalar::reduce_mod_order(&bytes);
+
+    // the prover opens f + r * g, the verifier folds the two commitment points itself
+    let folded = f.checked_add(&(&g * r)).unwrap();
+    let z = Fr::from_u64(1234);
+    let opening = folded.open_at(z).unwrap();
+
+    let folded_point = CommitmentPoint::from(&f) + r * CommitmentPoint::from(&g);
+    assert!(opening.verify_against(z, folded_point.0, pp.point_in_g2()));
+    assert_eq!(opening.value, f.open_at(z).unwrap().value + r * g.open_at(z).unwrap().value);
+
+    // a folding with another challenge doesn't match
+    let other = CommitmentPoint::from(&f) + (r + Fr::from_u64(1)) * CommitmentPoint::from(&g);
+    assert!(!opening.verify_against(z, other.0, pp.point_in_g2()));
+}